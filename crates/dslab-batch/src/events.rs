@@ -0,0 +1,41 @@
+//! Events of batch scheduler.
+
+use serde::Serialize;
+
+use dslab_core::component::Id;
+
+use crate::job::{Job, JobFailReason};
+
+/// Request to submit a job.
+///
+/// The notifications about the job start and completion are sent to the component which submitted the job.
+#[derive(Clone, Serialize)]
+pub struct SubmitJob {
+    /// Submitted job.
+    pub job: Job,
+}
+
+/// Job is started.
+#[derive(Clone, Serialize)]
+pub struct JobStarted {
+    /// Id of the job.
+    pub id: u64,
+    /// Ids of compute resources and numbers of cores used by the job.
+    pub allocation: Vec<(Id, u32)>,
+}
+
+/// Job is completed successfully.
+#[derive(Clone, Serialize)]
+pub struct JobFinished {
+    /// Id of the job.
+    pub id: u64,
+}
+
+/// Job is failed.
+#[derive(Clone, Serialize)]
+pub struct JobFailed {
+    /// Id of the job.
+    pub id: u64,
+    /// Reason for failure.
+    pub reason: JobFailReason,
+}
@@ -0,0 +1,87 @@
+//! Batch job and its execution status.
+
+use serde::Serialize;
+
+use dslab_core::component::Id;
+
+/// Batch job description.
+///
+/// The job runs on the specified number of cores, which can be located on different nodes,
+/// and reserves the specified amount of memory per each core. The job work is evenly split between the cores
+/// assuming linear speedup, so on cores with speed `s` the job runs for `flops / (cores * s)` seconds.
+#[derive(Clone, Debug, Serialize)]
+pub struct Job {
+    /// Unique job id.
+    pub id: u64,
+    /// Job submission time, used when the job is submitted via
+    /// [`BatchScheduler::submit_jobs`](crate::scheduler::BatchScheduler::submit_jobs).
+    pub submit_time: f64,
+    /// Number of requested cores.
+    pub cores: u32,
+    /// Amount of requested memory per core.
+    pub memory: u64,
+    /// Total computation size.
+    pub flops: f64,
+    /// User estimate of the job run time (requested wall time).
+    ///
+    /// The estimate is used by the scheduler to plan the execution of queued jobs,
+    /// and the job is killed if its run time exceeds the estimate.
+    pub walltime: f64,
+    /// Job priority, the jobs with higher priority are placed closer to the queue head.
+    pub priority: i64,
+}
+
+/// Reason for job failure.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum JobFailReason {
+    /// Job requires more resources than the cluster can provide.
+    NotEnoughResources,
+    /// Job run time exceeded the requested wall time.
+    WalltimeExceeded,
+    /// One of the nodes running the job has failed.
+    ResourceFailure,
+}
+
+/// Job status.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum JobStatus {
+    /// Job is waiting in the queue.
+    Queued,
+    /// Job is running.
+    Running,
+    /// Job is completed successfully.
+    Completed,
+    /// Job is failed.
+    Failed(JobFailReason),
+}
+
+/// Information about the submitted job.
+#[derive(Clone, Debug, Serialize)]
+pub struct JobInfo {
+    /// Job description.
+    pub job: Job,
+    /// Job status.
+    pub status: JobStatus,
+    /// Time of job submission to the scheduler.
+    pub submit_time: f64,
+    /// Time of job start (if started).
+    pub start_time: Option<f64>,
+    /// Time of job completion or failure (if finished).
+    pub finish_time: Option<f64>,
+    /// Ids of compute resources and numbers of cores used by the job (if started).
+    pub allocation: Vec<(Id, u32)>,
+}
+
+impl JobInfo {
+    /// Returns the time spent by the job in the queue (if started).
+    pub fn wait_time(&self) -> Option<f64> {
+        self.start_time.map(|start_time| start_time - self.submit_time)
+    }
+
+    /// Returns the job run time (if finished after start).
+    pub fn run_time(&self) -> Option<f64> {
+        self.start_time
+            .zip(self.finish_time)
+            .map(|(start_time, finish_time)| finish_time - start_time)
+    }
+}
@@ -0,0 +1,11 @@
+#![warn(missing_docs)]
+#![doc = include_str!("../readme.md")]
+
+pub mod events;
+pub mod job;
+mod profile;
+pub mod scheduler;
+pub mod swf;
+
+pub use job::{Job, JobFailReason, JobInfo, JobStatus};
+pub use scheduler::{BatchScheduler, BatchStats, SchedulingPolicy};
@@ -0,0 +1,112 @@
+// Availability profile of cluster resources used to plan the execution of jobs.
+
+// Resources of a node used by a running or planned job during [start, end) interval.
+struct Usage {
+    start: f64,
+    end: f64,
+    cores: u32,
+    memory: u64,
+}
+
+struct NodeProfile {
+    cores: u32,
+    memory: u64,
+    available: bool,
+    usages: Vec<Usage>,
+}
+
+impl NodeProfile {
+    // Returns the minimum amounts of free cores and memory during [start, end) interval.
+    fn min_free(&self, start: f64, end: f64) -> (u32, u64) {
+        let mut min_cores = self.cores;
+        let mut min_memory = self.memory;
+        let points = std::iter::once(start).chain(
+            self.usages
+                .iter()
+                .map(|u| u.start)
+                .filter(|time| *time > start && *time < end),
+        );
+        for time in points {
+            let mut cores = self.cores;
+            let mut memory = self.memory;
+            for usage in self.usages.iter().filter(|u| u.start <= time && time < u.end) {
+                cores = cores.saturating_sub(usage.cores);
+                memory = memory.saturating_sub(usage.memory);
+            }
+            min_cores = min_cores.min(cores);
+            min_memory = min_memory.min(memory);
+        }
+        (min_cores, min_memory)
+    }
+}
+
+pub(crate) struct Profile {
+    time: f64,
+    nodes: Vec<NodeProfile>,
+}
+
+impl Profile {
+    // Creates profile without usages for nodes described as (cores, memory, is available) tuples.
+    pub fn new(time: f64, nodes: impl Iterator<Item = (u32, u64, bool)>) -> Self {
+        Self {
+            time,
+            nodes: nodes
+                .map(|(cores, memory, available)| NodeProfile {
+                    cores,
+                    memory,
+                    available,
+                    usages: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    // Marks the resources allocated to a job as used during [start, end) interval.
+    pub fn add(&mut self, start: f64, end: f64, allocation: &[(usize, u32)], memory_per_core: u64) {
+        for (node, cores) in allocation.iter() {
+            self.nodes[*node].usages.push(Usage {
+                start,
+                end: end.max(self.time),
+                cores: *cores,
+                memory: memory_per_core * *cores as u64,
+            });
+        }
+    }
+
+    // Returns the earliest time when a job with given requirements can run for the specified duration,
+    // and the allocation of job cores to the nodes at this time.
+    // The nodes are filled in the order of their indices. Returns None if there is no such time.
+    pub fn find_start(&self, cores: u32, memory_per_core: u64, duration: f64) -> Option<(f64, Vec<(usize, u32)>)> {
+        let mut times: Vec<f64> = std::iter::once(self.time)
+            .chain(
+                self.nodes
+                    .iter()
+                    .flat_map(|n| n.usages.iter().map(|u| u.end))
+                    .filter(|time| *time > self.time),
+            )
+            .collect();
+        times.sort_by(|a, b| a.total_cmp(b));
+        times.dedup();
+
+        for start in times {
+            let mut allocation = Vec::new();
+            let mut cores_left = cores;
+            for (idx, node) in self.nodes.iter().enumerate().filter(|(_, n)| n.available) {
+                let (free_cores, free_memory) = node.min_free(start, start + duration);
+                // the number of cores is also limited by the memory if the job requests it
+                let usable_cores = free_memory
+                    .checked_div(memory_per_core)
+                    .map_or(free_cores, |cores| free_cores.min(cores.min(u32::MAX as u64) as u32));
+                let used_cores = usable_cores.min(cores_left);
+                if used_cores > 0 {
+                    allocation.push((idx, used_cores));
+                    cores_left -= used_cores;
+                }
+                if cores_left == 0 {
+                    return Some((start, allocation));
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,429 @@
+//! Batch scheduler component.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_compute::multicore::{
+    CompCancelled, CompFailed, CompFinished, CompStarted, Compute, CoresDependency, FailReason, ResourceFailed,
+    ResourceRecovered,
+};
+use dslab_core::component::Id;
+use dslab_core::context::SimulationContext;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::{cast, log_debug, EventId};
+
+use crate::events::{JobFailed, JobFinished, JobStarted, SubmitJob};
+use crate::job::{Job, JobFailReason, JobInfo, JobStatus};
+use crate::profile::Profile;
+
+/// Policy used to select the queued jobs to start.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum SchedulingPolicy {
+    /// First come first served: the jobs are started in the queue order,
+    /// and the job at the queue head blocks all other jobs until it can be started.
+    Fcfs,
+    /// EASY backfilling: the job at the queue head receives a reservation for the earliest time when
+    /// enough resources will be available, and the following jobs can be started out of order
+    /// if they do not delay this reservation.
+    EasyBackfilling,
+    /// Conservative backfilling: each queued job receives a reservation in the queue order,
+    /// and a job can be started out of order only if it does not delay any of the previous reservations.
+    ConservativeBackfilling,
+}
+
+/// Summary statistics of job execution.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BatchStats {
+    /// Number of completed jobs.
+    pub completed_jobs: usize,
+    /// Number of failed jobs.
+    pub failed_jobs: usize,
+    /// Mean wait time of completed jobs.
+    pub mean_wait_time: f64,
+    /// Mean turnaround time (from submission to completion) of completed jobs.
+    pub mean_turnaround_time: f64,
+    /// Mean bounded slowdown of completed jobs, the run times below 10 seconds are rounded up to 10 seconds.
+    pub mean_bounded_slowdown: f64,
+    /// Completion time of the last finished job.
+    pub makespan: f64,
+}
+
+#[derive(Clone, Serialize)]
+struct WalltimeExceeded {
+    id: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct CheckWalltime {
+    id: u64,
+}
+
+// Part of a running job executed on a single node.
+struct JobPart {
+    node: usize,
+    cores: u32,
+    comp_id: u64,
+    finished: bool,
+}
+
+struct RunningJob {
+    parts: Vec<JobPart>,
+    walltime_event: Option<EventId>,
+}
+
+/// Models a cluster-level batch system which runs jobs on a pool of compute resources.
+///
+/// The submitted jobs are placed into the queue sorted by priority and submission time, and then dispatched to the
+/// resources according to the [`SchedulingPolicy`]. Each job part is executed on a [`Compute`] resource as a computation
+/// with the fixed number of cores. The scheduler exclusively manages the cores and memory of its resources,
+/// so the resources should not be used by other components. The job plans are built using the job wall time estimates,
+/// and the job is killed if it runs longer than its estimate.
+///
+/// The scheduler listens to the resource failures: the jobs running on a failed resource are failed,
+/// and the resource is not used until it recovers.
+pub struct BatchScheduler {
+    nodes: Vec<Rc<RefCell<Compute>>>,
+    policy: SchedulingPolicy,
+    jobs: HashMap<u64, JobInfo>,
+    queue: Vec<u64>,
+    running: HashMap<u64, RunningJob>,
+    computations: HashMap<u64, u64>,
+    requesters: HashMap<u64, Id>,
+    ctx: SimulationContext,
+}
+
+impl BatchScheduler {
+    /// Creates a new batch scheduler.
+    pub fn new(nodes: Vec<Rc<RefCell<Compute>>>, policy: SchedulingPolicy, ctx: SimulationContext) -> Self {
+        for node in nodes.iter() {
+            node.borrow_mut().add_failure_listener(ctx.id());
+        }
+        Self {
+            nodes,
+            policy,
+            jobs: HashMap::new(),
+            queue: Vec::new(),
+            running: HashMap::new(),
+            computations: HashMap::new(),
+            requesters: HashMap::new(),
+            ctx,
+        }
+    }
+
+    /// Returns id of corresponding simulation component.
+    pub fn id(&self) -> Id {
+        self.ctx.id()
+    }
+
+    /// Returns the scheduling policy.
+    pub fn policy(&self) -> SchedulingPolicy {
+        self.policy
+    }
+
+    /// Submits the jobs at their submission times, e.g. to replay a workload trace.
+    ///
+    /// The jobs with submission times in the past are submitted immediately.
+    pub fn submit_jobs(&mut self, jobs: Vec<Job>) {
+        for job in jobs {
+            let delay = (job.submit_time - self.ctx.time()).max(0.);
+            self.ctx.emit_self(SubmitJob { job }, delay);
+        }
+    }
+
+    /// Returns information about the job.
+    pub fn job_info(&self, job_id: u64) -> Option<&JobInfo> {
+        self.jobs.get(&job_id)
+    }
+
+    /// Returns the number of queued jobs.
+    pub fn queue_length(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the number of running jobs.
+    pub fn running_jobs(&self) -> usize {
+        self.running.len()
+    }
+
+    /// Returns summary statistics of job execution.
+    pub fn stats(&self) -> BatchStats {
+        let mut stats = BatchStats::default();
+        for info in self.jobs.values() {
+            match info.status {
+                JobStatus::Completed => {
+                    let wait_time = info.wait_time().unwrap();
+                    let run_time = info.run_time().unwrap();
+                    stats.completed_jobs += 1;
+                    stats.mean_wait_time += wait_time;
+                    stats.mean_turnaround_time += wait_time + run_time;
+                    stats.mean_bounded_slowdown += ((wait_time + run_time) / run_time.max(10.)).max(1.);
+                }
+                JobStatus::Failed(_) => {
+                    stats.failed_jobs += 1;
+                }
+                _ => {}
+            }
+            if let Some(finish_time) = info.finish_time {
+                stats.makespan = stats.makespan.max(finish_time);
+            }
+        }
+        if stats.completed_jobs > 0 {
+            stats.mean_wait_time /= stats.completed_jobs as f64;
+            stats.mean_turnaround_time /= stats.completed_jobs as f64;
+            stats.mean_bounded_slowdown /= stats.completed_jobs as f64;
+        }
+        stats
+    }
+
+    fn on_job_submitted(&mut self, job: Job, requester: Option<Id>) {
+        assert!(job.cores > 0, "Job should request at least one core");
+        assert!(
+            !self.jobs.contains_key(&job.id),
+            "Job with id {} is already submitted",
+            job.id
+        );
+        let job_id = job.id;
+        if let Some(requester) = requester {
+            self.requesters.insert(job_id, requester);
+        }
+
+        // check that the job fits into the cluster without failures and other jobs
+        let empty_profile = Profile::new(
+            self.ctx.time(),
+            self.nodes.iter().map(|node| {
+                let node = node.borrow();
+                (node.cores_total(), node.memory_total(), true)
+            }),
+        );
+        let fits = empty_profile.find_start(job.cores, job.memory, job.walltime).is_some();
+
+        let key = (-job.priority, self.ctx.time(), job_id);
+        self.jobs.insert(
+            job_id,
+            JobInfo {
+                job,
+                status: JobStatus::Queued,
+                submit_time: self.ctx.time(),
+                start_time: None,
+                finish_time: None,
+                allocation: Vec::new(),
+            },
+        );
+        if !fits {
+            self.fail_job(job_id, JobFailReason::NotEnoughResources);
+            return;
+        }
+        let pos = self.queue.partition_point(|id| {
+            let info = &self.jobs[id];
+            (-info.job.priority, info.submit_time, *id) < key
+        });
+        self.queue.insert(pos, job_id);
+        self.schedule();
+    }
+
+    // Selects the queued jobs to start according to the scheduling policy.
+    fn schedule(&mut self) {
+        let now = self.ctx.time();
+        let mut profile = Profile::new(
+            now,
+            self.nodes.iter().map(|node| {
+                let node = node.borrow();
+                (node.cores_total(), node.memory_total(), !node.is_failed())
+            }),
+        );
+        for (job_id, running_job) in self.running.iter() {
+            let info = &self.jobs[job_id];
+            let allocation: Vec<(usize, u32)> = running_job.parts.iter().map(|p| (p.node, p.cores)).collect();
+            let end = info.start_time.unwrap() + info.job.walltime;
+            profile.add(now, end, &allocation, info.job.memory);
+        }
+
+        let mut started = Vec::new();
+        let mut reserved = false;
+        for job_id in self.queue.iter() {
+            let job = &self.jobs[job_id].job;
+            match profile.find_start(job.cores, job.memory, job.walltime) {
+                Some((start, allocation)) if start <= now => {
+                    profile.add(now, now + job.walltime, &allocation, job.memory);
+                    started.push((*job_id, allocation));
+                }
+                Some((start, allocation)) => match self.policy {
+                    SchedulingPolicy::Fcfs => break,
+                    SchedulingPolicy::EasyBackfilling if reserved => {}
+                    _ => {
+                        profile.add(start, start + job.walltime, &allocation, job.memory);
+                        reserved = true;
+                    }
+                },
+                // can't be started until a failed resource recovers
+                None => {
+                    if self.policy == SchedulingPolicy::Fcfs {
+                        break;
+                    }
+                }
+            }
+        }
+
+        for (job_id, allocation) in started {
+            self.start_job(job_id, allocation);
+        }
+    }
+
+    fn start_job(&mut self, job_id: u64, allocation: Vec<(usize, u32)>) {
+        self.queue.retain(|id| *id != job_id);
+        let info = self.jobs.get_mut(&job_id).unwrap();
+        info.status = JobStatus::Running;
+        info.start_time = Some(self.ctx.time());
+        info.allocation = allocation
+            .iter()
+            .map(|(node, cores)| (self.nodes[*node].borrow().id(), *cores))
+            .collect();
+
+        let job = &info.job;
+        let mut parts = Vec::new();
+        for (node, cores) in allocation {
+            let flops = job.flops * cores as f64 / job.cores as f64;
+            let comp_id = self.nodes[node].borrow_mut().run(
+                flops,
+                job.memory * cores as u64,
+                cores,
+                cores,
+                CoresDependency::Linear,
+                self.ctx.id(),
+            );
+            self.computations.insert(comp_id, job_id);
+            parts.push(JobPart {
+                node,
+                cores,
+                comp_id,
+                finished: false,
+            });
+        }
+        let walltime_event = Some(self.ctx.emit_self(WalltimeExceeded { id: job_id }, job.walltime));
+        self.running.insert(job_id, RunningJob { parts, walltime_event });
+
+        log_debug!(self.ctx, "started job {} on {:?}", job_id, info.allocation);
+        if let Some(requester) = self.requesters.get(&job_id) {
+            self.ctx.emit_now(
+                JobStarted {
+                    id: job_id,
+                    allocation: info.allocation.clone(),
+                },
+                *requester,
+            );
+        }
+    }
+
+    fn on_part_finished(&mut self, comp_id: u64) {
+        if let Some(job_id) = self.computations.remove(&comp_id) {
+            let running_job = self.running.get_mut(&job_id).unwrap();
+            for part in running_job.parts.iter_mut().filter(|p| p.comp_id == comp_id) {
+                part.finished = true;
+            }
+            if running_job.parts.iter().all(|p| p.finished) {
+                if let Some(event_id) = self.running.remove(&job_id).unwrap().walltime_event {
+                    self.ctx.cancel_event(event_id);
+                }
+                let info = self.jobs.get_mut(&job_id).unwrap();
+                info.status = JobStatus::Completed;
+                info.finish_time = Some(self.ctx.time());
+                log_debug!(self.ctx, "finished job {}", job_id);
+                if let Some(requester) = self.requesters.remove(&job_id) {
+                    self.ctx.emit_now(JobFinished { id: job_id }, requester);
+                }
+                self.schedule();
+            }
+        }
+    }
+
+    fn on_walltime_exceeded(&mut self, job_id: u64) {
+        // The job parts can finish at the same time, but their completion events are created by the compute resources
+        // after the walltime event. So the check is delayed until these events are processed by the resources.
+        let check_event = self.ctx.emit_self_now(CheckWalltime { id: job_id });
+        self.running.get_mut(&job_id).unwrap().walltime_event = Some(check_event);
+    }
+
+    fn on_check_walltime(&mut self, job_id: u64) {
+        let running_job = self.running.get_mut(&job_id).unwrap();
+        running_job.walltime_event = None;
+        // the computations completed by resources are not running anymore, their completion is delivered next
+        let all_done = running_job
+            .parts
+            .iter()
+            .filter(|p| !p.finished)
+            .all(|p| self.nodes[p.node].borrow().fraction_done(p.comp_id).is_err());
+        if !all_done {
+            self.fail_job(job_id, JobFailReason::WalltimeExceeded);
+            self.schedule();
+        }
+    }
+
+    // Marks the job as failed and cancels its unfinished computations.
+    fn fail_job(&mut self, job_id: u64, reason: JobFailReason) {
+        self.queue.retain(|id| *id != job_id);
+        if let Some(running_job) = self.running.remove(&job_id) {
+            if let Some(event_id) = running_job.walltime_event {
+                self.ctx.cancel_event(event_id);
+            }
+            for part in running_job.parts.iter().filter(|p| !p.finished) {
+                if self.computations.remove(&part.comp_id).is_some() {
+                    self.nodes[part.node].borrow_mut().cancel_computation(part.comp_id);
+                }
+            }
+        }
+        let info = self.jobs.get_mut(&job_id).unwrap();
+        info.status = JobStatus::Failed(reason.clone());
+        info.finish_time = Some(self.ctx.time());
+        log_debug!(self.ctx, "failed job {}: {:?}", job_id, reason);
+        if let Some(requester) = self.requesters.remove(&job_id) {
+            self.ctx.emit_now(JobFailed { id: job_id, reason }, requester);
+        }
+    }
+}
+
+impl EventHandler for BatchScheduler {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            SubmitJob { job } => {
+                let requester = if event.src != self.ctx.id() {
+                    Some(event.src)
+                } else {
+                    None
+                };
+                self.on_job_submitted(job, requester);
+            }
+            CompStarted { .. } => {}
+            CompFinished { id } => {
+                self.on_part_finished(id);
+            }
+            CompCancelled { .. } => {}
+            CompFailed { id, reason } => {
+                // the computation is not known if it was cancelled due to the failure of another job part
+                if let Some(job_id) = self.computations.remove(&id) {
+                    let reason = match reason {
+                        FailReason::NotEnoughResources { .. } => JobFailReason::NotEnoughResources,
+                        FailReason::ResourceFailure => JobFailReason::ResourceFailure,
+                    };
+                    self.fail_job(job_id, reason);
+                    self.schedule();
+                }
+            }
+            WalltimeExceeded { id } => {
+                self.on_walltime_exceeded(id);
+            }
+            CheckWalltime { id } => {
+                self.on_check_walltime(id);
+            }
+            ResourceFailed {} => {
+                self.schedule();
+            }
+            ResourceRecovered {} => {
+                self.schedule();
+            }
+        })
+    }
+}
@@ -0,0 +1,92 @@
+//! Reader of workload traces in Standard Workload Format (SWF).
+//!
+//! The format is described in the [Parallel Workloads Archive](https://www.cs.huji.ac.il/labs/parallel/workload/swf.html).
+//! Each trace line describes a single job using 18 whitespace-separated fields, with `-1` denoting a missing value.
+//! The lines starting with `;` are header comments.
+//!
+//! The jobs are converted to [`Job`] as follows:
+//! - the number of cores is the number of requested processors, or allocated processors if the former is missing,
+//! - the job size is computed from the run time so that the job runs for this time on cores with specified speed,
+//! - the wall time is the requested time, or the run time if the former is missing,
+//! - the memory per core is the requested memory, or used memory if the former is missing, in kilobytes as in the trace,
+//! - the priority is zero.
+//!
+//! The jobs with missing run time or number of processors are skipped.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dslab_batch::swf::parse_swf;
+//!
+//! let trace = "; Version: 2.2
+//! 1 0 5 100 4 -1 -1 4 200 1024 1 1 1 -1 1 -1 -1 -1
+//! 2 10 -1 -1 -1 -1 -1 2 50 -1 0 1 1 -1 1 -1 -1 -1
+//! 3 20 0 30 -1 -1 -1 8 -1 -1 1 1 1 -1 1 -1 -1 -1";
+//! let jobs = parse_swf(trace, 10.).unwrap();
+//! assert_eq!(jobs.len(), 2);
+//! assert_eq!(jobs[0].flops, 4000.);
+//! assert_eq!(jobs[0].walltime, 200.);
+//! assert_eq!(jobs[0].memory, 1024);
+//! assert_eq!(jobs[1].cores, 8);
+//! assert_eq!(jobs[1].walltime, 30.);
+//! ```
+
+use crate::job::Job;
+
+const JOB_NUMBER: usize = 0;
+const SUBMIT_TIME: usize = 1;
+const RUN_TIME: usize = 3;
+const ALLOCATED_PROCESSORS: usize = 4;
+const USED_MEMORY: usize = 6;
+const REQUESTED_PROCESSORS: usize = 7;
+const REQUESTED_TIME: usize = 8;
+const REQUESTED_MEMORY: usize = 9;
+
+/// Reads jobs from SWF trace file, the job sizes are computed for cores with specified speed.
+pub fn read_swf(path: &str, core_speed: f64) -> Result<Vec<Job>, String> {
+    let trace = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+    parse_swf(&trace, core_speed)
+}
+
+/// Parses jobs from SWF trace, the job sizes are computed for cores with specified speed.
+pub fn parse_swf(trace: &str, core_speed: f64) -> Result<Vec<Job>, String> {
+    let mut jobs = Vec::new();
+    for (line_idx, line) in trace.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        let fields = line
+            .split_whitespace()
+            .map(|field| field.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| format!("Invalid value at line {}: {}", line_idx + 1, e))?;
+        if fields.len() <= REQUESTED_MEMORY {
+            return Err(format!(
+                "Expected at least {} fields at line {}, got {}",
+                REQUESTED_MEMORY + 1,
+                line_idx + 1,
+                fields.len()
+            ));
+        }
+        let field = |idx: usize| Some(fields[idx]).filter(|value| *value >= 0.);
+
+        let cores = field(REQUESTED_PROCESSORS)
+            .filter(|value| *value > 0.)
+            .or(field(ALLOCATED_PROCESSORS).filter(|value| *value > 0.));
+        let (Some(cores), Some(run_time)) = (cores, field(RUN_TIME)) else {
+            continue;
+        };
+        let cores = cores as u32;
+        jobs.push(Job {
+            id: fields[JOB_NUMBER] as u64,
+            submit_time: field(SUBMIT_TIME).unwrap_or(0.),
+            cores,
+            memory: field(REQUESTED_MEMORY).or(field(USED_MEMORY)).unwrap_or(0.) as u64,
+            flops: run_time * core_speed * cores as f64,
+            walltime: field(REQUESTED_TIME).filter(|value| *value > 0.).unwrap_or(run_time),
+            priority: 0,
+        });
+    }
+    Ok(jobs)
+}
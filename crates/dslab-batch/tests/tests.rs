@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_compute::failure::FailureModel;
+use dslab_compute::multicore::Compute;
+use dslab_core::component::Id;
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+use dslab_batch::events::{JobFailed, JobFinished, JobStarted, SubmitJob};
+use dslab_batch::swf::{parse_swf, read_swf};
+use dslab_batch::{BatchScheduler, Job, JobFailReason, JobStatus, SchedulingPolicy};
+
+fn job(id: u64, cores: u32, run_time: f64, walltime: f64) -> Job {
+    Job {
+        id,
+        submit_time: 0.,
+        cores,
+        memory: 0,
+        flops: run_time * cores as f64,
+        walltime,
+        priority: 0,
+    }
+}
+
+fn build_cluster(
+    sim: &mut Simulation,
+    nodes: &[(u32, u64)],
+    policy: SchedulingPolicy,
+) -> (Vec<Rc<RefCell<Compute>>>, Rc<RefCell<BatchScheduler>>) {
+    let mut computes = Vec::new();
+    for (i, (cores, memory)) in nodes.iter().enumerate() {
+        let name = format!("node{}", i);
+        let compute = Rc::new(RefCell::new(Compute::new(
+            1.,
+            *cores,
+            *memory,
+            sim.create_context(&name),
+        )));
+        sim.add_handler(&name, compute.clone());
+        computes.push(compute);
+    }
+    let scheduler = Rc::new(RefCell::new(BatchScheduler::new(
+        computes.clone(),
+        policy,
+        sim.create_context("scheduler"),
+    )));
+    sim.add_handler("scheduler", scheduler.clone());
+    (computes, scheduler)
+}
+
+fn start_times(scheduler: &BatchScheduler, job_ids: &[u64]) -> Vec<f64> {
+    job_ids
+        .iter()
+        .map(|id| scheduler.job_info(*id).unwrap().start_time.unwrap())
+        .collect()
+}
+
+// Runs the jobs on a single node with 4 cores, so that each policy produces a different schedule.
+fn run_policy(policy: SchedulingPolicy) -> Vec<f64> {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100)], policy);
+    scheduler.borrow_mut().submit_jobs(vec![
+        job(1, 3, 10., 10.),
+        job(2, 2, 10., 10.),
+        job(3, 2, 10., 10.),
+        job(4, 1, 30., 30.),
+        job(5, 1, 5., 5.),
+    ]);
+    sim.step_until_no_events();
+    let scheduler = scheduler.borrow();
+    assert_eq!(scheduler.stats().completed_jobs, 5);
+    start_times(&scheduler, &[1, 2, 3, 4, 5])
+}
+
+#[test]
+fn test_fcfs() {
+    assert_eq!(run_policy(SchedulingPolicy::Fcfs), vec![0., 10., 10., 20., 20.]);
+}
+
+#[test]
+fn test_easy_backfilling() {
+    // job 4 is backfilled since it does not delay job 2, but delays job 3
+    assert_eq!(
+        run_policy(SchedulingPolicy::EasyBackfilling),
+        vec![0., 10., 20., 0., 10.]
+    );
+}
+
+#[test]
+fn test_conservative_backfilling() {
+    // only job 5 can be backfilled without delaying the reservations of previous jobs
+    assert_eq!(
+        run_policy(SchedulingPolicy::ConservativeBackfilling),
+        vec![0., 10., 10., 20., 0.]
+    );
+}
+
+#[test]
+fn test_priority() {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100)], SchedulingPolicy::Fcfs);
+    let mut high_priority = job(3, 4, 10., 10.);
+    high_priority.priority = 1;
+    scheduler
+        .borrow_mut()
+        .submit_jobs(vec![job(1, 4, 10., 10.), job(2, 4, 10., 10.)]);
+    sim.step();
+    sim.step();
+    scheduler.borrow_mut().submit_jobs(vec![high_priority]);
+    sim.step_until_no_events();
+    assert_eq!(start_times(&scheduler.borrow(), &[1, 2, 3]), vec![0., 20., 10.]);
+}
+
+#[test]
+fn test_multinode_job() {
+    let mut sim = Simulation::new(123);
+    let (computes, scheduler) = build_cluster(&mut sim, &[(4, 100), (4, 100)], SchedulingPolicy::Fcfs);
+    let mut job = job(1, 6, 10., 20.);
+    job.memory = 10;
+    scheduler.borrow_mut().submit_jobs(vec![job]);
+    sim.step_until_time(1.);
+    assert_eq!(computes[0].borrow().memory_available(), 60);
+    assert_eq!(computes[1].borrow().memory_available(), 80);
+    sim.step_until_no_events();
+
+    let scheduler = scheduler.borrow();
+    let info = scheduler.job_info(1).unwrap();
+    assert_eq!(info.status, JobStatus::Completed);
+    assert_eq!(info.run_time(), Some(10.));
+    let node_ids: Vec<Id> = computes.iter().map(|c| c.borrow().id()).collect();
+    assert_eq!(info.allocation, vec![(node_ids[0], 4), (node_ids[1], 2)]);
+}
+
+#[test]
+fn test_memory_limit() {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100)], SchedulingPolicy::Fcfs);
+    let mut jobs = vec![job(1, 2, 10., 10.), job(2, 2, 10., 10.)];
+    for job in jobs.iter_mut() {
+        job.memory = 30;
+    }
+    scheduler.borrow_mut().submit_jobs(jobs);
+    sim.step_until_no_events();
+    assert_eq!(start_times(&scheduler.borrow(), &[1, 2]), vec![0., 10.]);
+}
+
+#[test]
+fn test_walltime_exceeded() {
+    let mut sim = Simulation::new(123);
+    let (computes, scheduler) = build_cluster(&mut sim, &[(4, 100)], SchedulingPolicy::Fcfs);
+    scheduler
+        .borrow_mut()
+        .submit_jobs(vec![job(1, 4, 20., 10.), job(2, 4, 10., 10.), job(3, 2, 0., 0.)]);
+    sim.step_until_no_events();
+
+    let scheduler = scheduler.borrow();
+    let info = scheduler.job_info(1).unwrap();
+    assert_eq!(info.status, JobStatus::Failed(JobFailReason::WalltimeExceeded));
+    assert_eq!(info.finish_time, Some(10.));
+    let info = scheduler.job_info(2).unwrap();
+    assert_eq!(info.status, JobStatus::Completed);
+    assert_eq!(info.finish_time, Some(20.));
+    assert_eq!(scheduler.job_info(3).unwrap().status, JobStatus::Completed);
+    assert_eq!(computes[0].borrow().cores_available(), 4);
+}
+
+#[test]
+fn test_run_time_equal_to_walltime() {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100), (4, 100)], SchedulingPolicy::Fcfs);
+    scheduler
+        .borrow_mut()
+        .submit_jobs(vec![job(1, 6, 7., 7.), job(2, 2, 7., 7.), job(3, 8, 5., 5.)]);
+    sim.step_until_no_events();
+
+    // the jobs completed at the walltime are not killed
+    let scheduler = scheduler.borrow();
+    assert_eq!(scheduler.stats().completed_jobs, 3);
+    assert_eq!(scheduler.job_info(3).unwrap().finish_time, Some(12.));
+}
+
+#[test]
+fn test_not_enough_resources() {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100), (4, 100)], SchedulingPolicy::Fcfs);
+    let mut big_memory = job(2, 2, 10., 10.);
+    big_memory.memory = 120;
+    scheduler
+        .borrow_mut()
+        .submit_jobs(vec![job(1, 10, 10., 10.), big_memory, job(3, 8, 10., 10.)]);
+    sim.step_until_no_events();
+
+    let scheduler = scheduler.borrow();
+    for id in [1, 2] {
+        let info = scheduler.job_info(id).unwrap();
+        assert_eq!(info.status, JobStatus::Failed(JobFailReason::NotEnoughResources));
+        assert_eq!(info.start_time, None);
+    }
+    assert_eq!(scheduler.job_info(3).unwrap().status, JobStatus::Completed);
+    let stats = scheduler.stats();
+    assert_eq!(stats.completed_jobs, 1);
+    assert_eq!(stats.failed_jobs, 2);
+}
+
+#[test]
+fn test_resource_failure() {
+    let mut sim = Simulation::new(123);
+    let compute = Compute::new(1., 4, 100, sim.create_context("node"))
+        .with_failure_model(FailureModel::from_trace(vec![(5., 15.)]).unwrap());
+    let compute = Rc::new(RefCell::new(compute));
+    sim.add_handler("node", compute.clone());
+    let scheduler = Rc::new(RefCell::new(BatchScheduler::new(
+        vec![compute],
+        SchedulingPolicy::EasyBackfilling,
+        sim.create_context("scheduler"),
+    )));
+    sim.add_handler("scheduler", scheduler.clone());
+
+    let mut late_job = job(2, 4, 10., 10.);
+    late_job.submit_time = 10.;
+    scheduler.borrow_mut().submit_jobs(vec![job(1, 4, 10., 10.), late_job]);
+    sim.step_until_no_events();
+
+    let scheduler = scheduler.borrow();
+    let info = scheduler.job_info(1).unwrap();
+    assert_eq!(info.status, JobStatus::Failed(JobFailReason::ResourceFailure));
+    assert_eq!(info.finish_time, Some(5.));
+    let info = scheduler.job_info(2).unwrap();
+    assert_eq!(info.status, JobStatus::Completed);
+    assert_eq!(info.start_time, Some(15.));
+}
+
+struct Client {
+    scheduler_id: Id,
+    events: Vec<(f64, String)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Client {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            JobStarted { id, .. } => {
+                self.events.push((event.time, format!("started {}", id)));
+            }
+            JobFinished { id } => {
+                self.events.push((event.time, format!("finished {}", id)));
+            }
+            JobFailed { id, reason } => {
+                self.events.push((event.time, format!("failed {} {:?}", id, reason)));
+            }
+        })
+    }
+}
+
+#[test]
+fn test_notifications() {
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 100)], SchedulingPolicy::Fcfs);
+    let client = Rc::new(RefCell::new(Client {
+        scheduler_id: scheduler.borrow().id(),
+        events: Vec::new(),
+        ctx: sim.create_context("client"),
+    }));
+    sim.add_handler("client", client.clone());
+    {
+        let client = client.borrow();
+        client.ctx.emit(
+            SubmitJob {
+                job: job(1, 2, 10., 10.),
+            },
+            client.scheduler_id,
+            1.,
+        );
+        client.ctx.emit(
+            SubmitJob {
+                job: job(2, 8, 10., 10.),
+            },
+            client.scheduler_id,
+            2.,
+        );
+    }
+    sim.step_until_no_events();
+    assert_eq!(
+        client.borrow().events,
+        vec![
+            (1., "started 1".to_string()),
+            (2., "failed 2 NotEnoughResources".to_string()),
+            (11., "finished 1".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_swf() {
+    let trace = "; Computer: test
+; MaxProcs: 8
+1 0 0 100 4 -1 512 -1 120 -1 1 1 1 -1 1 -1 -1 -1
+2 5 0 50 2 -1 -1 2 -1 256 1 1 1 -1 1 -1 -1 -1
+3 7 0 -1 2 -1 -1 2 -1 -1 5 1 1 -1 1 -1 -1 -1
+";
+    let path = std::env::temp_dir().join(format!("dslab-batch-{}.swf", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, trace).unwrap();
+    let jobs = read_swf(path, 1.).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(
+        (jobs[0].cores, jobs[0].memory, jobs[0].flops, jobs[0].walltime),
+        (4, 512, 400., 120.)
+    );
+    assert_eq!(
+        (jobs[1].cores, jobs[1].memory, jobs[1].flops, jobs[1].walltime),
+        (2, 256, 100., 50.)
+    );
+    assert_eq!(jobs[1].submit_time, 5.);
+
+    let mut sim = Simulation::new(123);
+    let (_, scheduler) = build_cluster(&mut sim, &[(4, 2048)], SchedulingPolicy::Fcfs);
+    scheduler.borrow_mut().submit_jobs(jobs);
+    sim.step_until_no_events();
+    assert_eq!(start_times(&scheduler.borrow(), &[1, 2]), vec![0., 100.]);
+
+    assert!(parse_swf("1 0 0 100", 1.).is_err());
+    assert!(parse_swf("1 0 0 x 4 -1 -1 4 -1 -1", 1.).is_err());
+}
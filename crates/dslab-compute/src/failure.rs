@@ -0,0 +1,68 @@
+//! Models of computing resource failures.
+
+use dslab_core::distribution::DistributionSampler;
+
+/// Describes when the resource fails and how long it stays unavailable.
+#[derive(Clone, Debug)]
+pub enum FailureModel {
+    /// Failures and recoveries are generated using the specified distributions.
+    ///
+    /// The time to the next failure and its duration are sampled upon the resource start and after each recovery.
+    /// Note that the failures are generated indefinitely, so the simulation should be run until a specific time
+    /// or condition instead of running until there are no events.
+    Random {
+        /// Distribution of time between the resource recovery (or start) and the next failure.
+        time_to_failure: DistributionSampler,
+        /// Distribution of failure duration.
+        time_to_recovery: DistributionSampler,
+    },
+    /// Failures are replayed from the trace.
+    Trace {
+        /// Non-overlapping failure intervals as (failure time, recovery time) pairs.
+        intervals: Vec<(f64, f64)>,
+    },
+}
+
+impl FailureModel {
+    /// Creates random failure model with exponentially distributed time to failure and time to recovery
+    /// with specified means, i.e. mean time between failures (MTBF) and mean time to repair (MTTR).
+    pub fn exponential(mtbf: f64, mttr: f64) -> Self {
+        Self::Random {
+            time_to_failure: DistributionSampler::Exponential(1. / mtbf),
+            time_to_recovery: DistributionSampler::Exponential(1. / mttr),
+        }
+    }
+
+    /// Creates trace failure model from specified failure intervals.
+    ///
+    /// The intervals are sorted by the failure time and checked to be non-overlapping.
+    pub fn from_trace(mut intervals: Vec<(f64, f64)>) -> Result<Self, String> {
+        intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut prev_recovery_time = 0.;
+        for (failure_time, recovery_time) in intervals.iter() {
+            if *failure_time < prev_recovery_time || recovery_time < failure_time {
+                return Err(format!(
+                    "Invalid failure interval [{}, {}]: intervals should be non-negative and non-overlapping",
+                    failure_time, recovery_time
+                ));
+            }
+            prev_recovery_time = *recovery_time;
+        }
+        Ok(Self::Trace { intervals })
+    }
+
+    /// Reads trace failure model from CSV file without header, where each line contains the failure time
+    /// and the recovery time.
+    pub fn from_trace_file(path: &str) -> Result<Self, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .map_err(|e| format!("Failed to open file {}: {}", path, e))?;
+        let intervals = reader
+            .deserialize()
+            .collect::<Result<Vec<(f64, f64)>, _>>()
+            .map_err(|e| format!("Failed to read failure intervals from {}: {}", path, e))?;
+        Self::from_trace(intervals)
+    }
+}
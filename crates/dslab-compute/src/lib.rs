@@ -1,5 +1,7 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../readme.md")]
 
+pub mod failure;
+pub mod migration;
 pub mod multicore;
 pub mod singlecore;
@@ -0,0 +1,133 @@
+//! Migration of computations between computing resources.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dslab_core::component::Id;
+use dslab_core::context::SimulationContext;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::{cast, log_debug};
+use dslab_network::{DataTransferCompleted, Network};
+
+use crate::multicore::{Checkpoint, CheckpointFailed, CompCheckpointed, Compute};
+
+struct Migration {
+    source: Id,
+    target: Rc<RefCell<Compute>>,
+    checkpoint: Option<Checkpoint>,
+}
+
+/// Migrates computations between [`multicore`](crate::multicore) resources using checkpointing.
+///
+/// The migrated computation is checkpointed on the source resource, then the checkpoint is optionally transferred
+/// over the network to the target resource, and the computation is restored on the target resource.
+/// The computation keeps its id and requester, which receives [`CompRestored`](crate::multicore::CompRestored)
+/// or [`CompFailed`](crate::multicore::CompFailed) event from the target resource, and then continues to receive
+/// the events about the computation progress from it.
+///
+/// If the checkpointing is aborted on the source resource, the migration is cancelled and the computation
+/// remains on the source resource, unless it was cancelled or failed there.
+///
+/// If the network is used, the locations of source and target resources should be set in the network.
+pub struct Migrator {
+    network: Option<Rc<RefCell<Network>>>,
+    migrations: HashMap<u64, Migration>,
+    transfers: HashMap<usize, u64>,
+    ctx: SimulationContext,
+}
+
+impl Migrator {
+    /// Creates a new migrator which transfers the checkpoints instantly.
+    pub fn new(ctx: SimulationContext) -> Self {
+        Self {
+            network: None,
+            migrations: HashMap::new(),
+            transfers: HashMap::new(),
+            ctx,
+        }
+    }
+
+    /// Sets the network used to transfer the checkpoints between the resources.
+    pub fn with_network(mut self, network: Rc<RefCell<Network>>) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Returns id of corresponding simulation component.
+    pub fn id(&self) -> Id {
+        self.ctx.id()
+    }
+
+    /// Starts migration of running or preempted computation with specified checkpoint state size.
+    pub fn migrate(
+        &mut self,
+        comp_id: u64,
+        source: &Rc<RefCell<Compute>>,
+        target: Rc<RefCell<Compute>>,
+        state_size: u64,
+    ) {
+        let source_id = source.borrow().id();
+        source
+            .borrow_mut()
+            .checkpoint_computation(comp_id, state_size, self.ctx.id());
+        self.migrations.insert(
+            comp_id,
+            Migration {
+                source: source_id,
+                target,
+                checkpoint: None,
+            },
+        );
+    }
+
+    fn restore(&mut self, comp_id: u64) {
+        let migration = self.migrations.remove(&comp_id).unwrap();
+        let checkpoint = migration.checkpoint.unwrap();
+        let requester = checkpoint.request.requester;
+        log_debug!(
+            self.ctx,
+            "restoring computation {} on {}",
+            comp_id,
+            migration.target.borrow().id()
+        );
+        migration.target.borrow_mut().restore_computation(checkpoint, requester);
+    }
+}
+
+impl EventHandler for Migrator {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            CompCheckpointed { id, checkpoint } => {
+                let state_size = checkpoint.state_size;
+                let migration = self
+                    .migrations
+                    .get_mut(&id)
+                    .expect("Unexpected CompCheckpointed event in Migrator");
+                migration.checkpoint = Some(checkpoint);
+                if let Some(network) = self.network.as_ref() {
+                    let target = migration.target.borrow().id();
+                    let transfer_id =
+                        network
+                            .borrow_mut()
+                            .transfer_data(migration.source, target, state_size as f64, self.ctx.id());
+                    self.transfers.insert(transfer_id, id);
+                } else {
+                    self.restore(id);
+                }
+            }
+            CheckpointFailed { id } => {
+                log_debug!(self.ctx, "migration of computation {} is cancelled", id);
+                self.migrations.remove(&id);
+            }
+            DataTransferCompleted { dt } => {
+                let comp_id = self
+                    .transfers
+                    .remove(&dt.id)
+                    .expect("Unexpected DataTransferCompleted event in Migrator");
+                self.restore(comp_id);
+            }
+        })
+    }
+}
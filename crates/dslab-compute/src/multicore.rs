@@ -11,6 +11,11 @@ use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
 use dslab_core::{cast, EventId};
 
+use dslab_models::power::host::{HostPowerModel, HostState};
+use dslab_models::throughput_sharing::{FairThroughputSharingModel, ThroughputSharingModel};
+
+use crate::failure::FailureModel;
+
 // STRUCTS -------------------------------------------------------------------------------------------------------------
 
 /// Resource allocation.
@@ -60,6 +65,24 @@ impl CoresDependency {
     }
 }
 
+/// Mode of sharing the cores between concurrent computations.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum SharingMode {
+    /// Each core can only be used by one computation.
+    ///
+    /// The cores are allocated to a computation upon its start and are not changed afterwards.
+    /// The computation fails if there are not enough available cores.
+    SpaceShared,
+    /// Cores are shared between concurrent computations using the processor sharing model.
+    ///
+    /// Each computation runs `min(max_cores, cores_total)` threads, and all cores are evenly shared between
+    /// the threads of running computations. While the total number of threads does not exceed the number of cores,
+    /// each thread runs at the full core speed, otherwise all threads are slowed down proportionally.
+    /// The computations only reserve memory, so a computation fails only if there is not enough memory
+    /// or its `min_cores` exceeds the total number of cores.
+    TimeShared,
+}
+
 /// Reason for computation failure.
 #[derive(Clone, Debug, Serialize)]
 pub enum FailReason {
@@ -74,6 +97,52 @@ pub enum FailReason {
         /// Requested amount of memory.
         requested_memory: u64,
     },
+    /// Resource has failed.
+    ResourceFailure,
+}
+
+/// Overhead of computation checkpointing and restart.
+///
+/// The checkpoint (restart) time is computed as the fixed checkpoint (restart) time
+/// plus the time of writing (reading) the checkpoint state with the specified bandwidth.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct CheckpointOverhead {
+    /// Fixed time of writing a checkpoint.
+    pub checkpoint_time: f64,
+    /// Fixed time of restarting a computation from checkpoint.
+    pub restart_time: f64,
+    /// Bandwidth of writing and reading the checkpoint state.
+    pub bandwidth: f64,
+}
+
+impl Default for CheckpointOverhead {
+    fn default() -> Self {
+        Self {
+            checkpoint_time: 0.,
+            restart_time: 0.,
+            bandwidth: f64::INFINITY,
+        }
+    }
+}
+
+/// Checkpoint of computation which can be used to restore the computation on any resource.
+#[derive(Clone, Debug, Serialize)]
+pub struct Checkpoint {
+    /// Id of the computation.
+    pub id: u64,
+    /// Original computation request.
+    pub request: CompRequest,
+    /// Amount of flops computed before the checkpoint.
+    pub flops_done: f64,
+    /// Size of the checkpoint state.
+    pub state_size: u64,
+}
+
+impl Checkpoint {
+    /// Returns the fraction of the flops computed before the checkpoint.
+    pub fn fraction_done(&self) -> f64 {
+        self.flops_done / self.request.flops
+    }
 }
 
 /// Computation state.
@@ -83,6 +152,10 @@ pub enum ComputationState {
     Running,
     /// Computation is preempted.
     Preempted,
+    /// Checkpoint of computation is being written.
+    Checkpointing,
+    /// Computation is being restarted from checkpoint.
+    Restarting,
 }
 
 #[derive(Debug)]
@@ -92,11 +165,20 @@ struct Computation {
     cores: u32,
     state: ComputationState,
     flops_done: f64,
-    comp_finished_event_id: EventId,
+    // Completion event in space-shared mode.
+    comp_finished_event_id: Option<EventId>,
+    // Number of threads which are not finished yet in time-shared mode.
+    running_threads: u32,
+    // Whether the computation holds its memory and cores (in space-shared mode).
+    allocated: bool,
+    // Completion event of checkpointing or restart.
+    phase_event_id: Option<EventId>,
+    // State size and requester of the checkpoint being written.
+    checkpoint_request: Option<(u64, Id)>,
 }
 
 impl Computation {
-    fn new(req: CompRequest, start_time: f64, cores: u32, comp_finished_event_id: EventId) -> Self {
+    fn new(req: CompRequest, start_time: f64, cores: u32, comp_finished_event_id: Option<EventId>) -> Self {
         Computation {
             req,
             start_time,
@@ -104,10 +186,58 @@ impl Computation {
             state: ComputationState::Running,
             flops_done: 0.,
             comp_finished_event_id,
+            running_threads: cores,
+            allocated: true,
+            phase_event_id: None,
+            checkpoint_request: None,
         }
     }
 }
 
+// Tracks the time integrals of resource utilization and power consumption.
+struct UsageMeter {
+    start_time: f64,
+    last_time: f64,
+    utilization: f64,
+    power: f64,
+    utilization_integral: f64,
+    energy_consumed: f64,
+}
+
+impl UsageMeter {
+    fn new(time: f64, power: f64) -> Self {
+        Self {
+            start_time: time,
+            last_time: time,
+            utilization: 0.,
+            power,
+            utilization_integral: 0.,
+            energy_consumed: 0.,
+        }
+    }
+
+    // Invoked each time the utilization or power consumption is changed.
+    fn update(&mut self, time: f64, utilization: f64, power: f64) {
+        self.utilization_integral += (time - self.last_time) * self.utilization;
+        self.energy_consumed += (time - self.last_time) * self.power;
+        self.last_time = time;
+        self.utilization = utilization;
+        self.power = power;
+    }
+
+    fn average_utilization(&self, time: f64) -> f64 {
+        if time > self.start_time {
+            (self.utilization_integral + (time - self.last_time) * self.utilization) / (time - self.start_time)
+        } else {
+            self.utilization
+        }
+    }
+
+    fn energy_consumed(&self, time: f64) -> f64 {
+        self.energy_consumed + (time - self.last_time) * self.power
+    }
+}
+
 // EVENTS --------------------------------------------------------------------------------------------------------------
 
 /// Request to start a computation.
@@ -184,6 +314,57 @@ pub struct CompResumed {
     pub id: u64,
 }
 
+/// Computation checkpoint request.
+///
+/// The computation can be either running or preempted. The running computation is stopped, and its resources are
+/// released after the checkpoint is written.
+#[derive(Clone, Serialize)]
+pub struct CheckpointComp {
+    /// Id of the computation.
+    pub id: u64,
+    /// Size of the checkpoint state.
+    pub state_size: u64,
+    /// Id of simulation component to inform about the checkpoint result.
+    pub requester: Id,
+}
+
+/// Checkpoint is written successfully, the computation is removed from the resource.
+#[derive(Clone, Serialize)]
+pub struct CompCheckpointed {
+    /// Id of the computation.
+    pub id: u64,
+    /// Checkpoint of the computation.
+    pub checkpoint: Checkpoint,
+}
+
+/// Checkpointing is aborted since the computation was preempted, cancelled or failed before the checkpoint was written,
+/// or the computation has already finished.
+#[derive(Clone, Serialize)]
+pub struct CheckpointFailed {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Request to restore computation from checkpoint.
+#[derive(Clone, Serialize)]
+pub struct RestoreComp {
+    /// Checkpoint of the computation.
+    pub checkpoint: Checkpoint,
+    /// Id of simulation component to inform about the computation progress.
+    pub requester: Id,
+}
+
+/// Computation is restored from checkpoint successfully.
+///
+/// The computation continues its execution after the restart overhead.
+#[derive(Clone, Serialize)]
+pub struct CompRestored {
+    /// Id of the computation.
+    pub id: u64,
+    /// Number of cores allocated to the computation.
+    pub cores: u32,
+}
+
 /// Computation is finished successfully.
 #[derive(Clone, Serialize)]
 pub struct CompFinished {
@@ -191,6 +372,41 @@ pub struct CompFinished {
     pub id: u64,
 }
 
+#[derive(Clone, Serialize)]
+struct ThreadFinished {
+    id: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct CheckpointDone {
+    id: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct RestartDone {
+    id: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct FailResource {
+    duration: f64,
+}
+
+#[derive(Clone, Serialize)]
+struct RecoverResource {}
+
+/// Resource has failed, all its computations are aborted.
+///
+/// Sent to the failure listeners, the resource is identified by the event source.
+#[derive(Clone, Serialize)]
+pub struct ResourceFailed {}
+
+/// Resource has recovered after failure and can accept new requests.
+///
+/// Sent to the failure listeners, the resource is identified by the event source.
+#[derive(Clone, Serialize)]
+pub struct ResourceRecovered {}
+
 /// Computation is failed.
 #[derive(Clone, Serialize)]
 pub struct CompFailed {
@@ -256,45 +472,222 @@ pub struct DeallocationFailed {
 ///
 /// In this model, the computation request can specify the minimum and maximum number of used cores,
 /// and provide a function which defines the dependence of parallel speedup on the number of used cores.
-/// Each core can only be used by one computation. The cores allocation for each computation is computed
-/// upon the request arrival and is not changed afterwards.
-/// This model also supports the manual allocation and release of cores and memory.
+/// By default, each core can only be used by one computation. The cores allocation for each computation is computed
+/// upon the request arrival and is not changed afterwards. Alternatively, the cores can be shared between
+/// the computations, allowing to run more threads than cores with slowdown (see [`SharingMode`]).
+/// This model also supports the manual allocation and release of cores and memory. Note that in time-shared mode
+/// the manually allocated cores are only accounted in [`cores_available`](Self::cores_available)
+/// and are not excluded from sharing between computations.
+///
+/// The core speed can be changed at runtime via [`set_frequency`](Self::set_frequency) to model DVFS.
+/// The relative frequency from 0 to 1 linearly maps to the core speed between the minimum speed
+/// (see [`with_min_speed`](Self::with_min_speed)) and the maximum speed passed upon the resource creation.
+/// The running computations are rescaled to the new speed upon each frequency change.
+///
+/// The resource tracks its CPU utilization over time, which is computed as the fraction of cores used by computations
+/// (the manually allocated cores are not considered busy). If the power model is specified via
+/// [`with_power_model`](Self::with_power_model), the power consumption is computed from the current utilization and
+/// frequency, and the consumed energy is accumulated. The utilization and power are also reported as time-weighted
+/// metrics `utilization` and `power` of the component.
+///
+/// The resource can fail according to the specified [`FailureModel`] or upon the [`fail`](Self::fail) call.
+/// Upon failure all computations, including the preempted ones, are aborted with [`FailReason::ResourceFailure`],
+/// and [`ResourceFailed`] event is sent to the components registered via
+/// [`add_failure_listener`](Self::add_failure_listener). While the resource is down, it rejects new computation and
+/// allocation requests and consumes no power. Upon recovery [`ResourceRecovered`] event is sent to the listeners.
+/// The manual allocations are kept during the failure.
+///
+/// The running or preempted computation can be checkpointed via
+/// [`checkpoint_computation`](Self::checkpoint_computation) and then restored from the obtained [`Checkpoint`]
+/// on the same or another resource via [`restore_computation`](Self::restore_computation).
+/// The computation is paused during the checkpointing and restart, which take the time specified
+/// by [`CheckpointOverhead`] of the corresponding resource.
+/// See also [`Migrator`](crate::migration::Migrator) for migration of computations between the resources.
 pub struct Compute {
     speed: f64,
+    min_speed: f64,
+    max_speed: f64,
+    frequency: f64,
     cores_total: u32,
     cores_available: u32,
     memory_total: u64,
     memory_available: u64,
     computations: HashMap<u64, Computation>,
     allocations: HashMap<Id, Allocation>,
+    sharing_mode: SharingMode,
+    // Model of core sharing between the threads of computations in time-shared mode.
+    throughput_model: FairThroughputSharingModel<u64>,
+    next_thread_finish: Option<EventId>,
+    power_model: Option<HostPowerModel>,
+    usage_meter: UsageMeter,
+    failed: bool,
+    failure_model: Option<FailureModel>,
+    next_failure: Option<EventId>,
+    failure_listeners: Vec<Id>,
+    // Requesters of computations aborted by the current failure, used to reply to requests which are in flight.
+    failed_computations: HashMap<u64, Id>,
+    checkpoint_overhead: CheckpointOverhead,
     ctx: SimulationContext,
 }
 
 impl Compute {
     /// Creates a new computing resource.
     pub fn new(speed: f64, cores: u32, memory: u64, ctx: SimulationContext) -> Self {
+        ctx.metrics().update_time_weighted("utilization", 0.);
+        ctx.metrics().update_time_weighted("power", 0.);
         Self {
             speed,
+            min_speed: speed,
+            max_speed: speed,
+            frequency: 1.,
             cores_total: cores,
             cores_available: cores,
             memory_total: memory,
             memory_available: memory,
             computations: HashMap::new(),
             allocations: HashMap::new(),
+            sharing_mode: SharingMode::SpaceShared,
+            throughput_model: make_throughput_model(speed, cores),
+            next_thread_finish: None,
+            power_model: None,
+            usage_meter: UsageMeter::new(ctx.time(), 0.),
+            failed: false,
+            failure_model: None,
+            next_failure: None,
+            failure_listeners: Vec::new(),
+            failed_computations: HashMap::new(),
+            checkpoint_overhead: CheckpointOverhead::default(),
             ctx,
         }
     }
 
+    /// Sets the mode of sharing the cores between computations (space-shared by default).
+    pub fn with_sharing_mode(mut self, sharing_mode: SharingMode) -> Self {
+        self.sharing_mode = sharing_mode;
+        self
+    }
+
+    /// Sets the core speed corresponding to the minimum frequency (equals to the maximum speed by default).
+    pub fn with_min_speed(mut self, min_speed: f64) -> Self {
+        assert!(
+            min_speed > 0. && min_speed <= self.max_speed,
+            "Minimum speed should be positive and not greater than the maximum speed"
+        );
+        self.min_speed = min_speed;
+        self
+    }
+
+    /// Sets the model used to compute the power consumption.
+    pub fn with_power_model(mut self, power_model: HostPowerModel) -> Self {
+        self.power_model = Some(power_model);
+        self.update_usage();
+        self
+    }
+
+    /// Sets the model of resource failures.
+    pub fn with_failure_model(mut self, failure_model: FailureModel) -> Self {
+        if let FailureModel::Trace { intervals } = &failure_model {
+            for (failure_time, recovery_time) in intervals.iter() {
+                self.ctx.emit_self(
+                    FailResource {
+                        duration: recovery_time - failure_time,
+                    },
+                    (failure_time - self.ctx.time()).max(0.),
+                );
+            }
+        }
+        self.failure_model = Some(failure_model);
+        self.schedule_failure();
+        self
+    }
+
+    /// Sets the overhead of computation checkpointing and restart (zero by default).
+    pub fn with_checkpoint_overhead(mut self, overhead: CheckpointOverhead) -> Self {
+        self.checkpoint_overhead = overhead;
+        self
+    }
+
+    /// Registers the component which will be notified about the resource failures and recoveries.
+    pub fn add_failure_listener(&mut self, listener: Id) {
+        self.failure_listeners.push(listener);
+    }
+
     /// Returns id of corresponding simulation component.
     pub fn id(&self) -> Id {
         self.ctx.id()
     }
 
-    /// Returns the core speed.
+    /// Returns the current core speed.
     pub fn speed(&self) -> f64 {
         self.speed
     }
 
+    /// Returns the core speed at the minimum frequency.
+    pub fn min_speed(&self) -> f64 {
+        self.min_speed
+    }
+
+    /// Returns the core speed at the maximum frequency.
+    pub fn max_speed(&self) -> f64 {
+        self.max_speed
+    }
+
+    /// Returns the current relative frequency from 0 (minimum speed) to 1 (maximum speed).
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Sets the relative frequency from 0 (minimum speed) to 1 (maximum speed).
+    ///
+    /// The new speed is applied immediately, and the completion times of running computations are updated accordingly.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        assert!((0. ..=1.).contains(&frequency), "Frequency should be in [0, 1] range");
+        let old_speed = self.speed;
+        self.frequency = frequency;
+        self.speed = self.min_speed + frequency * (self.max_speed - self.min_speed);
+        if self.speed != old_speed {
+            self.rescale_computations(old_speed);
+        }
+        self.update_usage();
+    }
+
+    /// Returns whether the resource is currently failed.
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Fails the resource for the specified duration.
+    ///
+    /// Has no effect if the resource is already failed at the time of the request processing.
+    pub fn fail(&mut self, duration: f64) {
+        self.ctx.emit_self_now(FailResource { duration });
+    }
+
+    /// Returns the current CPU utilization from 0 to 1.
+    pub fn utilization(&self) -> f64 {
+        self.usage_meter.utilization
+    }
+
+    /// Returns the time-weighted average CPU utilization since the resource creation.
+    pub fn average_utilization(&self) -> f64 {
+        self.usage_meter.average_utilization(self.ctx.time())
+    }
+
+    /// Returns the current power consumption (zero if the power model is not specified).
+    pub fn power(&self) -> f64 {
+        self.usage_meter.power
+    }
+
+    /// Returns the total energy consumed since the resource creation (zero if the power model is not specified).
+    pub fn energy_consumed(&self) -> f64 {
+        self.usage_meter.energy_consumed(self.ctx.time())
+    }
+
+    /// Returns the mode of sharing the cores between computations.
+    pub fn sharing_mode(&self) -> SharingMode {
+        self.sharing_mode
+    }
+
     /// Returns the total number of cores.
     pub fn cores_total(&self) -> u32 {
         self.cores_total
@@ -315,7 +708,8 @@ impl Compute {
         self.memory_available
     }
 
-    /// Returns the minimum compute time for a workload with given flops, cores and cores dependency.
+    /// Returns the minimum compute time for a workload with given flops, cores and cores dependency
+    /// at the current core speed.
     pub fn min_compute_time(
         &self,
         flops: f64,
@@ -334,12 +728,16 @@ impl Compute {
     pub fn fraction_done(&self, comp_id: EventId) -> Result<f64, &str> {
         if let Some(computation) = self.computations.get(&comp_id) {
             match computation.state {
+                ComputationState::Running if self.sharing_mode == SharingMode::TimeShared => {
+                    let flops_left = self.thread_flops_left(comp_id, computation);
+                    Ok((computation.req.flops - flops_left) / computation.req.flops)
+                }
                 ComputationState::Running => {
                     let speedup = computation.req.cores_dependency.speedup(computation.cores);
                     let flops_computed = (self.ctx.time() - computation.start_time) * self.speed * speedup;
                     Ok((computation.flops_done + flops_computed) / computation.req.flops)
                 }
-                ComputationState::Preempted => Ok(computation.flops_done / computation.req.flops),
+                _ => Ok(computation.flops_done / computation.req.flops),
             }
         } else {
             Err("Computation does not exist")
@@ -378,10 +776,31 @@ impl Compute {
     }
 
     /// Resumes computation which has been preempted.
+    ///
+    /// If the computation is aborted by the resource failure before the request is processed,
+    /// the requester receives [`CompFailed`] event with [`FailReason::ResourceFailure`].
     pub fn resume_computation(&mut self, comp_id: u64) {
         self.ctx.emit_self_now(ResumeComp { id: comp_id });
     }
 
+    /// Writes checkpoint of running or preempted computation with specified state size.
+    ///
+    /// The checkpoint is sent to the requester via [`CompCheckpointed`] event. If the computation is preempted,
+    /// cancelled or failed before the checkpoint is written, the requester receives [`CheckpointFailed`] event instead.
+    /// The same applies if the computation no longer exists when the request is processed, e.g. it has just finished.
+    pub fn checkpoint_computation(&mut self, comp_id: u64, state_size: u64, requester: Id) {
+        self.ctx.emit_self_now(CheckpointComp {
+            id: comp_id,
+            state_size,
+            requester,
+        });
+    }
+
+    /// Restores computation from checkpoint, the computation keeps its original id.
+    pub fn restore_computation(&mut self, checkpoint: Checkpoint, requester: Id) {
+        self.ctx.emit_self_now(RestoreComp { checkpoint, requester });
+    }
+
     /// Requests resource allocation with given parameters and returns allocation id.
     pub fn allocate(&mut self, cores: u32, memory: u64, requester: Id) -> u64 {
         let request = AllocationRequest {
@@ -400,23 +819,232 @@ impl Compute {
         self.ctx.emit_self_now(request)
     }
 
-    fn stop_computation(&mut self, comp_id: u64, preempt: bool) {
-        if let Some(computation) = self.computations.get_mut(&comp_id) {
-            if computation.state == ComputationState::Running {
-                computation.state = ComputationState::Preempted;
+    // Returns the number of cores which can be used by a new computation.
+    fn cores_for_computations(&self) -> u32 {
+        match self.sharing_mode {
+            SharingMode::SpaceShared => self.cores_available,
+            SharingMode::TimeShared => self.cores_total,
+        }
+    }
+
+    // Starts the threads of computation in time-shared mode, the flops are evenly split between the threads.
+    fn start_threads(&mut self, comp_id: u64, threads: u32, flops: f64, cores_dependency: CoresDependency) {
+        let thread_flops = flops / cores_dependency.speedup(threads);
+        for _ in 0..threads {
+            self.throughput_model.insert(comp_id, thread_flops, &mut self.ctx);
+        }
+        self.schedule_next_thread_finish();
+    }
+
+    fn schedule_next_thread_finish(&mut self) {
+        if let Some(event_id) = self.next_thread_finish.take() {
+            self.ctx.cancel_event(event_id);
+        }
+        if let Some((time, comp_id)) = self.throughput_model.peek() {
+            let delay = (time - self.ctx.time()).max(0.);
+            self.next_thread_finish = Some(self.ctx.emit_self(ThreadFinished { id: *comp_id }, delay));
+        }
+    }
+
+    // Returns the number of flops left to compute by running computation in time-shared mode.
+    fn thread_flops_left(&self, comp_id: u64, computation: &Computation) -> f64 {
+        let thread_flops_left = self
+            .throughput_model
+            .remaining_volume(|id| *id == comp_id, &self.ctx)
+            .first()
+            .map_or(0., |(_, volume)| *volume);
+        thread_flops_left * computation.req.cores_dependency.speedup(computation.cores)
+    }
+
+    // Schedules the next failure in the random failure model.
+    fn schedule_failure(&mut self) {
+        if let Some(FailureModel::Random {
+            time_to_failure,
+            time_to_recovery,
+        }) = &self.failure_model
+        {
+            let delay = self.ctx.sample_from_distribution(time_to_failure).max(0.);
+            let duration = self.ctx.sample_from_distribution(time_to_recovery).max(0.);
+            self.next_failure = Some(self.ctx.emit_self(FailResource { duration }, delay));
+        }
+    }
+
+    // Aborts all computations and notifies the listeners about the failure.
+    fn on_failure(&mut self, duration: f64) {
+        self.failed = true;
+        if let Some(event_id) = self.next_failure.take() {
+            self.ctx.cancel_event(event_id);
+        }
+        let mut comp_ids: Vec<u64> = self.computations.keys().copied().collect();
+        comp_ids.sort();
+        for comp_id in comp_ids {
+            self.stop_progress(comp_id);
+            self.release_resources(comp_id);
+            self.abort_checkpoint(comp_id);
+            let computation = self.computations.remove(&comp_id).unwrap();
+            self.ctx.emit_now(
+                CompFailed {
+                    id: comp_id,
+                    reason: FailReason::ResourceFailure,
+                },
+                computation.req.requester,
+            );
+            self.failed_computations.insert(comp_id, computation.req.requester);
+        }
+        for listener in self.failure_listeners.iter() {
+            self.ctx.emit_now(ResourceFailed {}, *listener);
+        }
+        self.ctx.emit_self(RecoverResource {}, duration);
+    }
+
+    // Updates the progress of running computations after the core speed is changed.
+    fn rescale_computations(&mut self, old_speed: f64) {
+        match self.sharing_mode {
+            SharingMode::SpaceShared => {
+                let mut comp_ids: Vec<u64> = self.computations.keys().copied().collect();
+                comp_ids.sort();
+                for comp_id in comp_ids {
+                    let computation = self.computations.get_mut(&comp_id).unwrap();
+                    if let Some(event_id) = computation.comp_finished_event_id {
+                        self.ctx.cancel_event(event_id);
+                        let speedup = computation.req.cores_dependency.speedup(computation.cores);
+                        computation.flops_done += (self.ctx.time() - computation.start_time) * old_speed * speedup;
+                        computation.start_time = self.ctx.time();
+                        let compute_time =
+                            (computation.req.flops - computation.flops_done).max(0.) / self.speed / speedup;
+                        computation.comp_finished_event_id =
+                            Some(self.ctx.emit_self(CompFinished { id: comp_id }, compute_time));
+                    }
+                }
+            }
+            SharingMode::TimeShared => {
+                // the remaining volumes are returned in the order of insertion, which is preserved in the new model
+                let threads = self.throughput_model.remove(|_| true, &self.ctx);
+                self.throughput_model = make_throughput_model(self.speed, self.cores_total);
+                for (comp_id, volume) in threads {
+                    self.throughput_model.insert(comp_id, volume, &mut self.ctx);
+                }
+                self.schedule_next_thread_finish();
+            }
+        }
+    }
+
+    // Updates the utilization and power consumption, should be called after each change of running computations.
+    fn update_usage(&mut self) {
+        let running = self
+            .computations
+            .values()
+            .filter(|c| c.state == ComputationState::Running);
+        let busy_cores = match self.sharing_mode {
+            SharingMode::SpaceShared => running.map(|c| c.cores).sum::<u32>(),
+            SharingMode::TimeShared => running.map(|c| c.running_threads).sum::<u32>().min(self.cores_total),
+        };
+        let utilization = busy_cores as f64 / self.cores_total as f64;
+        let power = self.power_model.as_ref().filter(|_| !self.failed).map_or(0., |model| {
+            model.get_power(HostState::cpu_util_freq(utilization, self.frequency))
+        });
+        if utilization != self.usage_meter.utilization || power != self.usage_meter.power {
+            self.usage_meter.update(self.ctx.time(), utilization, power);
+            self.ctx.metrics().update_time_weighted("utilization", utilization);
+            self.ctx.metrics().update_time_weighted("power", power);
+        }
+    }
+
+    // Stops the progress of computation and updates the amount of computed flops.
+    fn stop_progress(&mut self, comp_id: u64) {
+        let computation = self.computations.get_mut(&comp_id).unwrap();
+        if let Some(event_id) = computation.phase_event_id.take() {
+            self.ctx.cancel_event(event_id);
+        }
+        if computation.state != ComputationState::Running {
+            return;
+        }
+        let speedup = computation.req.cores_dependency.speedup(computation.cores);
+        match self.sharing_mode {
+            SharingMode::SpaceShared => {
+                if let Some(event_id) = computation.comp_finished_event_id.take() {
+                    self.ctx.cancel_event(event_id);
+                }
+                computation.flops_done += (self.ctx.time() - computation.start_time) * self.speed * speedup;
+            }
+            SharingMode::TimeShared => {
+                let thread_flops_left = self
+                    .throughput_model
+                    .remove(|id| *id == comp_id, &self.ctx)
+                    .first()
+                    .map_or(0., |(_, volume)| *volume);
+                computation.flops_done = computation.req.flops - thread_flops_left * speedup;
+                computation.running_threads = 0;
+                self.schedule_next_thread_finish();
+            }
+        }
+    }
+
+    // Starts or continues the progress of computation which holds its resources.
+    fn start_progress(&mut self, comp_id: u64) {
+        let computation = self.computations.get_mut(&comp_id).unwrap();
+        let flops_left = computation.req.flops - computation.flops_done;
+        let cores = computation.cores;
+        computation.start_time = self.ctx.time();
+        computation.state = ComputationState::Running;
+        computation.running_threads = cores;
+
+        match self.sharing_mode {
+            SharingMode::SpaceShared => {
+                let speedup = computation.req.cores_dependency.speedup(cores);
+                let compute_time = flops_left / self.speed / speedup;
+                computation.comp_finished_event_id =
+                    Some(self.ctx.emit_self(CompFinished { id: comp_id }, compute_time));
+            }
+            SharingMode::TimeShared => {
+                let cores_dependency = computation.req.cores_dependency;
+                self.start_threads(comp_id, cores, flops_left, cores_dependency);
+            }
+        }
+    }
 
-                self.ctx.cancel_event(computation.comp_finished_event_id);
+    // Allocates memory and cores (in space-shared mode) to computation.
+    fn allocate_resources(&mut self, comp_id: u64) {
+        let computation = self.computations.get_mut(&comp_id).unwrap();
+        computation.allocated = true;
+        self.memory_available -= computation.req.memory;
+        if self.sharing_mode == SharingMode::SpaceShared {
+            self.cores_available -= computation.cores;
+        }
+    }
 
-                self.memory_available += computation.req.memory;
+    // Releases the resources held by computation.
+    fn release_resources(&mut self, comp_id: u64) {
+        let computation = self.computations.get_mut(&comp_id).unwrap();
+        if computation.allocated {
+            computation.allocated = false;
+            self.memory_available += computation.req.memory;
+            if self.sharing_mode == SharingMode::SpaceShared {
                 self.cores_available += computation.cores;
+            }
+        }
+    }
 
-                let speedup = computation.req.cores_dependency.speedup(computation.cores);
-                let flops_computed = (self.ctx.time() - computation.start_time) * self.speed * speedup;
+    // Notifies the checkpoint requester if the computation is being checkpointed.
+    fn abort_checkpoint(&mut self, comp_id: u64) {
+        let computation = self.computations.get_mut(&comp_id).unwrap();
+        if let Some((_, requester)) = computation.checkpoint_request.take() {
+            self.ctx.emit_now(CheckpointFailed { id: comp_id }, requester);
+        }
+    }
 
-                computation.flops_done += flops_computed;
+    fn stop_computation(&mut self, comp_id: u64, preempt: bool) {
+        if let Some(computation) = self.computations.get(&comp_id) {
+            if computation.state != ComputationState::Preempted {
+                // checkpointing or restart of computation is also aborted
+                self.stop_progress(comp_id);
+                self.release_resources(comp_id);
+                self.abort_checkpoint(comp_id);
             } else if preempt {
                 panic!("Computation is already preempted");
             }
+            let computation = self.computations.get_mut(&comp_id).unwrap();
+            computation.state = ComputationState::Preempted;
 
             if preempt {
                 self.ctx.emit_now(
@@ -451,12 +1079,21 @@ impl EventHandler for Compute {
                 ref cores_dependency,
                 requester,
             } => {
-                if self.memory_available < memory || self.cores_available < min_cores {
+                let cores_available = self.cores_for_computations();
+                if self.failed {
+                    self.ctx.emit_now(
+                        CompFailed {
+                            id: event.id,
+                            reason: FailReason::ResourceFailure,
+                        },
+                        requester,
+                    );
+                } else if self.memory_available < memory || cores_available < min_cores {
                     self.ctx.emit_now(
                         CompFailed {
                             id: event.id,
                             reason: FailReason::NotEnoughResources {
-                                available_cores: self.cores_available,
+                                available_cores: cores_available,
                                 available_memory: self.memory_available,
                                 requested_cores: min_cores,
                                 requested_memory: memory,
@@ -465,15 +1102,21 @@ impl EventHandler for Compute {
                         requester,
                     );
                 } else {
-                    let cores = self.cores_available.min(max_cores);
+                    let cores = cores_available.min(max_cores);
                     self.memory_available -= memory;
-                    self.cores_available -= cores;
                     self.ctx.emit_now(CompStarted { id: event.id, cores }, requester);
 
-                    let speedup = cores_dependency.speedup(cores);
-
-                    let compute_time = flops / self.speed / speedup;
-                    let comp_finished_event_id = self.ctx.emit_self(CompFinished { id: event.id }, compute_time);
+                    let comp_finished_event_id = match self.sharing_mode {
+                        SharingMode::SpaceShared => {
+                            self.cores_available -= cores;
+                            let compute_time = flops / self.speed / cores_dependency.speedup(cores);
+                            Some(self.ctx.emit_self(CompFinished { id: event.id }, compute_time))
+                        }
+                        SharingMode::TimeShared => {
+                            self.start_threads(event.id, cores, flops, *cores_dependency);
+                            None
+                        }
+                    };
 
                     let req = CompRequest {
                         flops,
@@ -496,6 +1139,18 @@ impl EventHandler for Compute {
                 self.stop_computation(id, true);
             }
             ResumeComp { id } => {
+                let cores_available = self.cores_for_computations();
+                if let Some(&requester) = self.failed_computations.get(&id) {
+                    // the preempted computation is aborted by the failure
+                    self.ctx.emit_now(
+                        CompFailed {
+                            id,
+                            reason: FailReason::ResourceFailure,
+                        },
+                        requester,
+                    );
+                    return;
+                }
                 let computation = self
                     .computations
                     .get_mut(&id)
@@ -505,12 +1160,12 @@ impl EventHandler for Compute {
                     panic!("Computation is already running");
                 }
 
-                if self.memory_available < computation.req.memory || self.cores_available < computation.req.min_cores {
+                if self.memory_available < computation.req.memory || cores_available < computation.req.min_cores {
                     self.ctx.emit_now(
                         CompFailed {
                             id,
                             reason: FailReason::NotEnoughResources {
-                                available_cores: self.cores_available,
+                                available_cores: cores_available,
                                 available_memory: self.memory_available,
                                 requested_cores: computation.req.min_cores,
                                 requested_memory: computation.req.memory,
@@ -519,22 +1174,105 @@ impl EventHandler for Compute {
                         computation.req.requester,
                     );
                 } else {
-                    let cores = self.cores_available.min(computation.req.max_cores);
-                    self.memory_available -= computation.req.memory;
-                    self.cores_available -= cores;
+                    computation.cores = cores_available.min(computation.req.max_cores);
                     self.ctx.emit_now(CompResumed { id }, computation.req.requester);
-
-                    let speedup = computation.req.cores_dependency.speedup(cores);
-
-                    let compute_time = (computation.req.flops - computation.flops_done) / self.speed / speedup;
-
-                    computation.comp_finished_event_id = self.ctx.emit_self(CompFinished { id }, compute_time);
-
-                    computation.cores = cores;
-                    computation.start_time = self.ctx.time();
-                    computation.state = ComputationState::Running;
+                    self.allocate_resources(id);
+                    self.start_progress(id);
+                }
+            }
+            CheckpointComp {
+                id,
+                state_size,
+                requester,
+            } => {
+                // the computation could be finished, cancelled or failed at the same time
+                let Some(computation) = self.computations.get(&id) else {
+                    self.ctx.emit_now(CheckpointFailed { id }, requester);
+                    return;
+                };
+                if computation.state != ComputationState::Running && computation.state != ComputationState::Preempted {
+                    panic!("Computation is already being checkpointed or restarted");
+                }
+                self.stop_progress(id);
+                let overhead = self.checkpoint_overhead;
+                let checkpoint_time = overhead.checkpoint_time + state_size as f64 / overhead.bandwidth;
+                let computation = self.computations.get_mut(&id).unwrap();
+                computation.state = ComputationState::Checkpointing;
+                computation.checkpoint_request = Some((state_size, requester));
+                computation.phase_event_id = Some(self.ctx.emit_self(CheckpointDone { id }, checkpoint_time));
+            }
+            CheckpointDone { id } => {
+                self.release_resources(id);
+                let computation = self.computations.remove(&id).unwrap();
+                let (state_size, requester) = computation.checkpoint_request.unwrap();
+                let checkpoint = Checkpoint {
+                    id,
+                    request: computation.req,
+                    flops_done: computation.flops_done,
+                    state_size,
+                };
+                self.ctx.emit_now(CompCheckpointed { id, checkpoint }, requester);
+            }
+            RestoreComp {
+                ref checkpoint,
+                requester,
+            } => {
+                let id = checkpoint.id;
+                let cores_available = self.cores_for_computations();
+                let req = &checkpoint.request;
+                if self.failed {
+                    self.ctx.emit_now(
+                        CompFailed {
+                            id,
+                            reason: FailReason::ResourceFailure,
+                        },
+                        requester,
+                    );
+                } else if self.memory_available < req.memory || cores_available < req.min_cores {
+                    self.ctx.emit_now(
+                        CompFailed {
+                            id,
+                            reason: FailReason::NotEnoughResources {
+                                available_cores: cores_available,
+                                available_memory: self.memory_available,
+                                requested_cores: req.min_cores,
+                                requested_memory: req.memory,
+                            },
+                        },
+                        requester,
+                    );
+                } else {
+                    assert!(
+                        !self.computations.contains_key(&id),
+                        "Computation {} already exists",
+                        id
+                    );
+                    let cores = cores_available.min(req.max_cores);
+                    let mut computation = Computation::new(
+                        CompRequest {
+                            requester,
+                            ..req.clone()
+                        },
+                        self.ctx.time(),
+                        cores,
+                        None,
+                    );
+                    computation.state = ComputationState::Restarting;
+                    computation.flops_done = checkpoint.flops_done;
+                    computation.running_threads = 0;
+                    computation.allocated = false;
+                    let overhead = self.checkpoint_overhead;
+                    let restart_time = overhead.restart_time + checkpoint.state_size as f64 / overhead.bandwidth;
+                    computation.phase_event_id = Some(self.ctx.emit_self(RestartDone { id }, restart_time));
+                    self.computations.insert(id, computation);
+                    self.allocate_resources(id);
+                    self.ctx.emit_now(CompRestored { id, cores }, requester);
                 }
             }
+            RestartDone { id } => {
+                self.computations.get_mut(&id).unwrap().phase_event_id = None;
+                self.start_progress(id);
+            }
             CompFinished { id } => {
                 let running_computation = self
                     .computations
@@ -545,8 +1283,45 @@ impl EventHandler for Compute {
                 self.ctx
                     .emit(CompFinished { id }, running_computation.req.requester, 0.);
             }
+            ThreadFinished { id } => {
+                let (_, comp_id) = self.throughput_model.pop().unwrap();
+                assert!(comp_id == id, "Got unexpected ThreadFinished event");
+                self.next_thread_finish = None;
+                let computation = self
+                    .computations
+                    .get_mut(&id)
+                    .expect("Unexpected ThreadFinished event in Compute");
+                computation.running_threads -= 1;
+                if computation.running_threads == 0 {
+                    let computation = self.computations.remove(&id).unwrap();
+                    self.memory_available += computation.req.memory;
+                    self.ctx.emit_now(CompFinished { id }, computation.req.requester);
+                }
+                self.schedule_next_thread_finish();
+            }
+            FailResource { duration } => {
+                if !self.failed {
+                    self.on_failure(duration);
+                }
+            }
+            RecoverResource {} => {
+                self.failed = false;
+                self.failed_computations.clear();
+                for listener in self.failure_listeners.iter() {
+                    self.ctx.emit_now(ResourceRecovered {}, *listener);
+                }
+                self.schedule_failure();
+            }
             AllocationRequest { allocation, requester } => {
-                if self.memory_available < allocation.memory || self.cores_available < allocation.cores {
+                if self.failed {
+                    self.ctx.emit_now(
+                        AllocationFailed {
+                            id: event.id,
+                            reason: FailReason::ResourceFailure,
+                        },
+                        requester,
+                    );
+                } else if self.memory_available < allocation.memory || self.cores_available < allocation.cores {
                     self.ctx.emit_now(
                         AllocationFailed {
                             id: event.id,
@@ -600,6 +1375,13 @@ impl EventHandler for Compute {
                     self.allocations.remove(&requester);
                 }
             }
-        })
+        });
+        self.update_usage();
     }
 }
+
+fn make_throughput_model(speed: f64, cores: u32) -> FairThroughputSharingModel<u64> {
+    FairThroughputSharingModel::with_dynamic_throughput(Box::new(move |threads| {
+        speed * threads.min(cores as usize) as f64
+    }))
+}
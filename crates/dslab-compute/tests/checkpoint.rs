@@ -0,0 +1,295 @@
+mod common;
+use common::{build_compute, Recorder};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_compute::migration::Migrator;
+use dslab_compute::multicore::*;
+use dslab_core::Simulation;
+use dslab_network::models::{ConstantBandwidthNetworkModel, SharedBandwidthNetworkModel};
+use dslab_network::Network;
+
+const OVERHEAD: CheckpointOverhead = CheckpointOverhead {
+    checkpoint_time: 1.,
+    restart_time: 2.,
+    bandwidth: 100.,
+};
+
+fn events(recorder: &Rc<RefCell<Recorder>>) -> Vec<(f64, String)> {
+    recorder.borrow().events.clone()
+}
+
+#[test]
+fn test_checkpoint_and_restore() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute1 = build_compute(&mut sim, "compute1", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+    let compute2 = build_compute(&mut sim, "compute2", 20., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+
+    let id = compute1.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(5.);
+    compute1.borrow_mut().checkpoint_computation(id, 100, rid);
+    // the resources are held until the checkpoint is written
+    sim.step_until_time(6.);
+    assert_eq!(compute1.borrow().cores_available(), 0);
+    assert_eq!(compute1.borrow().memory_available(), 50);
+    assert_eq!(compute1.borrow().fraction_done(id), Ok(0.25));
+    sim.step_until_no_events();
+    assert_eq!(compute1.borrow().cores_available(), 2);
+    assert_eq!(compute1.borrow().memory_available(), 100);
+    assert!(compute1.borrow().fraction_done(id).is_err());
+
+    let checkpoint = recorder.borrow().checkpoints[0].clone();
+    assert_eq!(checkpoint.id, id);
+    assert_eq!(checkpoint.flops_done, 100.);
+    assert_eq!(checkpoint.state_size, 100);
+    compute2.borrow_mut().restore_computation(checkpoint, rid);
+    sim.step_until_time(8.);
+    assert_eq!(compute2.borrow().cores_available(), 0);
+    assert_eq!(compute2.borrow().memory_available(), 50);
+    assert_eq!(compute2.borrow().fraction_done(id), Ok(0.25));
+    sim.step_until_no_events();
+
+    // checkpoint: 1 + 100 / 100 = 2, restart: 2 + 100 / 100 = 3, remaining 300 flops at 40 flop/s: 7.5
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (7., format!("checkpointed {} at 0.25", id)),
+            (7., format!("restored {} on 2 cores", id)),
+            (17.5, format!("finished {}", id)),
+        ]
+    );
+    assert_eq!(compute2.borrow().cores_available(), 2);
+    assert_eq!(compute2.borrow().memory_available(), 100);
+}
+
+#[test]
+fn test_checkpoint_preempted() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+
+    let id = compute.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(5.);
+    compute.borrow_mut().preempt_computation(id);
+    sim.step_until_time(6.);
+    compute.borrow_mut().checkpoint_computation(id, 100, rid);
+    sim.step_until_no_events();
+    let checkpoint = recorder.borrow().checkpoints[0].clone();
+    assert_eq!(checkpoint.flops_done, 100.);
+    compute.borrow_mut().restore_computation(checkpoint, rid);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (5., format!("preempted {} at 0.25", id)),
+            (8., format!("checkpointed {} at 0.25", id)),
+            (8., format!("restored {} on 2 cores", id)),
+            (26., format!("finished {}", id)),
+        ]
+    );
+}
+
+#[test]
+fn test_restore_failures() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+
+    let checkpoint = |id: u64, min_cores: u32, memory: u64| Checkpoint {
+        id,
+        request: CompRequest {
+            flops: 200.,
+            memory,
+            min_cores,
+            max_cores: 2,
+            cores_dependency: CoresDependency::Linear,
+            requester: rid,
+        },
+        flops_done: 100.,
+        state_size: 0,
+    };
+    compute.borrow_mut().restore_computation(checkpoint(1, 4, 50), rid);
+    compute.borrow_mut().restore_computation(checkpoint(2, 1, 200), rid);
+    sim.step_until_no_events();
+    compute.borrow_mut().fail(10.);
+    sim.step();
+    compute.borrow_mut().restore_computation(checkpoint(3, 1, 50), rid);
+    sim.step_until_no_events();
+    compute.borrow_mut().restore_computation(checkpoint(4, 1, 50), rid);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., "failed 1 NotEnoughResources".to_string()),
+            (0., "failed 2 NotEnoughResources".to_string()),
+            (0., "failed 3 ResourceFailure".to_string()),
+            (10., "restored 4 on 2 cores".to_string()),
+            (17., "finished 4".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_checkpoint_aborted() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+
+    let id = compute.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(5.);
+    compute.borrow_mut().checkpoint_computation(id, 100, rid);
+    sim.step_until_time(6.);
+    compute.borrow_mut().preempt_computation(id);
+    sim.step_until_time(6.);
+    compute.borrow_mut().resume_computation(id);
+    sim.step_until_no_events();
+
+    let id2 = compute.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(25.);
+    compute.borrow_mut().checkpoint_computation(id2, 100, rid);
+    sim.step_until_time(26.);
+    compute.borrow_mut().fail(1.);
+    sim.step_until_no_events();
+
+    assert!(recorder.borrow().checkpoints.is_empty());
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (6., format!("checkpoint failed {}", id)),
+            (6., format!("preempted {} at 0.25", id)),
+            (6., format!("resumed {}", id)),
+            (21., format!("finished {}", id)),
+            (21., format!("started {} on 2 cores", id2)),
+            (26., format!("checkpoint failed {}", id2)),
+            (26., format!("failed {} ResourceFailure", id2)),
+        ]
+    );
+}
+
+#[test]
+fn test_checkpoint_finished() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+
+    // the checkpoint is requested at the time when the computation finishes
+    let id = compute.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(20.);
+    compute.borrow_mut().checkpoint_computation(id, 100, rid);
+    sim.step_until_no_events();
+
+    assert!(recorder.borrow().checkpoints.is_empty());
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (20., format!("finished {}", id)),
+            (20., format!("checkpoint failed {}", id)),
+        ]
+    );
+}
+
+#[test]
+fn test_migration_cancelled() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute1 = build_compute(&mut sim, "compute1", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+    let compute2 = build_compute(&mut sim, "compute2", 20., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+    let migrator = Rc::new(RefCell::new(Migrator::new(sim.create_context("migrator"))));
+    sim.add_handler("migrator", migrator.clone());
+
+    let id = compute1.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(5.);
+    migrator.borrow_mut().migrate(id, &compute1, compute2.clone(), 100);
+    sim.step_until_time(6.);
+    compute1.borrow_mut().preempt_computation(id);
+    sim.step_until_time(6.);
+    // the aborted migration does not prevent the next one
+    migrator.borrow_mut().migrate(id, &compute1, compute2.clone(), 100);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (6., format!("preempted {} at 0.25", id)),
+            (8., format!("restored {} on 2 cores", id)),
+            (18.5, format!("finished {}", id)),
+        ]
+    );
+    assert_eq!(compute1.borrow().cores_available(), 2);
+}
+
+#[test]
+fn test_migration_over_network() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let network = Rc::new(RefCell::new(Network::new(
+        Box::new(ConstantBandwidthNetworkModel::new(50., 0.)),
+        sim.create_context("net"),
+    )));
+    sim.add_handler("net", network.clone());
+    let compute1 = build_compute(&mut sim, "compute1", 10., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+    let compute2 = build_compute(&mut sim, "compute2", 20., 2, 100, |c| {
+        c.with_checkpoint_overhead(OVERHEAD)
+    });
+    for (host, compute) in [("host1", &compute1), ("host2", &compute2)] {
+        let mut network = network.borrow_mut();
+        network.add_node(host, Box::new(SharedBandwidthNetworkModel::new(1000., 0.)));
+        network.set_location(compute.borrow().id(), host);
+    }
+    let migrator = Rc::new(RefCell::new(
+        Migrator::new(sim.create_context("migrator")).with_network(network),
+    ));
+    sim.add_handler("migrator", migrator.clone());
+
+    let id = compute1.borrow_mut().run(400., 50, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(5.);
+    migrator.borrow_mut().migrate(id, &compute1, compute2.clone(), 100);
+    sim.step_until_no_events();
+
+    // checkpoint is written at 7 and transferred at 50 B/s until 9, then restart takes 3 seconds
+    // and remaining 300 flops are computed at 40 flop/s
+    assert_eq!(
+        events(&recorder),
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (9., format!("restored {} on 2 cores", id)),
+            (19.5, format!("finished {}", id)),
+        ]
+    );
+    assert_eq!(compute1.borrow().cores_available(), 2);
+    assert_eq!(compute2.borrow().cores_available(), 2);
+}
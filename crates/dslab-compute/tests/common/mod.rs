@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_compute::multicore::*;
+use dslab_core::{cast, Event, EventHandler, Id, Simulation};
+
+// Creates a computing resource with the specified properties and registers it in the simulation,
+// the resource can be further configured via `configure` function.
+pub fn build_compute<F>(
+    sim: &mut Simulation,
+    name: &str,
+    speed: f64,
+    cores: u32,
+    memory: u64,
+    configure: F,
+) -> Rc<RefCell<Compute>>
+where
+    F: FnOnce(Compute) -> Compute,
+{
+    let compute = configure(Compute::new(speed, cores, memory, sim.create_context(name)));
+    let compute = Rc::new(RefCell::new(compute));
+    sim.add_handler(name, compute.clone());
+    compute
+}
+
+fn reason_name(reason: &FailReason) -> &str {
+    match reason {
+        FailReason::NotEnoughResources { .. } => "NotEnoughResources",
+        FailReason::ResourceFailure => "ResourceFailure",
+    }
+}
+
+// Records the events received from computing resources as (time, description) pairs.
+pub struct Recorder {
+    pub id: Id,
+    pub events: Vec<(f64, String)>,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl Recorder {
+    pub fn new(sim: &mut Simulation, name: &str) -> Rc<RefCell<Self>> {
+        let recorder = Rc::new(RefCell::new(Self {
+            id: sim.create_context(name).id(),
+            events: Vec::new(),
+            checkpoints: Vec::new(),
+        }));
+        sim.add_handler(name, recorder.clone());
+        recorder
+    }
+}
+
+impl EventHandler for Recorder {
+    fn on(&mut self, event: Event) {
+        let time = event.time;
+        let src = event.src;
+        let mut record = |description: String| self.events.push((time, description));
+        cast!(match event.data {
+            CompStarted { id, cores } => {
+                record(format!("started {} on {} cores", id, cores));
+            }
+            CompFinished { id } => {
+                record(format!("finished {}", id));
+            }
+            CompFailed { id, reason } => {
+                record(format!("failed {} {}", id, reason_name(&reason)));
+            }
+            CompCancelled { id, fraction_done } => {
+                record(format!("cancelled {} at {:.2}", id, fraction_done));
+            }
+            CompPreempted { id, fraction_done } => {
+                record(format!("preempted {} at {:.2}", id, fraction_done));
+            }
+            CompResumed { id } => {
+                record(format!("resumed {}", id));
+            }
+            CompCheckpointed { id, checkpoint } => {
+                record(format!("checkpointed {} at {:.2}", id, checkpoint.fraction_done()));
+                self.checkpoints.push(checkpoint);
+            }
+            CheckpointFailed { id } => {
+                record(format!("checkpoint failed {}", id));
+            }
+            CompRestored { id, cores } => {
+                record(format!("restored {} on {} cores", id, cores));
+            }
+            AllocationSuccess { id } => {
+                record(format!("allocated {}", id));
+            }
+            AllocationFailed { id, reason } => {
+                record(format!("allocation failed {} {}", id, reason_name(&reason)));
+            }
+            ResourceFailed {} => {
+                record(format!("resource {} failed", src));
+            }
+            ResourceRecovered {} => {
+                record(format!("resource {} recovered", src));
+            }
+        })
+    }
+}
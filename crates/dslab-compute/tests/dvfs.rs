@@ -0,0 +1,99 @@
+mod common;
+use common::{build_compute, Recorder};
+
+use dslab_compute::multicore::*;
+use dslab_core::Simulation;
+use dslab_models::power::cpu_models::dvfs::DvfsAwareCpuPowerModel;
+use dslab_models::power::host::HostPowerModelBuilder;
+
+#[test]
+fn test_set_frequency_space_shared() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 10., 4, 1024, |c| c.with_min_speed(5.));
+
+    let id = compute.borrow_mut().run(200., 256, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(4.);
+    compute.borrow_mut().set_frequency(0.);
+    assert_eq!(compute.borrow().speed(), 5.);
+    sim.step_until_time(6.);
+    // 80 flops are computed at 20 flop/s and then 20 flops at 10 flop/s
+    assert_eq!(compute.borrow().fraction_done(id), Ok(0.5));
+    sim.step_until_time(8.);
+    compute.borrow_mut().set_frequency(1.);
+    assert_eq!(compute.borrow().speed(), 10.);
+    sim.step_until_no_events();
+
+    // the remaining 80 flops are computed at 20 flop/s
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 2 cores", id)),
+            (12., format!("finished {}", id)),
+        ]
+    );
+}
+
+#[test]
+fn test_set_frequency_time_shared() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 4., 4, 1024, |c| {
+        c.with_min_speed(2.).with_sharing_mode(SharingMode::TimeShared)
+    });
+
+    // 8 threads on 4 cores run at the half speed
+    let id1 = compute.borrow_mut().run(80., 256, 1, 4, CoresDependency::Linear, rid);
+    let id2 = compute.borrow_mut().run(40., 256, 1, 4, CoresDependency::Linear, rid);
+    sim.step_until_time(4.);
+    compute.borrow_mut().set_frequency(0.5);
+    assert_eq!(compute.borrow().speed(), 3.);
+    sim.step_until_time(5.);
+    // each thread of the first computation computed 8 flops at 2 flop/s and then 1.5 flops at 1.5 flop/s
+    assert_eq!(compute.borrow().fraction_done(id1), Ok(0.475));
+    sim.step_until_no_events();
+
+    // the threads of the second computation have 2 flops left at time 4 and finish after 4/3 seconds,
+    // then the threads of the first computation have 10 flops left and run at the full speed of 3 flop/s
+    let events = recorder.borrow().events.clone();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[2].1, format!("finished {}", id2));
+    assert!((events[2].0 - 16. / 3.).abs() < 1e-9);
+    assert_eq!(events[3].1, format!("finished {}", id1));
+    assert!((events[3].0 - 16. / 3. - 10. / 3.).abs() < 1e-9);
+}
+
+#[test]
+fn test_energy_consumption() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let power_model = HostPowerModelBuilder::new()
+        .cpu(Box::new(DvfsAwareCpuPowerModel::new(50., 50., 100.)))
+        .cpu_idle(40.)
+        .build();
+    let compute = build_compute(&mut sim, "compute", 10., 4, 1024, |c| {
+        c.with_min_speed(5.).with_power_model(power_model)
+    });
+    assert_eq!(compute.borrow().power(), 40.);
+
+    compute.borrow_mut().run(200., 256, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(4.);
+    // 50 + 50 * 0.5 + 100 * 0.5 * 1
+    assert_eq!(compute.borrow().utilization(), 0.5);
+    assert_eq!(compute.borrow().power(), 125.);
+    compute.borrow_mut().set_frequency(0.);
+    // 50 + 50 * 0.5 + 100 * 0.5 * 0
+    assert_eq!(compute.borrow().power(), 75.);
+    sim.step_until_time(20.);
+
+    // the computation is finished at 16, then the resource is idle
+    assert_eq!(recorder.borrow().events[1].0, 16.);
+    let compute = compute.borrow();
+    assert_eq!(compute.utilization(), 0.);
+    assert_eq!(compute.power(), 40.);
+    assert_eq!(compute.average_utilization(), 0.4);
+    assert_eq!(compute.energy_consumed(), 125. * 4. + 75. * 12. + 40. * 4.);
+}
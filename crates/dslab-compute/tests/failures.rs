@@ -0,0 +1,164 @@
+mod common;
+use common::{build_compute, Recorder};
+
+use dslab_compute::failure::FailureModel;
+use dslab_compute::multicore::*;
+use dslab_core::Simulation;
+use dslab_models::power::cpu_models::linear::LinearCpuPowerModel;
+use dslab_models::power::host::{HostPowerModel, HostPowerModelBuilder};
+
+fn power_model() -> HostPowerModel {
+    HostPowerModelBuilder::new()
+        .cpu(Box::new(LinearCpuPowerModel::new(50., 100.)))
+        .build()
+}
+
+#[test]
+fn test_trace_failure() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let failure_model = FailureModel::from_trace(vec![(5., 10.)]).unwrap();
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_power_model(power_model()).with_failure_model(failure_model)
+    });
+    let cid = compute.borrow().id();
+    compute.borrow_mut().add_failure_listener(rid);
+
+    let id1 = compute.borrow_mut().run(40., 256, 2, 2, CoresDependency::Linear, rid);
+    let id2 = compute.borrow_mut().run(40., 256, 2, 2, CoresDependency::Linear, rid);
+    sim.step_until_time(2.);
+    compute.borrow_mut().preempt_computation(id2);
+    sim.step_until_time(7.);
+    // the failed resource rejects requests and consumes no power
+    assert!(compute.borrow().is_failed());
+    assert_eq!(compute.borrow().power(), 0.);
+    assert_eq!(compute.borrow().cores_available(), 4);
+    assert_eq!(compute.borrow().memory_available(), 1024);
+    let id3 = compute.borrow_mut().run(40., 256, 2, 2, CoresDependency::Linear, rid);
+    let alloc_id = compute.borrow_mut().allocate(1, 256, rid);
+    sim.step_until_time(12.);
+    assert!(!compute.borrow().is_failed());
+    assert_eq!(compute.borrow().power(), 50.);
+    let id4 = compute.borrow_mut().run(40., 256, 4, 4, CoresDependency::Linear, rid);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 2 cores", id1)),
+            (0., format!("started {} on 2 cores", id2)),
+            (2., format!("preempted {} at 0.10", id2)),
+            (5., format!("failed {} ResourceFailure", id1)),
+            (5., format!("failed {} ResourceFailure", id2)),
+            (5., format!("resource {} failed", cid)),
+            (7., format!("failed {} ResourceFailure", id3)),
+            (7., format!("allocation failed {} ResourceFailure", alloc_id)),
+            (10., format!("resource {} recovered", cid)),
+            (12., format!("started {} on 4 cores", id4)),
+            (22., format!("finished {}", id4)),
+        ]
+    );
+    // 50 + 50 * utilization before and after the failure, no power during the failure
+    assert_eq!(
+        compute.borrow().energy_consumed(),
+        100. * 2. + 75. * 3. + 50. * 2. + 100. * 10.
+    );
+}
+
+#[test]
+fn test_manual_failure() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| c.with_power_model(power_model()));
+    let cid = compute.borrow().id();
+    compute.borrow_mut().add_failure_listener(rid);
+
+    let id = compute.borrow_mut().run(40., 256, 4, 4, CoresDependency::Linear, rid);
+    sim.step_until_time(1.);
+    compute.borrow_mut().fail(3.);
+    sim.step_until_time(2.);
+    // repeated failure is ignored while the resource is failed
+    compute.borrow_mut().fail(10.);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 4 cores", id)),
+            (1., format!("failed {} ResourceFailure", id)),
+            (1., format!("resource {} failed", cid)),
+            (4., format!("resource {} recovered", cid)),
+        ]
+    );
+    assert!(!compute.borrow().is_failed());
+}
+
+#[test]
+fn test_resume_after_failure() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| c);
+
+    let id = compute.borrow_mut().run(40., 256, 4, 4, CoresDependency::Linear, rid);
+    sim.step_until_time(1.);
+    compute.borrow_mut().preempt_computation(id);
+    sim.step_until_time(2.);
+    // the preempted computation is aborted by the failure before the resume request is processed
+    compute.borrow_mut().fail(3.);
+    compute.borrow_mut().resume_computation(id);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 4 cores", id)),
+            (1., format!("preempted {} at 0.10", id)),
+            (2., format!("failed {} ResourceFailure", id)),
+            (2., format!("failed {} ResourceFailure", id)),
+        ]
+    );
+}
+
+#[test]
+fn test_random_failures() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_failure_model(FailureModel::exponential(100., 10.))
+    });
+    let cid = compute.borrow().id();
+    compute.borrow_mut().add_failure_listener(rid);
+    sim.step_until_time(10000.);
+
+    // the failures and recoveries alternate
+    let events = recorder.borrow().events.clone();
+    assert!(events.len() > 10);
+    for (i, (_, event)) in events.iter().enumerate() {
+        let state = if i % 2 == 0 { "failed" } else { "recovered" };
+        assert_eq!(*event, format!("resource {} {}", cid, state));
+    }
+    assert_eq!(compute.borrow().is_failed(), events.len() % 2 == 1);
+}
+
+#[test]
+fn test_failure_trace() {
+    let model = FailureModel::from_trace(vec![(10., 12.), (0., 5.), (5., 6.)]).unwrap();
+    assert!(matches!(model, FailureModel::Trace { intervals } if intervals == vec![(0., 5.), (5., 6.), (10., 12.)]));
+    assert!(FailureModel::from_trace(vec![(0., 5.), (4., 6.)]).is_err());
+    assert!(FailureModel::from_trace(vec![(5., 4.)]).is_err());
+    assert!(FailureModel::from_trace(vec![(-1., 4.)]).is_err());
+
+    let path = std::env::temp_dir().join(format!("dslab-compute-failures-{}.csv", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "20, 30\n0, 10\n").unwrap();
+    let model = FailureModel::from_trace_file(path);
+    std::fs::write(path, "0, 10\n5, 15\n").unwrap();
+    let overlapping = FailureModel::from_trace_file(path);
+    std::fs::remove_file(path).unwrap();
+    assert!(matches!(model, Ok(FailureModel::Trace { intervals }) if intervals == vec![(0., 10.), (20., 30.)]));
+    assert!(overlapping.is_err());
+}
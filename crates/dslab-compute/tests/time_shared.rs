@@ -0,0 +1,135 @@
+mod common;
+use common::{build_compute, Recorder};
+
+use dslab_compute::multicore::*;
+use dslab_core::Simulation;
+
+#[test]
+fn test_oversubscription() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_sharing_mode(SharingMode::TimeShared)
+    });
+
+    // 8 threads on 4 cores run at the half speed until the threads of the second computation are finished
+    let ids: Vec<u64> = [2, 4, 2]
+        .into_iter()
+        .map(|cores| {
+            compute
+                .borrow_mut()
+                .run(100., 256, 1, cores, CoresDependency::Linear, rid)
+        })
+        .collect();
+    sim.step_until_time(20.);
+    assert_eq!(compute.borrow().memory_available(), 256);
+    assert_eq!(compute.borrow().fraction_done(ids[0]), Ok(0.2));
+    assert_eq!(compute.borrow().fraction_done(ids[1]), Ok(0.4));
+    sim.step_until_time(60.);
+    assert_eq!(compute.borrow().fraction_done(ids[0]), Ok(0.7));
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 2 cores", ids[0])),
+            (0., format!("started {} on 4 cores", ids[1])),
+            (0., format!("started {} on 2 cores", ids[2])),
+            (50., format!("finished {}", ids[1])),
+            (75., format!("finished {}", ids[0])),
+            (75., format!("finished {}", ids[2])),
+        ]
+    );
+    assert_eq!(compute.borrow().memory_available(), 1024);
+}
+
+#[test]
+fn test_no_oversubscription() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_sharing_mode(SharingMode::TimeShared)
+    });
+
+    let id1 = compute.borrow_mut().run(100., 256, 1, 2, CoresDependency::Linear, rid);
+    let id2 = compute.borrow_mut().run(200., 256, 1, 2, CoresDependency::Linear, rid);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 2 cores", id1)),
+            (0., format!("started {} on 2 cores", id2)),
+            (50., format!("finished {}", id1)),
+            (100., format!("finished {}", id2)),
+        ]
+    );
+}
+
+#[test]
+fn test_admission() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_sharing_mode(SharingMode::TimeShared)
+    });
+
+    // the cores are not reserved, so only the memory and the total number of cores are checked
+    let id1 = compute.borrow_mut().run(100., 512, 4, 4, CoresDependency::Linear, rid);
+    let id2 = compute.borrow_mut().run(100., 512, 4, 8, CoresDependency::Linear, rid);
+    let id3 = compute.borrow_mut().run(100., 1, 1, 1, CoresDependency::Linear, rid);
+    let id4 = compute.borrow_mut().run(100., 0, 5, 5, CoresDependency::Linear, rid);
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 4 cores", id1)),
+            (0., format!("started {} on 4 cores", id2)),
+            (0., format!("failed {} NotEnoughResources", id3)),
+            (0., format!("failed {} NotEnoughResources", id4)),
+            (50., format!("finished {}", id1)),
+            (50., format!("finished {}", id2)),
+        ]
+    );
+}
+
+#[test]
+fn test_preempt_resume() {
+    let mut sim = Simulation::new(123);
+    let recorder = Recorder::new(&mut sim, "recorder");
+    let rid = recorder.borrow().id;
+    let compute = build_compute(&mut sim, "compute", 1., 4, 1024, |c| {
+        c.with_sharing_mode(SharingMode::TimeShared)
+    });
+
+    let id1 = compute.borrow_mut().run(100., 256, 4, 4, CoresDependency::Linear, rid);
+    let id2 = compute.borrow_mut().run(100., 256, 4, 4, CoresDependency::Linear, rid);
+    sim.step_until_time(10.);
+    compute.borrow_mut().preempt_computation(id1);
+    sim.step_until_time(15.);
+    // the preempted computation releases its memory, and the other one runs at the full speed
+    assert_eq!(compute.borrow().fraction_done(id1), Ok(0.2));
+    assert_eq!(compute.borrow().fraction_done(id2), Ok(0.4));
+    assert_eq!(compute.borrow().memory_available(), 768);
+    sim.step_until_time(20.);
+    compute.borrow_mut().resume_computation(id1);
+    sim.step_until_time(30.);
+    assert_eq!(compute.borrow().fraction_done(id1), Ok(0.4));
+    sim.step_until_no_events();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            (0., format!("started {} on 4 cores", id1)),
+            (0., format!("started {} on 4 cores", id2)),
+            (10., format!("preempted {} at 0.20", id1)),
+            (20., format!("resumed {}", id1)),
+            (40., format!("finished {}", id2)),
+            (50., format!("finished {}", id1)),
+        ]
+    );
+}
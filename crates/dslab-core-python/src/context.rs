@@ -0,0 +1,97 @@
+//! Python wrapper of simulation context.
+
+use pyo3::prelude::*;
+
+use dslab_core::{EventId, Id, SimulationContext};
+
+use crate::event::{py_to_json, PyEvent};
+
+/// Wrapper of [`SimulationContext`] passed to Python components (`SimulationContext` class in Python).
+///
+/// Events are emitted with payload represented as [`PyEvent`], i.e. `(type, data)` pair.
+#[pyclass(name = "SimulationContext", unsendable)]
+pub struct PySimulationContext {
+    ctx: SimulationContext,
+}
+
+impl PySimulationContext {
+    /// Creates wrapper of the specified context.
+    pub fn new(ctx: SimulationContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Returns the wrapped context.
+    pub fn ctx(&self) -> &SimulationContext {
+        &self.ctx
+    }
+}
+
+#[pymethods]
+impl PySimulationContext {
+    /// Returns the identifier of component associated with this context.
+    fn id(&self) -> Id {
+        self.ctx.id()
+    }
+
+    /// Returns the name of component associated with this context.
+    fn name(&self) -> &str {
+        self.ctx.name()
+    }
+
+    /// Returns the current simulation time.
+    fn time(&self) -> f64 {
+        self.ctx.time()
+    }
+
+    /// Returns a random float in the range [0, 1) using the random number generator of the component.
+    fn rand(&self) -> f64 {
+        self.ctx.rand()
+    }
+
+    /// Returns a random float in the range [low, high) using the random number generator of the component.
+    fn gen_range(&self, low: f64, high: f64) -> f64 {
+        self.ctx.gen_range(low..high)
+    }
+
+    /// Returns a random alphanumeric string of specified length using the random number generator of the component.
+    fn random_string(&self, len: usize) -> String {
+        self.ctx.random_string(len)
+    }
+
+    /// Creates new event with specified type, data, destination and delay, returns event id.
+    fn emit(&self, event_type: String, data: &PyAny, dst: Id, delay: f64) -> PyResult<EventId> {
+        Ok(self.ctx.emit(py_event(event_type, data)?, dst, delay))
+    }
+
+    /// Creates new immediate (zero-delay) event with specified type, data and destination, returns event id.
+    fn emit_now(&self, event_type: String, data: &PyAny, dst: Id) -> PyResult<EventId> {
+        Ok(self.ctx.emit_now(py_event(event_type, data)?, dst))
+    }
+
+    /// Creates new event for itself with specified type, data and delay, returns event id.
+    fn emit_self(&self, event_type: String, data: &PyAny, delay: f64) -> PyResult<EventId> {
+        Ok(self.ctx.emit_self(py_event(event_type, data)?, delay))
+    }
+
+    /// Creates new immediate event for itself with specified type and data, returns event id.
+    fn emit_self_now(&self, event_type: String, data: &PyAny) -> PyResult<EventId> {
+        Ok(self.ctx.emit_self_now(py_event(event_type, data)?))
+    }
+
+    /// Cancels the specified event.
+    fn cancel_event(&self, id: EventId) {
+        self.ctx.cancel_event(id)
+    }
+
+    /// Returns the name of component by its identifier.
+    fn lookup_name(&self, id: Id) -> String {
+        self.ctx.lookup_name(id)
+    }
+}
+
+fn py_event(event_type: String, data: &PyAny) -> PyResult<PyEvent> {
+    Ok(PyEvent {
+        event_type,
+        data: py_to_json(data)?,
+    })
+}
@@ -0,0 +1,93 @@
+//! Events exchanged with Python components.
+
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use serde_type_name::type_name;
+
+use dslab_core::{Event, EventId, Id};
+
+/// Payload of event emitted by Python component.
+#[derive(Clone, Serialize)]
+pub struct PyEvent {
+    /// Event type specified by Python component.
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Event data.
+    pub data: Value,
+}
+
+/// Event delivered to Python component (`Event` class in Python).
+#[pyclass(name = "Event", unsendable)]
+pub struct PyEventView {
+    /// Event identifier.
+    #[pyo3(get)]
+    pub id: EventId,
+    /// Time of event occurrence.
+    #[pyo3(get)]
+    pub time: f64,
+    /// Identifier of event source.
+    #[pyo3(get)]
+    pub src: Id,
+    /// Identifier of event destination.
+    #[pyo3(get)]
+    pub dst: Id,
+    /// Event type (available as `type` attribute in Python).
+    ///
+    /// For events emitted by Rust components it is the name of payload type.
+    pub event_type: String,
+    /// Event data.
+    ///
+    /// For events emitted by Rust components it is obtained by serializing the payload to JSON.
+    #[pyo3(get)]
+    pub data: PyObject,
+}
+
+impl PyEventView {
+    /// Converts the simulation event to the representation passed to Python component.
+    pub fn from_event(py: Python, event: &Event) -> PyResult<Self> {
+        let (event_type, data) = match event.data.downcast_ref::<PyEvent>() {
+            Some(py_event) => (py_event.event_type.clone(), py_event.data.clone()),
+            None => (
+                type_name(&event.data).unwrap_or("unknown").to_string(),
+                serde_json::to_value(&event.data).unwrap_or(Value::Null),
+            ),
+        };
+        Ok(Self {
+            id: event.id,
+            time: event.time,
+            src: event.src,
+            dst: event.dst,
+            event_type,
+            data: json_to_py(py, &data)?,
+        })
+    }
+}
+
+#[pymethods]
+impl PyEventView {
+    #[getter]
+    fn r#type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Event(id={}, time={}, src={}, dst={}, type={})",
+            self.id, self.time, self.src, self.dst, self.event_type
+        )
+    }
+}
+
+/// Converts JSON value to Python object.
+pub(crate) fn json_to_py(py: Python, value: &Value) -> PyResult<PyObject> {
+    let json = py.import("json")?;
+    Ok(json.call_method1("loads", (value.to_string(),))?.to_object(py))
+}
+
+/// Converts JSON-serializable Python object to JSON value.
+pub(crate) fn py_to_json(obj: &PyAny) -> PyResult<Value> {
+    let json = obj.py().import("json")?;
+    let s: String = json.call_method1("dumps", (obj,))?.extract()?;
+    serde_json::from_str(&s).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
@@ -0,0 +1,33 @@
+//! Python event handlers.
+
+use pyo3::prelude::*;
+
+use dslab_core::{Event, EventHandler};
+
+use crate::error_to_string;
+use crate::event::PyEventView;
+
+/// Event handler which passes events to Python object via its `on(event)` method.
+///
+/// Panics if the Python code raises an exception.
+pub struct PyEventHandler {
+    handler: PyObject,
+}
+
+impl PyEventHandler {
+    /// Creates event handler for the specified Python object.
+    pub fn new(handler: PyObject) -> Self {
+        Self { handler }
+    }
+}
+
+impl EventHandler for PyEventHandler {
+    fn on(&mut self, event: Event) {
+        Python::with_gil(|py| {
+            PyEventView::from_event(py, &event)
+                .and_then(|view| self.handler.call_method1(py, "on", (view,)))
+                .map_err(|e| error_to_string(e, py))
+                .unwrap_or_else(|e| panic!("Error when handling event {} in Python:\n{}", event.id, e));
+        })
+    }
+}
@@ -0,0 +1,75 @@
+//! Python bindings for dslab-core.
+//!
+//! The bindings allow to implement simulation components in Python and run them in the same simulation
+//! with components implemented in Rust. The bindings are provided as `dslabcore` Python module, which should be
+//! registered via [`register_module`] before running the Python code from Rust.
+//!
+//! The module exposes the following classes:
+//!
+//! - `Simulation` - wrapper of [`dslab_core::Simulation`] (see [`PySimulation`]),
+//! - `SimulationContext` - wrapper of [`dslab_core::SimulationContext`] (see [`PySimulationContext`]),
+//! - `Event` - event delivered to Python component (see [`PyEventView`]).
+//!
+//! A Python component is an object with `on(event)` method, which is registered as the event handler via
+//! `Simulation.add_handler` in Python or via [`PyEventHandler`] in Rust.
+//!
+//! Python components emit events with payload represented as `(type, data)` pair, where data is a JSON-serializable
+//! object. Such events are delivered to Rust components as [`PyEvent`]. Events emitted by Rust components
+//! are delivered to Python components with the type equal to the name of payload type and the data obtained by
+//! serializing the payload to JSON.
+
+pub mod context;
+pub mod event;
+pub mod handler;
+pub mod simulation;
+
+use std::fs;
+
+use pyo3::prelude::*;
+use pyo3::types::PyModule;
+
+pub use context::PySimulationContext;
+pub use event::{PyEvent, PyEventView};
+pub use handler::PyEventHandler;
+pub use simulation::PySimulation;
+
+/// Python module with dslab-core bindings.
+#[pymodule]
+fn dslabcore(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PySimulation>()?;
+    m.add_class::<PySimulationContext>()?;
+    m.add_class::<PyEventView>()?;
+    Ok(())
+}
+
+/// Registers `dslabcore` module, so it can be imported by the Python code.
+///
+/// Repeated calls have no effect.
+pub fn register_module(py: Python) -> PyResult<()> {
+    let modules = py.import("sys")?.getattr("modules")?;
+    if modules.contains("dslabcore")? {
+        return Ok(());
+    }
+    let module = PyModule::new(py, "dslabcore")?;
+    dslabcore(py, module)?;
+    modules.set_item("dslabcore", module)
+}
+
+/// Loads Python module from the file with specified path.
+///
+/// The `dslabcore` module is registered before running the module code.
+pub fn load_module<'py>(py: Python<'py>, path: &str) -> Result<&'py PyModule, String> {
+    register_module(py).map_err(|e| error_to_string(e, py))?;
+    let code = fs::read_to_string(path).map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+    let realpath = fs::canonicalize(path).map_err(|e| format!("Failed to resolve path {}: {}", path, e))?;
+    let filename = realpath.to_str().unwrap();
+    let module_name = filename.replace(".py", "");
+    PyModule::from_code(py, &code, filename, &module_name).map_err(|e| error_to_string(e, py))
+}
+
+pub(crate) fn error_to_string(err: PyErr, py: Python) -> String {
+    match err.traceback(py) {
+        Some(traceback) => err.to_string() + "\n" + &traceback.format().unwrap(),
+        None => err.to_string(),
+    }
+}
@@ -0,0 +1,138 @@
+//! Python wrapper of simulation.
+
+// pyo3 0.19 expands the constructor into code which triggers this lint on newer compilers
+#![allow(non_local_definitions)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use dslab_core::{EventCancellationPolicy, Id, Simulation};
+
+use crate::context::PySimulationContext;
+use crate::handler::PyEventHandler;
+
+/// Wrapper of [`Simulation`] (`Simulation` class in Python).
+///
+/// The simulation can be created in Python or passed from Rust via [`PySimulation::from`], so that Python code
+/// could add its components to the simulation with existing Rust components.
+#[pyclass(name = "Simulation", unsendable)]
+pub struct PySimulation {
+    sim: Simulation,
+}
+
+impl PySimulation {
+    /// Returns the wrapped simulation.
+    pub fn sim(&self) -> &Simulation {
+        &self.sim
+    }
+
+    /// Returns the mutable reference to the wrapped simulation.
+    pub fn sim_mut(&mut self) -> &mut Simulation {
+        &mut self.sim
+    }
+
+    /// Consumes the wrapper and returns the wrapped simulation.
+    pub fn into_inner(self) -> Simulation {
+        self.sim
+    }
+}
+
+impl From<Simulation> for PySimulation {
+    fn from(sim: Simulation) -> Self {
+        Self { sim }
+    }
+}
+
+#[pymethods]
+impl PySimulation {
+    /// Creates a new simulation with specified random seed.
+    #[new]
+    fn new(seed: u64) -> Self {
+        Self {
+            sim: Simulation::new(seed),
+        }
+    }
+
+    /// Creates a new simulation context with specified name.
+    fn create_context(&mut self, name: &str) -> PySimulationContext {
+        PySimulationContext::new(self.sim.create_context(name))
+    }
+
+    /// Registers Python object with `on(event)` method as the event handler for component with specified name,
+    /// returns the component Id.
+    fn add_handler(&mut self, name: &str, handler: PyObject) -> Id {
+        self.sim
+            .add_handler(name, Rc::new(RefCell::new(PyEventHandler::new(handler))))
+    }
+
+    /// Removes the event handler for component with specified name.
+    ///
+    /// The pending events to be cancelled are specified by `cancel_policy`: "all", "incoming", "outgoing"
+    /// or "none" (default).
+    #[pyo3(signature = (name, cancel_policy = "none"))]
+    fn remove_handler(&mut self, name: &str, cancel_policy: &str) -> PyResult<()> {
+        let cancel_policy = match cancel_policy {
+            "all" => EventCancellationPolicy::All,
+            "incoming" => EventCancellationPolicy::Incoming,
+            "outgoing" => EventCancellationPolicy::Outgoing,
+            "none" => EventCancellationPolicy::None,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown cancel policy: {}",
+                    cancel_policy
+                )))
+            }
+        };
+        self.sim.remove_handler(name, cancel_policy);
+        Ok(())
+    }
+
+    /// Returns the identifier of component by its name.
+    fn lookup_id(&self, name: &str) -> Id {
+        self.sim.lookup_id(name)
+    }
+
+    /// Returns the name of component by its identifier.
+    fn lookup_name(&self, id: Id) -> String {
+        self.sim.lookup_name(id)
+    }
+
+    /// Returns the current simulation time.
+    fn time(&self) -> f64 {
+        self.sim.time()
+    }
+
+    /// Returns the total number of created events.
+    fn event_count(&self) -> u64 {
+        self.sim.event_count()
+    }
+
+    /// Performs a single step through the simulation, returns false if there are no pending events.
+    fn step(&self) -> bool {
+        self.sim.step()
+    }
+
+    /// Performs the specified number of steps through the simulation, returns false if there are no more
+    /// pending events.
+    fn steps(&mut self, step_count: u64) -> bool {
+        self.sim.steps(step_count)
+    }
+
+    /// Steps through the simulation until there are no pending events left.
+    fn step_until_no_events(&mut self) {
+        self.sim.step_until_no_events()
+    }
+
+    /// Steps through the simulation with duration limit, returns false if there are no more pending events.
+    fn step_for_duration(&mut self, duration: f64) -> bool {
+        self.sim.step_for_duration(duration)
+    }
+
+    /// Steps through the simulation until the specified time, returns false if there are no more pending events.
+    fn step_until_time(&mut self, time: f64) -> bool {
+        self.sim.step_until_time(time)
+    }
+}
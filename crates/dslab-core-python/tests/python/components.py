@@ -0,0 +1,51 @@
+from dslabcore import Event, Simulation, SimulationContext
+
+
+class Ponger:
+    def __init__(self, ctx: SimulationContext):
+        self.ctx = ctx
+        self.received = []
+
+    def on(self, event: Event):
+        self.received.append((event.type, event.data))
+        if event.type == 'Ping':
+            self.ctx.emit('Pong', {'value': event.data['value'] + 1, 'from': self.ctx.name()}, event.src, 1.0)
+
+
+class Counter:
+    def __init__(self, ctx: SimulationContext):
+        self.ctx = ctx
+        self.count = 0
+        self.pending = None
+
+    def on(self, event: Event):
+        if event.type == 'Tick':
+            self.count += 1
+            if self.count < event.data['limit']:
+                self.ctx.emit_self('Tick', event.data, 0.5)
+            # this timer is cancelled by the next tick
+            if self.pending is not None:
+                self.ctx.cancel_event(self.pending)
+            self.pending = self.ctx.emit_self('Timeout', None, 10.0)
+        elif event.type == 'Timeout':
+            self.ctx.emit_now('Done', {'count': self.count, 'time': self.ctx.time()}, self.ctx.id())
+
+
+def setup(sim: Simulation):
+    ponger = Ponger(sim.create_context('ponger'))
+    sim.add_handler('ponger', ponger)
+    return ponger
+
+
+def run_python_simulation():
+    sim = Simulation(123)
+    counter = Counter(sim.create_context('counter'))
+    sim.add_handler('counter', counter)
+    starter = sim.create_context('starter')
+    starter.emit('Tick', {'limit': 3}, sim.lookup_id('counter'), 1.0)
+    sim.step_until_no_events()
+    assert counter.count == 3
+    assert sim.time() == 12.0
+    assert 0.0 <= starter.rand() < 1.0
+    assert 5.0 <= starter.gen_range(5.0, 6.0) < 6.0
+    return sim.event_count()
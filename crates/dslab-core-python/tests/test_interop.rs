@@ -0,0 +1,109 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde_json::json;
+
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+use dslab_core_python::{load_module, PyEvent, PySimulation};
+
+const COMPONENTS_PATH: &str = "tests/python/components.py";
+
+#[derive(Clone, Serialize)]
+struct Ping {
+    value: u32,
+}
+
+struct Pinger {
+    ctx: SimulationContext,
+    replies: Vec<(f64, PyEvent)>,
+}
+
+impl EventHandler for Pinger {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            PyEvent { event_type, data } => {
+                self.replies.push((event.time, PyEvent { event_type, data }));
+            }
+        })
+    }
+}
+
+#[test]
+fn test_rust_and_python_components() {
+    let mut sim = Simulation::new(123);
+    let pinger_ctx = sim.create_context("pinger");
+    let pinger = Rc::new(RefCell::new(Pinger {
+        ctx: pinger_ctx,
+        replies: Vec::new(),
+    }));
+    sim.add_handler("pinger", pinger.clone());
+
+    Python::with_gil(|py| {
+        let module = load_module(py, COMPONENTS_PATH).unwrap();
+        let py_sim = Py::new(py, PySimulation::from(sim)).unwrap();
+        let ponger = module.getattr("setup").unwrap().call1((py_sim.clone_ref(py),)).unwrap();
+
+        let ponger_id = py_sim.borrow(py).sim().lookup_id("ponger");
+        pinger.borrow().ctx.emit(Ping { value: 1 }, ponger_id, 1.0);
+        pinger.borrow().ctx.emit(Ping { value: 10 }, ponger_id, 2.0);
+        py_sim.borrow_mut(py).sim_mut().step_until_no_events();
+        assert_eq!(py_sim.borrow(py).sim().time(), 3.0);
+
+        let received: Vec<(String, u32)> = ponger
+            .getattr("received")
+            .unwrap()
+            .iter()
+            .unwrap()
+            .map(|item| {
+                let (event_type, data): (String, &PyAny) = item.unwrap().extract().unwrap();
+                (event_type, data.get_item("value").unwrap().extract().unwrap())
+            })
+            .collect();
+        assert_eq!(received, vec![("Ping".to_string(), 1), ("Ping".to_string(), 10)]);
+    });
+
+    let replies = &pinger.borrow().replies;
+    assert_eq!(replies.len(), 2);
+    assert_eq!(replies[0].0, 2.0);
+    assert_eq!(replies[0].1.event_type, "Pong");
+    assert_eq!(replies[0].1.data, json!({"value": 2, "from": "ponger"}));
+    assert_eq!(replies[1].0, 3.0);
+    assert_eq!(replies[1].1.data, json!({"value": 11, "from": "ponger"}));
+}
+
+#[test]
+fn test_python_simulation() {
+    Python::with_gil(|py| {
+        let module = load_module(py, COMPONENTS_PATH).unwrap();
+        let event_count: u64 = module
+            .getattr("run_python_simulation")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .extract()
+            .unwrap();
+        // 1 initial tick, 2 next ticks, 3 timeouts (2 cancelled), 1 done
+        assert_eq!(event_count, 7);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error when handling event")]
+fn test_python_exception() {
+    Python::with_gil(|py| {
+        load_module(py, COMPONENTS_PATH).unwrap();
+        let mut sim = Simulation::new(123);
+        let ctx = sim.create_context("client");
+        let ponger = py
+            .eval("type('Broken', (), {'on': lambda self, event: 1 / 0})()", None, None)
+            .unwrap();
+        let id = sim.add_handler(
+            "broken",
+            Rc::new(RefCell::new(dslab_core_python::PyEventHandler::new(ponger.into()))),
+        );
+        ctx.emit(Ping { value: 1 }, id, 1.0);
+        sim.step_until_no_events();
+    });
+}
@@ -13,6 +13,10 @@ impl<T> Receiver<T> {
     pub fn try_recv(&self) -> Option<T> {
         self.data.borrow_mut().pop_front()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.borrow().is_empty()
+    }
 }
 
 #[derive(Clone)]
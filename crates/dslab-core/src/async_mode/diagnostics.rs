@@ -0,0 +1,72 @@
+//! Diagnostics of asynchronous tasks.
+
+use std::fmt::{Display, Formatter};
+
+use crate::async_mode::EventKey;
+use crate::Id;
+
+/// Information about the task suspended while waiting for an event or a synchronization primitive.
+///
+/// Returned by [`Simulation::suspended_tasks`](crate::Simulation::suspended_tasks).
+/// Tasks waiting for items from [`UnboundedQueue`](crate::async_mode::UnboundedQueue) are also reported,
+/// as waiting for the queue notification event. Tasks waiting on [synchronization primitives](crate::async_mode::sync)
+/// or [`BoundedQueue`](crate::async_mode::BoundedQueue) are reported with the name of the awaited operation,
+/// e.g. `Mutex::lock`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuspendedTask {
+    /// Identifier of component which awaits the event or spawned the waiting task
+    /// (None for the task spawned via [`Simulation::spawn`](crate::Simulation::spawn) which waits on
+    /// synchronization primitive).
+    pub component_id: Option<Id>,
+    /// Name of component which awaits the event or spawned the waiting task (None if the component is unknown).
+    pub component_name: Option<String>,
+    /// Name of awaited event type or synchronization primitive operation.
+    pub awaited: String,
+    /// Identifier of awaited event source (None if it is not specified).
+    pub src: Option<Id>,
+    /// Name of awaited event source (None if it is not specified).
+    pub src_name: Option<String>,
+    /// Key of awaited event (None if it is not specified).
+    pub event_key: Option<EventKey>,
+    /// The time when the task started waiting.
+    pub suspended_at: f64,
+}
+
+impl Display for SuspendedTask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} awaits {}",
+            self.component_name.as_deref().unwrap_or("task"),
+            self.awaited
+        )?;
+        if let Some(src_name) = &self.src_name {
+            write!(f, " from {}", src_name)?;
+        }
+        if let Some(event_key) = self.event_key {
+            write!(f, " with key {}", event_key)?;
+        }
+        write!(f, " since {:.3}", self.suspended_at)
+    }
+}
+
+// Task waiting on synchronization primitive.
+#[derive(Clone)]
+pub(crate) struct SyncWait {
+    pub component_id: Option<Id>,
+    pub operation: &'static str,
+    pub suspended_at: f64,
+}
+
+// Creates the report listing the suspended tasks.
+pub(crate) fn stall_report(time: f64, tasks: &[SuspendedTask]) -> String {
+    let mut report = format!(
+        "Simulation stalled at time {:.3} with {} suspended task(s):",
+        time,
+        tasks.len()
+    );
+    for task in tasks {
+        report.push_str(&format!("\n  - {}", task));
+    }
+    report
+}
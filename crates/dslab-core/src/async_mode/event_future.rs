@@ -269,6 +269,13 @@ impl<T: EventData> Drop for EventFuture<T> {
 pub(crate) struct EventPromise {
     // State with completion info shared with EventFuture.
     state: Rc<RefCell<dyn EventAwaitState>>,
+    // Details of the awaited event used for diagnostics.
+    pub dst: Id,
+    pub src: Option<Id>,
+    pub event_key: Option<EventKey>,
+    pub event_type: &'static str,
+    // The time when the waiting started.
+    pub created_at: f64,
 }
 
 impl EventPromise {
@@ -276,11 +283,20 @@ impl EventPromise {
         dst: Id,
         src: Option<Id>,
         event_key: Option<EventKey>,
+        time: f64,
         sim_state: Rc<RefCell<SimulationState>>,
     ) -> (Self, EventFuture<T>) {
         let state = Rc::new(RefCell::new(TypedEventAwaitState::<T>::default()));
         let future = EventFuture::new(dst, src, event_key, state.clone(), sim_state);
-        (Self { state }, future)
+        let promise = Self {
+            state,
+            dst,
+            src,
+            event_key,
+            event_type: std::any::type_name::<T>(),
+            created_at: time,
+        };
+        (promise, future)
     }
 
     pub fn complete(&self, e: Event) {
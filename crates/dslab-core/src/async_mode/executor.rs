@@ -14,14 +14,13 @@ impl Executor {
         Self { scheduled_tasks }
     }
 
-    // Polls one scheduled task, if any.
-    // Returns true if a task was polled and false otherwise.
-    pub fn process_task(&self) -> bool {
-        if let Some(task) = self.scheduled_tasks.try_recv() {
-            task.poll();
-            true
-        } else {
-            false
-        }
+    // Returns the next scheduled task without polling it.
+    pub fn next_task(&self) -> Option<Rc<Task>> {
+        self.scheduled_tasks.try_recv()
+    }
+
+    // Returns true if there are scheduled tasks.
+    pub fn has_tasks(&self) -> bool {
+        !self.scheduled_tasks.is_empty()
     }
 }
@@ -0,0 +1,79 @@
+//! Asynchronous periodic ticks.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures::{Stream, StreamExt};
+
+use crate::async_mode::timer_future::TimerFuture;
+use crate::{state::SimulationState, Id};
+
+/// Stream of periodic ticks created via [`SimulationContext::interval`](crate::SimulationContext::interval).
+///
+/// The ticks happen at times `start + period`, `start + 2 * period`, etc., where `start` is the interval creation
+/// time. Each tick yields the current simulation time. If the tick is awaited later than its scheduled time,
+/// it completes immediately and the following ticks keep the original schedule.
+pub struct Interval {
+    component_id: Id,
+    period: f64,
+    next_time: f64,
+    timer: Option<TimerFuture>,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl Interval {
+    pub(crate) fn new(component_id: Id, period: f64, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        assert!(period > 0., "Interval period must be positive");
+        let next_time = sim_state.borrow().time() + period;
+        Self {
+            component_id,
+            period,
+            next_time,
+            timer: None,
+            sim_state,
+        }
+    }
+
+    /// Waits (asynchronously) for the next tick and returns the current time.
+    pub async fn tick(&mut self) -> f64 {
+        self.next().await.unwrap()
+    }
+
+    /// Returns the interval period.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Changes the interval period.
+    ///
+    /// The already scheduled tick is not affected, the new period is used for the following ones.
+    pub fn set_period(&mut self, period: f64) {
+        assert!(period > 0., "Interval period must be positive");
+        self.period = period;
+    }
+}
+
+impl Stream for Interval {
+    type Item = f64;
+
+    fn poll_next(mut self: Pin<&mut Self>, async_ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.timer.is_none() {
+            let mut sim_state = self.sim_state.borrow_mut();
+            let delay = (self.next_time - sim_state.time()).max(0.);
+            let timer = sim_state.create_timer(self.component_id, delay, self.sim_state.clone());
+            drop(sim_state);
+            self.timer = Some(timer);
+        }
+        match Pin::new(self.timer.as_mut().unwrap()).poll(async_ctx) {
+            Poll::Ready(()) => {
+                self.timer = None;
+                self.next_time += self.period;
+                Poll::Ready(Some(self.sim_state.borrow().time()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
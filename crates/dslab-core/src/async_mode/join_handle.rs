@@ -0,0 +1,162 @@
+//! Awaiting and cancelling spawned tasks.
+
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use super::channel::Sender;
+use super::task::Task;
+use crate::component::Id;
+
+/// Error returned by awaiting [`JoinHandle`] if the task did not complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinError {
+    /// The task was aborted via [`JoinHandle::abort`].
+    Aborted,
+}
+
+impl Display for JoinError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinError::Aborted => write!(f, "task was aborted"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+// State shared between the task and its JoinHandle.
+struct JoinState<T> {
+    finished: bool,
+    result: Option<Result<T, JoinError>>,
+    waker: Option<Waker>,
+}
+
+impl<T> JoinState<T> {
+    fn complete(&mut self, result: Result<T, JoinError>) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        self.result = Some(result);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Handle of the task spawned via [`Simulation::spawn`](crate::Simulation::spawn) or
+/// [`SimulationContext::spawn`](crate::SimulationContext::spawn).
+///
+/// The handle can be awaited to obtain the task result or used to abort the task.
+/// Dropping the handle does not affect the task, it continues to run in background.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use dslab_core::async_mode::JoinError;
+/// use dslab_core::{Event, Simulation, SimulationContext, StaticEventHandler};
+///
+/// struct Worker {
+///     ctx: SimulationContext,
+/// }
+///
+/// impl Worker {
+///     async fn compute(self: Rc<Self>, duration: f64) -> f64 {
+///         self.ctx.sleep(duration).await;
+///         duration * 2.
+///     }
+///
+///     async fn run(self: Rc<Self>) {
+///         let child = self.ctx.spawn(self.clone().compute(5.));
+///         assert_eq!(child.await, Ok(10.));
+///         assert_eq!(self.ctx.time(), 5.);
+///
+///         let child = self.ctx.spawn(self.clone().compute(100.));
+///         self.ctx.sleep(1.).await;
+///         assert!(!child.is_finished());
+///         child.abort();
+///         assert_eq!(child.await, Err(JoinError::Aborted));
+///     }
+/// }
+///
+/// impl StaticEventHandler for Worker {
+///     fn on(self: Rc<Self>, _event: Event) {}
+/// }
+///
+/// let mut sim = Simulation::new(123);
+/// let worker = Rc::new(Worker { ctx: sim.create_context("worker") });
+/// sim.add_static_handler("worker", worker.clone());
+/// sim.spawn(worker.run());
+///
+/// sim.step_until_no_events();
+/// // the timer of aborted task is cancelled
+/// assert_eq!(sim.time(), 6.);
+/// ```
+pub struct JoinHandle<T> {
+    task: Rc<Task>,
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+impl<T: 'static> JoinHandle<T> {
+    pub(crate) fn spawn(
+        future: impl Future<Output = T> + 'static,
+        component_id: Option<Id>,
+        executor: Sender<Rc<Task>>,
+    ) -> Self {
+        let state = Rc::new(RefCell::new(JoinState {
+            finished: false,
+            result: None,
+            waker: None,
+        }));
+        let task_state = state.clone();
+        let task = Task::spawn(
+            async move {
+                let result = future.await;
+                task_state.borrow_mut().complete(Ok(result));
+            },
+            component_id,
+            executor,
+        );
+        Self { task, state }
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Aborts the task.
+    ///
+    /// The task is not polled anymore and its future is dropped immediately, which cancels all timers and event
+    /// awaits pending in this task. Awaiting the handle of aborted task returns [`JoinError::Aborted`].
+    /// Aborting the already finished task has no effect.
+    pub fn abort(&self) {
+        if self.is_finished() {
+            return;
+        }
+        self.task.abort();
+        self.state.borrow_mut().complete(Err(JoinError::Aborted));
+    }
+
+    /// Returns true if the task has completed or was aborted.
+    pub fn is_finished(&self) -> bool {
+        self.state.borrow().finished
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, async_ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if let Some(result) = state.result.take() {
+            Poll::Ready(result)
+        } else {
+            assert!(!state.finished, "JoinHandle is polled after completion");
+            state.waker = Some(async_ctx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
@@ -6,8 +6,12 @@ use crate::async_mode_enabled;
 pub(crate) mod macros;
 
 async_mode_enabled!(
+    pub mod diagnostics;
     pub mod event_future;
+    pub mod interval;
+    pub mod join_handle;
     pub mod queue;
+    pub mod sync;
     pub mod timer_future;
 
     pub(crate) mod channel;
@@ -18,6 +22,8 @@ async_mode_enabled!(
     mod waker;
 
     pub use event_future::{AwaitResult, EventFuture, EventKey};
+    pub use interval::Interval;
+    pub use join_handle::{JoinError, JoinHandle};
     pub use timer_future::TimerFuture;
-    pub use queue::UnboundedQueue;
+    pub use queue::{BoundedQueue, UnboundedQueue};
 );
@@ -101,6 +101,15 @@ impl EventPromiseStore {
         None
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &EventPromise> {
+        let promises_with_source = self.promises_with_source.values().flat_map(|p| p.values());
+        self.promises.values().chain(promises_with_source)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.promises.is_empty() && self.promises_with_source.values().all(|promises| promises.is_empty())
+    }
+
     pub fn drop_promises_by_dst(&mut self, dst: Id) -> u32 {
         let mut removed_count = 0;
         self.promises.retain(|key, promise| {
@@ -10,6 +10,8 @@ use std::task::{Context, Poll};
 use rustc_hash::FxHashSet;
 use serde::Serialize;
 
+use crate::async_mode::sync::Semaphore;
+use crate::state::SimulationState;
 use crate::SimulationContext;
 
 /// A simple implementation of unbounded multi-producer multi-consumer queue with items of type `T`.
@@ -146,3 +148,117 @@ impl<'a, T> Drop for ElementFutureWrapper<'a, T> {
         }
     }
 }
+
+/// Multi-producer multi-consumer queue with limited capacity and items of type `T`.
+///
+/// Producers are blocked while the queue is full and consumers are blocked while the queue is empty.
+/// Blocked producers and consumers are resumed in the order of [`put`](Self::put) and [`take`](Self::take) calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use dslab_core::Simulation;
+///
+/// let mut sim = Simulation::new(123);
+/// let queue = Rc::new(sim.create_bounded_queue(2));
+///
+/// let ctx = sim.create_context("producer");
+/// let producer_queue = queue.clone();
+/// sim.spawn(async move {
+///     for i in 0..5 {
+///         producer_queue.put(i).await;
+///     }
+///     // the producer waits for the consumer to free space for the last item
+///     assert_eq!(ctx.time(), 3.);
+/// });
+///
+/// let ctx = sim.create_context("consumer");
+/// sim.spawn(async move {
+///     for i in 0..5 {
+///         ctx.sleep(1.).await;
+///         assert_eq!(queue.take().await, i);
+///     }
+/// });
+///
+/// sim.step_until_no_events();
+/// assert_eq!(sim.time(), 5.);
+/// ```
+pub struct BoundedQueue<T> {
+    capacity: usize,
+    items: RefCell<VecDeque<T>>,
+    free_slots: Semaphore,
+    filled_slots: Semaphore,
+}
+
+impl<T> BoundedQueue<T> {
+    pub(crate) fn new(capacity: usize, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        assert!(capacity > 0, "Queue capacity must be positive");
+        Self {
+            capacity,
+            items: RefCell::new(VecDeque::with_capacity(capacity)),
+            free_slots: Semaphore::new(capacity, sim_state.clone()),
+            filled_slots: Semaphore::new(0, sim_state),
+        }
+    }
+
+    /// Inserts the specified item into the queue, waiting if necessary until there is free space.
+    ///
+    /// If the returned future is dropped before completion, the item is not inserted.
+    pub async fn put(&self, item: T) {
+        self.free_slots.acquire_for(1, "BoundedQueue::put").await.forget();
+        self.push(item);
+    }
+
+    /// Inserts the specified item into the queue if there is free space, otherwise returns the item back.
+    pub fn try_put(&self, item: T) -> Result<(), T> {
+        match self.free_slots.try_acquire() {
+            Some(permit) => {
+                permit.forget();
+                self.push(item);
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+
+    /// Removes the head of the queue and returns it, waiting if necessary until an item becomes available.
+    pub async fn take(&self) -> T {
+        self.filled_slots.acquire_for(1, "BoundedQueue::take").await.forget();
+        self.pop()
+    }
+
+    /// Removes the head of the queue and returns it if the queue is not empty.
+    pub fn try_take(&self) -> Option<T> {
+        self.filled_slots.try_acquire().map(|permit| {
+            permit.forget();
+            self.pop()
+        })
+    }
+
+    /// Returns the number of items in the queue.
+    pub fn len(&self) -> usize {
+        self.items.borrow().len()
+    }
+
+    /// Returns true if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.borrow().is_empty()
+    }
+
+    /// Returns the queue capacity.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn push(&self, item: T) {
+        self.items.borrow_mut().push_back(item);
+        self.filled_slots.add_permits(1);
+    }
+
+    fn pop(&self) -> T {
+        let item = self.items.borrow_mut().pop_front().unwrap();
+        self.free_slots.add_permits(1);
+        item
+    }
+}
@@ -0,0 +1,530 @@
+//! Synchronization primitives for asynchronous tasks.
+//!
+//! The primitives are created via [`Simulation`](crate::Simulation) methods and do not advance the simulation time
+//! by themselves: a task blocked on a primitive is resumed at the simulation time when the primitive is released
+//! by another task. Blocked tasks are resumed in FIFO order, so the simulation remains deterministic.
+//!
+//! Futures returned by the primitives can be safely dropped (e.g. when the task is aborted or the future loses in
+//! `select!`), in which case the task is removed from the wait list and the reserved resources are returned.
+//!
+//! The waiting tasks are reported by [`Simulation::suspended_tasks`](crate::Simulation::suspended_tasks),
+//! so the deadlocks on primitives are detected in the strict stall mode.
+
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use crate::state::SimulationState;
+
+// Wait list -----------------------------------------------------------------------------------------------------------
+
+// Task waiting in the wait list of some primitive.
+#[derive(Default)]
+struct Waiter {
+    // Set when the waiter is released by the primitive.
+    granted: bool,
+    waker: Option<Waker>,
+}
+
+impl Waiter {
+    fn grant(&mut self) {
+        self.granted = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+type WaiterRef = Rc<RefCell<Waiter>>;
+
+fn remove_waiter<T>(waiters: &mut VecDeque<(T, WaiterRef)>, waiter: &WaiterRef) {
+    waiters.retain(|(_, w)| !Rc::ptr_eq(w, waiter));
+}
+
+// Future which completes when the waiter is granted.
+// If dropped before completion, calls the cancel function with the granted flag of the waiter.
+// The wait is registered in the simulation state until completion for diagnostics of suspended tasks.
+struct Wait<'a> {
+    waiter: WaiterRef,
+    cancel: Option<Box<dyn FnOnce(bool) + 'a>>,
+    sim_state: &'a RefCell<SimulationState>,
+    wait_id: u64,
+}
+
+impl<'a> Wait<'a> {
+    fn new(
+        waiter: WaiterRef,
+        sim_state: &'a RefCell<SimulationState>,
+        operation: &'static str,
+        cancel: impl FnOnce(bool) + 'a,
+    ) -> Self {
+        let wait_id = sim_state.borrow_mut().add_sync_wait(operation);
+        Self {
+            waiter,
+            cancel: Some(Box::new(cancel)),
+            sim_state,
+            wait_id,
+        }
+    }
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, async_ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut waiter = self.waiter.borrow_mut();
+        if waiter.granted {
+            drop(waiter);
+            self.cancel = None;
+            self.sim_state.borrow_mut().remove_sync_wait(self.wait_id);
+            Poll::Ready(())
+        } else {
+            waiter.waker = Some(async_ctx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Wait<'_> {
+    fn drop(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            self.sim_state.borrow_mut().remove_sync_wait(self.wait_id);
+            let granted = self.waiter.borrow().granted;
+            cancel(granted);
+        }
+    }
+}
+
+// Semaphore -----------------------------------------------------------------------------------------------------------
+
+struct SemaphoreState {
+    permits: usize,
+    // Waiting tasks with the number of requested permits
+    waiters: VecDeque<(usize, WaiterRef)>,
+}
+
+/// Counting semaphore which limits the number of tasks accessing some resource, e.g. worker slots or connections.
+///
+/// Permits are granted to waiting tasks in the order of [`acquire`](Self::acquire) calls. A task requesting
+/// more permits than currently available blocks the tasks behind it even if their requests could be satisfied.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use dslab_core::Simulation;
+///
+/// let mut sim = Simulation::new(123);
+/// let slots = Rc::new(sim.create_semaphore(2));
+///
+/// for i in 0..3 {
+///     let ctx = sim.create_context(format!("client{}", i));
+///     let slots = slots.clone();
+///     sim.spawn(async move {
+///         let _permit = slots.acquire().await;
+///         ctx.sleep(10.).await;
+///     });
+/// }
+///
+/// sim.step_until_no_events();
+/// // the third client waits for the free slot
+/// assert_eq!(sim.time(), 20.);
+/// ```
+pub struct Semaphore {
+    state: RefCell<SemaphoreState>,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl Semaphore {
+    pub(crate) fn new(permits: usize, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self {
+            state: RefCell::new(SemaphoreState {
+                permits,
+                waiters: VecDeque::new(),
+            }),
+            sim_state,
+        }
+    }
+
+    /// Returns the number of permits available for acquiring.
+    pub fn available_permits(&self) -> usize {
+        self.state.borrow().permits
+    }
+
+    /// Adds the specified number of permits and wakes up the waiting tasks if possible.
+    pub fn add_permits(&self, count: usize) {
+        let mut state = self.state.borrow_mut();
+        state.permits += count;
+        Self::grant_waiters(&mut state);
+    }
+
+    /// Acquires a permit, waiting if necessary until it becomes available.
+    ///
+    /// The permit is returned to the semaphore when the returned [`SemaphorePermit`] is dropped.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1).await
+    }
+
+    /// Acquires the specified number of permits, waiting if necessary until they become available.
+    pub async fn acquire_many(&self, count: usize) -> SemaphorePermit<'_> {
+        self.acquire_for(count, "Semaphore::acquire").await
+    }
+
+    // Acquires the permits on behalf of the specified operation, which is reported if the task is suspended.
+    pub(crate) async fn acquire_for(&self, count: usize, operation: &'static str) -> SemaphorePermit<'_> {
+        if let Some(permit) = self.try_acquire_many(count) {
+            return permit;
+        }
+        let waiter = WaiterRef::default();
+        self.state.borrow_mut().waiters.push_back((count, waiter.clone()));
+        Wait::new(waiter.clone(), &self.sim_state, operation, |granted| {
+            if granted {
+                self.add_permits(count);
+            } else {
+                let mut state = self.state.borrow_mut();
+                remove_waiter(&mut state.waiters, &waiter);
+                // the removed task could block the following ones
+                Self::grant_waiters(&mut state);
+            }
+        })
+        .await;
+        SemaphorePermit { semaphore: self, count }
+    }
+
+    /// Acquires a permit if it is available without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Acquires the specified number of permits if they are available and there are no waiting tasks.
+    pub fn try_acquire_many(&self, count: usize) -> Option<SemaphorePermit<'_>> {
+        let mut state = self.state.borrow_mut();
+        if state.waiters.is_empty() && state.permits >= count {
+            state.permits -= count;
+            Some(SemaphorePermit { semaphore: self, count })
+        } else {
+            None
+        }
+    }
+
+    fn grant_waiters(state: &mut SemaphoreState) {
+        while let Some((count, _)) = state.waiters.front() {
+            if *count > state.permits {
+                break;
+            }
+            let (count, waiter) = state.waiters.pop_front().unwrap();
+            state.permits -= count;
+            waiter.borrow_mut().grant();
+        }
+    }
+}
+
+/// Permits acquired from [`Semaphore`], which are returned to the semaphore on drop.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    count: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Consumes the permits without returning them to the semaphore.
+    pub fn forget(mut self) {
+        self.count = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.count > 0 {
+            self.semaphore.add_permits(self.count);
+        }
+    }
+}
+
+// Mutex ---------------------------------------------------------------------------------------------------------------
+
+/// Mutual exclusion primitive protecting the shared value of type `T`.
+///
+/// Unlike `RefCell`, the lock can be held across `.await` points, while other tasks trying to lock the mutex
+/// wait until it is unlocked. The lock is granted to waiting tasks in the order of [`lock`](Self::lock) calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use dslab_core::Simulation;
+///
+/// let mut sim = Simulation::new(123);
+/// let log = Rc::new(sim.create_mutex(Vec::new()));
+///
+/// for i in 0..3 {
+///     let ctx = sim.create_context(format!("writer{}", i));
+///     let log = log.clone();
+///     sim.spawn(async move {
+///         let mut log = log.lock().await;
+///         ctx.sleep(1.).await;
+///         log.push((i, ctx.time()));
+///     });
+/// }
+///
+/// sim.step_until_no_events();
+/// assert_eq!(*log.try_lock().unwrap(), vec![(0, 1.), (1, 2.), (2, 3.)]);
+/// ```
+pub struct Mutex<T> {
+    semaphore: Semaphore,
+    value: RefCell<T>,
+}
+
+impl<T> Mutex<T> {
+    pub(crate) fn new(value: T, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self {
+            semaphore: Semaphore::new(1, sim_state),
+            value: RefCell::new(value),
+        }
+    }
+
+    /// Locks the mutex, waiting if necessary until it is unlocked.
+    ///
+    /// The mutex is unlocked when the returned [`MutexGuard`] is dropped.
+    pub async fn lock(&self) -> MutexGuard<'_, T> {
+        let permit = self.semaphore.acquire_for(1, "Mutex::lock").await;
+        MutexGuard {
+            value: self.value.borrow_mut(),
+            _permit: permit,
+        }
+    }
+
+    /// Locks the mutex if it is unlocked and there are no waiting tasks.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.semaphore.try_acquire().map(|permit| MutexGuard {
+            value: self.value.borrow_mut(),
+            _permit: permit,
+        })
+    }
+
+    /// Returns true if the mutex is locked.
+    pub fn is_locked(&self) -> bool {
+        self.semaphore.available_permits() == 0
+    }
+}
+
+/// Provides access to the value protected by [`Mutex`], unlocks the mutex on drop.
+pub struct MutexGuard<'a, T> {
+    // the value is released before the permit according to the fields order
+    value: RefMut<'a, T>,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+// Barrier -------------------------------------------------------------------------------------------------------------
+
+struct BarrierState {
+    arrived: usize,
+    waiters: VecDeque<((), WaiterRef)>,
+}
+
+/// Barrier which blocks tasks until the specified number of tasks reach it.
+///
+/// The barrier is reusable: after releasing the waiting tasks, it starts collecting the next group of tasks.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use dslab_core::Simulation;
+///
+/// let mut sim = Simulation::new(123);
+/// let barrier = Rc::new(sim.create_barrier(3));
+///
+/// for i in 1..=3 {
+///     let ctx = sim.create_context(format!("worker{}", i));
+///     let barrier = barrier.clone();
+///     sim.spawn(async move {
+///         ctx.sleep(i as f64).await;
+///         let is_leader = barrier.wait().await;
+///         assert_eq!(ctx.time(), 3.);
+///         assert_eq!(is_leader, i == 3);
+///     });
+/// }
+///
+/// sim.step_until_no_events();
+/// ```
+pub struct Barrier {
+    count: usize,
+    state: RefCell<BarrierState>,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl Barrier {
+    pub(crate) fn new(count: usize, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        assert!(count > 0, "Barrier count must be positive");
+        Self {
+            count,
+            state: RefCell::new(BarrierState {
+                arrived: 0,
+                waiters: VecDeque::new(),
+            }),
+            sim_state,
+        }
+    }
+
+    /// Waits until all tasks have reached the barrier.
+    ///
+    /// Returns true for the last arrived task (the leader), which releases the others, and false for other tasks.
+    /// Dropping the waiting future withdraws the task from the barrier.
+    pub async fn wait(&self) -> bool {
+        let waiter = {
+            let mut state = self.state.borrow_mut();
+            state.arrived += 1;
+            if state.arrived == self.count {
+                state.arrived = 0;
+                for (_, waiter) in state.waiters.drain(..) {
+                    waiter.borrow_mut().grant();
+                }
+                return true;
+            }
+            let waiter = WaiterRef::default();
+            state.waiters.push_back(((), waiter.clone()));
+            waiter
+        };
+        Wait::new(waiter.clone(), &self.sim_state, "Barrier::wait", |granted| {
+            if !granted {
+                let mut state = self.state.borrow_mut();
+                remove_waiter(&mut state.waiters, &waiter);
+                state.arrived -= 1;
+            }
+        })
+        .await;
+        false
+    }
+}
+
+// Notify --------------------------------------------------------------------------------------------------------------
+
+struct NotifyState {
+    permit: bool,
+    // Waiting tasks with the flag which is set when the task is woken by notify_one
+    waiters: VecDeque<(Rc<Cell<bool>>, WaiterRef)>,
+}
+
+/// Notifies the waiting tasks about some event, e.g. a change of condition checked by the tasks.
+///
+/// If [`notify_one`](Self::notify_one) is called when there are no waiting tasks, the notification is stored
+/// and the next call to [`notified`](Self::notified) completes immediately. At most one notification is stored.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use dslab_core::Simulation;
+///
+/// let mut sim = Simulation::new(123);
+/// let notify = Rc::new(sim.create_notify());
+/// let ready = Rc::new(Cell::new(false));
+///
+/// let ctx = sim.create_context("consumer");
+/// let (consumer_notify, consumer_ready) = (notify.clone(), ready.clone());
+/// sim.spawn(async move {
+///     while !consumer_ready.get() {
+///         consumer_notify.notified().await;
+///     }
+///     assert_eq!(ctx.time(), 5.);
+/// });
+///
+/// let ctx = sim.create_context("producer");
+/// sim.spawn(async move {
+///     ctx.sleep(5.).await;
+///     ready.set(true);
+///     notify.notify_one();
+/// });
+///
+/// sim.step_until_no_events();
+/// ```
+pub struct Notify {
+    state: RefCell<NotifyState>,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl Notify {
+    pub(crate) fn new(sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self {
+            state: RefCell::new(NotifyState {
+                permit: false,
+                waiters: VecDeque::new(),
+            }),
+            sim_state,
+        }
+    }
+
+    /// Waits for a notification.
+    pub async fn notified(&self) {
+        let (single, waiter) = {
+            let mut state = self.state.borrow_mut();
+            if state.permit {
+                state.permit = false;
+                return;
+            }
+            let waiter = WaiterRef::default();
+            let single = Rc::new(Cell::new(false));
+            state.waiters.push_back((single.clone(), waiter.clone()));
+            (single, waiter)
+        };
+        Wait::new(waiter.clone(), &self.sim_state, "Notify::notified", |granted| {
+            if granted {
+                // pass the notification from notify_one to another task,
+                // the notification from notify_waiters is not stored
+                if single.get() {
+                    self.notify_one();
+                }
+            } else {
+                remove_waiter(&mut self.state.borrow_mut().waiters, &waiter);
+            }
+        })
+        .await;
+    }
+
+    /// Wakes up the first waiting task or stores the notification if there are no waiting tasks.
+    pub fn notify_one(&self) {
+        let mut state = self.state.borrow_mut();
+        match state.waiters.pop_front() {
+            Some((single, waiter)) => {
+                single.set(true);
+                waiter.borrow_mut().grant();
+            }
+            None => state.permit = true,
+        }
+    }
+
+    /// Wakes up all waiting tasks.
+    ///
+    /// The notification is not stored, so it does not affect the tasks which call [`notified`](Self::notified)
+    /// later.
+    pub fn notify_waiters(&self) {
+        let mut state = self.state.borrow_mut();
+        for (_, waiter) in state.waiters.drain(..) {
+            waiter.borrow_mut().grant();
+        }
+    }
+}
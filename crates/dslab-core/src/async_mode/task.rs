@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
@@ -6,6 +6,7 @@ use std::task::Context;
 
 use super::channel::Sender;
 use super::waker::{waker_ref, RcWake};
+use crate::component::Id;
 
 type BoxedFuture = Pin<Box<dyn Future<Output = ()>>>;
 
@@ -13,35 +14,55 @@ type BoxedFuture = Pin<Box<dyn Future<Output = ()>>>;
 // Holds the corresponding future and schedules itself for polling by Executor on wake-up notifications.
 pub(crate) struct Task {
     future: RefCell<Option<BoxedFuture>>,
+    aborted: Cell<bool>,
+    // Component which spawned the task (None for tasks spawned via Simulation::spawn)
+    component_id: Option<Id>,
     executor: Sender<Rc<Task>>,
 }
 
 impl Task {
     // Creates a new task from a future.
-    fn new(future: impl Future<Output = ()> + 'static, executor: Sender<Rc<Task>>) -> Self {
+    fn new(future: impl Future<Output = ()> + 'static, component_id: Option<Id>, executor: Sender<Rc<Task>>) -> Self {
         Self {
             future: RefCell::new(Some(Box::pin(future))),
+            aborted: Cell::new(false),
+            component_id,
             executor,
         }
     }
 
     // Converts a future into a task and sends it to executor.
-    pub fn spawn(future: impl Future<Output = ()> + 'static, executor: Sender<Rc<Task>>) {
-        let task = Rc::new(Task::new(future, executor));
+    pub fn spawn(
+        future: impl Future<Output = ()> + 'static,
+        component_id: Option<Id>,
+        executor: Sender<Rc<Task>>,
+    ) -> Rc<Task> {
+        let task = Rc::new(Task::new(future, component_id, executor));
         task.schedule();
+        task
+    }
+
+    // Returns the component which spawned the task.
+    pub fn component_id(&self) -> Option<Id> {
+        self.component_id
     }
 
     // Polls the internal future and passes waker to it.
     // This method is called by the executor when the task is created or woken up.
     // Calling this method after the task completion will result in panic.
     pub fn poll(self: Rc<Self>) {
+        // Aborted task can still be scheduled by wake-up notifications received before the abort
+        if self.aborted.get() {
+            return;
+        }
         let mut future_slot = self.future.borrow_mut();
         if let Some(mut future) = future_slot.take() {
             // Create a waker from the task itself
             let waker = waker_ref(&self);
             // Create async context with waker and poll future with it
             let async_ctx = &mut Context::from_waker(&waker);
-            if future.as_mut().poll(async_ctx).is_pending() {
+            // The task can be aborted by itself during polling, then the future is dropped here
+            if future.as_mut().poll(async_ctx).is_pending() && !self.aborted.get() {
                 // Keep storing pending future
                 *future_slot = Some(future);
             }
@@ -50,6 +71,19 @@ impl Task {
         }
     }
 
+    // Cancels the task by dropping its future, which releases the pending promises held by the future.
+    pub fn abort(&self) {
+        if self.aborted.replace(true) {
+            return;
+        }
+        // The future is borrowed if the task aborts itself, then it is dropped after polling
+        if let Ok(mut future_slot) = self.future.try_borrow_mut() {
+            let future = future_slot.take();
+            drop(future_slot);
+            drop(future);
+        }
+    }
+
     // Schedules the task for polling by sending it to the executor.
     fn schedule(self: &Rc<Self>) {
         self.executor.send(self.clone());
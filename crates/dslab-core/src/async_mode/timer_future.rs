@@ -7,6 +7,7 @@ use std::pin::Pin;
 use std::rc::Rc;
 use std::task::{Context, Poll, Waker};
 
+use crate::time::{self, Timestamp};
 use crate::{state::SimulationState, Id};
 
 // Timer identifier.
@@ -69,13 +70,13 @@ pub(crate) struct TimerPromise {
     // Id of simulation component that set the timer.
     pub component_id: Id,
     // The time when the timer will be fired.
-    pub time: f64,
+    pub time: Timestamp,
     // State with completion info shared with TimerFuture.
     state: Rc<RefCell<TimerAwaitState>>,
 }
 
 impl TimerPromise {
-    pub(crate) fn new(id: TimerId, component_id: Id, time: f64) -> Self {
+    pub(crate) fn new(id: TimerId, component_id: Id, time: Timestamp) -> Self {
         Self {
             id,
             component_id,
@@ -110,7 +111,7 @@ impl PartialEq for TimerPromise {
 
 impl Ord for TimerPromise {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.time.total_cmp(&self.time).then_with(|| other.id.cmp(&self.id))
+        time::compare(other.time, self.time).then_with(|| other.id.cmp(&self.id))
     }
 }
 
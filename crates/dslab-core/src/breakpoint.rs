@@ -0,0 +1,18 @@
+//! Pausing the simulation before the delivery of specific events.
+
+use crate::event::Event;
+
+/// Identifier of breakpoint.
+pub type BreakpointId = u32;
+
+pub(crate) type BreakpointFn = Box<dyn Fn(&Event) -> bool>;
+
+/// Describes the breakpoint which paused the simulation in
+/// [`Simulation::step_until_breakpoint`](crate::Simulation::step_until_breakpoint).
+#[derive(Clone)]
+pub struct BreakpointHit {
+    /// Identifier of the matched breakpoint.
+    pub breakpoint_id: BreakpointId,
+    /// Copy of the matched event, which is not delivered yet.
+    pub event: Event,
+}
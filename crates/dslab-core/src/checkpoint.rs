@@ -0,0 +1,246 @@
+//! Simulation checkpointing.
+
+use std::collections::BTreeMap;
+
+use rand_pcg::Pcg64;
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::component::Id;
+use crate::event::{Event, EventData, EventId};
+use crate::metrics::MetricsRegistry;
+use crate::periodic::{PeriodicEventId, PeriodicSeries};
+use crate::time::{self, Timestamp};
+
+/// Trait for simulation components that can save and restore their state.
+///
+/// Components implementing this trait should be registered via
+/// [`Simulation::add_checkpointable`](crate::Simulation::add_checkpointable) to be included in checkpoints.
+pub trait Checkpointable {
+    /// Returns the component state to be stored in checkpoint.
+    fn save_state(&self) -> Value;
+
+    /// Restores the component state from the value previously returned by [`save_state`](Self::save_state).
+    fn restore_state(&mut self, state: &Value);
+}
+
+/// Snapshot of the simulation state which can be used to resume the simulation from this point.
+///
+/// Checkpoint includes the simulation clock, random number generators, pending and periodic events, metrics and
+/// the state of components registered via [`Simulation::add_checkpointable`](crate::Simulation::add_checkpointable).
+///
+/// Checkpoints are created by [`Simulation::checkpoint`](crate::Simulation::checkpoint) and applied by
+/// [`Simulation::restore`](crate::Simulation::restore). The same checkpoint can be restored multiple times,
+/// which allows running several simulation branches starting from a common state.
+#[derive(Clone)]
+pub struct SimulationCheckpoint {
+    pub(crate) time: Timestamp,
+    pub(crate) rand: Pcg64,
+    pub(crate) component_rands: Vec<Option<Pcg64>>,
+    pub(crate) named_rands: Vec<(Id, String, Pcg64)>,
+    pub(crate) event_count: u64,
+    pub(crate) component_names: Vec<String>,
+    pub(crate) events: Vec<Event>,
+    pub(crate) ordered_events: Vec<Event>,
+    pub(crate) periodic_events: Vec<(PeriodicEventId, PeriodicSeries)>,
+    pub(crate) periodic_event_count: u64,
+    pub(crate) metrics: MetricsRegistry,
+    pub(crate) components: BTreeMap<String, Value>,
+}
+
+impl SimulationCheckpoint {
+    /// Returns the simulation time at which the checkpoint was created.
+    pub fn time(&self) -> f64 {
+        time::seconds(self.time)
+    }
+
+    /// Returns the number of pending events stored in the checkpoint.
+    pub fn pending_event_count(&self) -> usize {
+        self.events.len() + self.ordered_events.len()
+    }
+
+    /// Returns the saved state of component with specified name, if any.
+    pub fn component_state(&self, name: &str) -> Option<&Value> {
+        self.components.get(name)
+    }
+}
+
+// Serialized representation of checkpoint ----------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SerializedEvent {
+    id: EventId,
+    time: f64,
+    // Exact time which is stored in addition to the time in seconds in integer time mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_nanos: Option<u64>,
+    src: Id,
+    dst: Id,
+    ordered: bool,
+    #[serde(rename = "type")]
+    type_name: String,
+    data: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SerializedPeriodicEvent {
+    id: PeriodicEventId,
+    period: f64,
+    paused: bool,
+    last_event: SerializedEvent,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SerializedCheckpoint {
+    time: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_nanos: Option<u64>,
+    rand: Pcg64,
+    component_rands: Vec<Option<Pcg64>>,
+    named_rands: Vec<(Id, String, Pcg64)>,
+    event_count: u64,
+    component_names: Vec<String>,
+    events: Vec<SerializedEvent>,
+    periodic_events: Vec<SerializedPeriodicEvent>,
+    periodic_event_count: u64,
+    metrics: MetricsRegistry,
+    components: BTreeMap<String, Value>,
+}
+
+type EventDeserializer = Box<dyn Fn(Value) -> serde_json::Result<Box<dyn EventData>>>;
+
+// Stores event types which can be serialized to and deserialized from checkpoint files.
+#[derive(Default)]
+pub(crate) struct EventTypeRegistry {
+    names: FxHashMap<std::any::TypeId, &'static str>,
+    deserializers: FxHashMap<&'static str, EventDeserializer>,
+}
+
+impl EventTypeRegistry {
+    pub fn register<T: EventData + DeserializeOwned>(&mut self) {
+        let name = std::any::type_name::<T>();
+        self.names.insert(std::any::TypeId::of::<T>(), name);
+        self.deserializers.insert(
+            name,
+            Box::new(|value| serde_json::from_value::<T>(value).map(|data| Box::new(data) as Box<dyn EventData>)),
+        );
+    }
+
+    pub fn serialize(&self, checkpoint: &SimulationCheckpoint) -> Result<SerializedCheckpoint, String> {
+        let heap_events = checkpoint.events.iter().map(|e| (e, false));
+        let ordered_events = checkpoint.ordered_events.iter().map(|e| (e, true));
+        let events = heap_events
+            .chain(ordered_events)
+            .map(|(event, ordered)| self.serialize_event(event, ordered))
+            .collect::<Result<Vec<_>, String>>()?;
+        let periodic_events = checkpoint
+            .periodic_events
+            .iter()
+            .map(|(id, series)| {
+                Ok(SerializedPeriodicEvent {
+                    id: *id,
+                    period: series.period,
+                    paused: series.paused,
+                    last_event: self.serialize_event(&series.last_event, false)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(SerializedCheckpoint {
+            time: checkpoint.time(),
+            time_nanos: time::INTEGER_TIME.then(|| time::nanos(checkpoint.time)),
+            rand: checkpoint.rand.clone(),
+            component_rands: checkpoint.component_rands.clone(),
+            named_rands: checkpoint.named_rands.clone(),
+            event_count: checkpoint.event_count,
+            component_names: checkpoint.component_names.clone(),
+            events,
+            periodic_events,
+            periodic_event_count: checkpoint.periodic_event_count,
+            metrics: checkpoint.metrics.clone(),
+            components: checkpoint.components.clone(),
+        })
+    }
+
+    pub fn deserialize(&self, checkpoint: SerializedCheckpoint) -> Result<SimulationCheckpoint, String> {
+        let mut events = Vec::new();
+        let mut ordered_events = Vec::new();
+        for e in checkpoint.events {
+            let ordered = e.ordered;
+            let event = self.deserialize_event(e)?;
+            if ordered {
+                ordered_events.push(event);
+            } else {
+                events.push(event);
+            }
+        }
+        let mut periodic_events = Vec::new();
+        for p in checkpoint.periodic_events {
+            let series = PeriodicSeries {
+                last_event: self.deserialize_event(p.last_event)?,
+                period: p.period,
+                paused: p.paused,
+            };
+            periodic_events.push((p.id, series));
+        }
+        Ok(SimulationCheckpoint {
+            time: deserialize_time(checkpoint.time, checkpoint.time_nanos),
+            rand: checkpoint.rand,
+            component_rands: checkpoint.component_rands,
+            named_rands: checkpoint.named_rands,
+            event_count: checkpoint.event_count,
+            component_names: checkpoint.component_names,
+            events,
+            ordered_events,
+            periodic_events,
+            periodic_event_count: checkpoint.periodic_event_count,
+            metrics: checkpoint.metrics,
+            components: checkpoint.components,
+        })
+    }
+
+    fn deserialize_event(&self, e: SerializedEvent) -> Result<Event, String> {
+        let deserializer = self
+            .deserializers
+            .get(e.type_name.as_str())
+            .ok_or_else(|| format!("Event type {} is not registered", e.type_name))?;
+        let data = deserializer(e.data).map_err(|err| format!("Failed to deserialize event {}: {}", e.id, err))?;
+        Ok(Event::from_timestamp(
+            e.id,
+            deserialize_time(e.time, e.time_nanos),
+            e.src,
+            e.dst,
+            data,
+        ))
+    }
+
+    fn serialize_event(&self, event: &Event, ordered: bool) -> Result<SerializedEvent, String> {
+        let type_name = self.names.get(&event.data.as_any().type_id()).ok_or_else(|| {
+            format!(
+                "Event type {} is not registered, use Simulation::register_event_type",
+                serde_type_name::type_name(&event.data).unwrap_or("unknown")
+            )
+        })?;
+        let data = serde_json::to_value(&event.data)
+            .map_err(|err| format!("Failed to serialize event {}: {}", event.id, err))?;
+        Ok(SerializedEvent {
+            id: event.id,
+            time: event.time_seconds(),
+            time_nanos: time::INTEGER_TIME.then(|| time::nanos(event.time)),
+            src: event.src,
+            dst: event.dst,
+            ordered,
+            type_name: type_name.to_string(),
+            data,
+        })
+    }
+}
+
+// Uses the exact time in nanoseconds if it is available and the integer time mode is enabled.
+fn deserialize_time(time: f64, time_nanos: Option<u64>) -> Timestamp {
+    match time_nanos {
+        Some(nanos) if time::INTEGER_TIME => time::add_delay_nanos(Timestamp::default(), nanos),
+        _ => time::timestamp(time),
+    }
+}
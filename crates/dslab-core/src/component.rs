@@ -1,4 +1,59 @@
 //! Simulation components.
+//!
+//! Component names can be hierarchical, i.e. consist of multiple segments separated by [`NAME_SEPARATOR`],
+//! such as `rack1/host3/disk0`. Such names are produced by
+//! [`SimulationContext::create_child_context`](crate::SimulationContext::create_child_context).
+//! The components with names starting with `rack1/host3/` form a subtree of `rack1/host3` component,
+//! which can be looked up via [`Simulation::lookup_ids`](crate::Simulation::lookup_ids)
+//! and removed at once via [`Simulation::remove_subtree`](crate::Simulation::remove_subtree).
 
 /// Identifier of simulation component.
 pub type Id = u32;
+
+/// Separator of segments in hierarchical component names.
+pub const NAME_SEPARATOR: char = '/';
+
+/// Returns the name of child component with specified name relative to the parent component.
+pub(crate) fn child_name(parent: &str, child: &str) -> String {
+    format!("{}{}{}", parent, NAME_SEPARATOR, child)
+}
+
+/// Checks whether the component name belongs to the subtree of component with the specified root name.
+pub(crate) fn in_subtree(name: &str, root: &str) -> bool {
+    name.strip_prefix(root)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(NAME_SEPARATOR))
+}
+
+/// Checks whether the component name matches the glob pattern.
+///
+/// The pattern is matched segment by segment. Within a segment, `*` matches any sequence of characters
+/// and `?` matches any single character. A segment consisting of `**` matches any number of segments, including zero.
+pub(crate) fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split(NAME_SEPARATOR).collect();
+    let name: Vec<&str> = name.split(NAME_SEPARATOR).collect();
+    match_segments(&pattern, &name)
+}
+
+fn match_segments(pattern: &[&str], name: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&"**", rest)) => (0..=name.len()).any(|skip| match_segments(rest, &name[skip..])),
+        Some((segment, rest)) => match name.split_first() {
+            Some((name_segment, name_rest)) => {
+                let segment: Vec<char> = segment.chars().collect();
+                let name_segment: Vec<char> = name_segment.chars().collect();
+                match_segment(&segment, &name_segment) && match_segments(rest, name_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
@@ -3,13 +3,20 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use log::debug;
 use rand::distributions::uniform::{SampleRange, SampleUniform};
 use rand::prelude::Distribution;
+use serde_json::json;
 
 use crate::async_mode_enabled;
-use crate::component::Id;
+use crate::component::{child_name, Id};
 use crate::event::{Event, EventData, EventId};
+use crate::metrics::ComponentMetrics;
+use crate::periodic::PeriodicEventHandle;
+use crate::random::{RandStream, RandomStream};
 use crate::state::SimulationState;
+use crate::time::{self, Timestamp};
+use crate::trace::SpanId;
 
 async_mode_enabled!(
     use std::any::TypeId;
@@ -19,6 +26,8 @@ async_mode_enabled!(
 
     use crate::async_mode::event_future::EventFuture;
     use crate::async_mode::EventKey;
+    use crate::async_mode::interval::Interval;
+    use crate::async_mode::join_handle::JoinHandle;
     use crate::async_mode::timer_future::TimerFuture;
 );
 
@@ -70,6 +79,38 @@ impl SimulationContext {
         &self.name
     }
 
+    /// Creates a new context for the child component with specified name.
+    ///
+    /// The child component is registered under the hierarchical name `<parent name>/<name>`
+    /// (see [`component`](crate::component) module), so the components can be organized into a tree.
+    /// The whole subtree can be looked up via [`Simulation::lookup_ids`](crate::Simulation::lookup_ids)
+    /// and removed via [`Simulation::remove_subtree`](crate::Simulation::remove_subtree).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let rack_ctx = sim.create_context("rack1");
+    /// let host_ctx = rack_ctx.create_child_context("host3");
+    /// let disk_ctx = host_ctx.create_child_context("disk0");
+    /// assert_eq!(disk_ctx.name(), "rack1/host3/disk0");
+    /// assert_eq!(sim.lookup_id("rack1/host3/disk0"), disk_ctx.id());
+    /// ```
+    pub fn create_child_context(&self, name: &str) -> SimulationContext {
+        let name = child_name(&self.name, name);
+        let id = self.sim_state.borrow_mut().register(&name);
+        debug!(
+            target: "simulation",
+            "[{:.3} {} simulation] Created context: {}",
+            self.time(),
+            crate::log::get_colored("DEBUG", colored::Color::Blue),
+            json!({"name": name, "id": id, "parent": self.id})
+        );
+        SimulationContext::new(id, &name, self.sim_state.clone())
+    }
+
     /// Returns the current simulation time.
     ///
     /// # Examples
@@ -86,8 +127,45 @@ impl SimulationContext {
         self.sim_state.borrow().time()
     }
 
+    /// Returns the current simulation time in nanoseconds.
+    ///
+    /// With `integer_time` feature this is the exact value of simulation clock, otherwise the current time
+    /// is rounded to the nearest nanosecond (see [`time`](crate::time)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use dslab_core::Simulation;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct SomeEvent {
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp_ctx = sim.create_context("comp");
+    /// comp_ctx.emit_self(SomeEvent {}, 1.5);
+    /// sim.step();
+    /// assert_eq!(comp_ctx.time_nanos(), 1_500_000_000);
+    /// ```
+    pub fn time_nanos(&self) -> u64 {
+        self.sim_state.borrow().time_nanos()
+    }
+
+    /// Converts time in seconds to the nearest integer number of nanoseconds.
+    ///
+    /// This is the conversion applied to event delays with `integer_time` feature.
+    pub fn to_nanos(&self, time: f64) -> u64 {
+        time::to_nanos(time)
+    }
+
+    /// Converts integer number of nanoseconds to time in seconds.
+    pub fn from_nanos(&self, nanos: u64) -> f64 {
+        time::from_nanos(nanos)
+    }
+
     /// Returns a random float in the range _[0, 1)_
-    /// using the simulation-wide random number generator.
+    /// using the random number generator of the component (see [`random`](crate::random)).
     ///
     /// # Examples
     ///
@@ -100,11 +178,11 @@ impl SimulationContext {
     /// assert!(f >= 0.0 && f < 1.0);
     /// ```
     pub fn rand(&self) -> f64 {
-        self.sim_state.borrow_mut().rand()
+        self.sim_state.borrow_mut().rand(RandStream::Component(self.id))
     }
 
     /// Returns a random number in the specified range
-    /// using the simulation-wide random number generator.
+    /// using the random number generator of the component (see [`random`](crate::random)).
     ///
     /// # Examples
     ///
@@ -123,19 +201,52 @@ impl SimulationContext {
         T: SampleUniform,
         R: SampleRange<T>,
     {
-        self.sim_state.borrow_mut().gen_range(range)
+        self.sim_state
+            .borrow_mut()
+            .gen_range(RandStream::Component(self.id), range)
     }
 
     /// Returns a random value from the specified distribution
-    /// using the simulation-wide random number generator.
+    /// using the random number generator of the component (see [`random`](crate::random)).
     pub fn sample_from_distribution<T, Dist: Distribution<T>>(&self, dist: &Dist) -> T {
-        self.sim_state.borrow_mut().sample_from_distribution(dist)
+        self.sim_state
+            .borrow_mut()
+            .sample_from_distribution(RandStream::Component(self.id), dist)
     }
 
     /// Returns a random alphanumeric string of specified length
-    /// using the simulation-wide random number generator.
+    /// using the random number generator of the component (see [`random`](crate::random)).
     pub fn random_string(&self, len: usize) -> String {
-        self.sim_state.borrow_mut().random_string(len)
+        self.sim_state
+            .borrow_mut()
+            .random_string(RandStream::Component(self.id), len)
+    }
+
+    /// Returns the named random stream of the component.
+    ///
+    /// The stream is derived from the simulation seed, the component name and the stream name, so it is not
+    /// affected by random draws from other streams. Repeated calls with the same name return handles to the same
+    /// stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    ///
+    /// let draw = |extra_draws: usize| {
+    ///     let mut sim = Simulation::new(123);
+    ///     let comp_ctx = sim.create_context("comp");
+    ///     for _ in 0..extra_draws {
+    ///         comp_ctx.rand();
+    ///     }
+    ///     let arrivals = comp_ctx.random_stream("arrivals");
+    ///     arrivals.gen_range(0..1000)
+    /// };
+    /// assert_eq!(draw(0), draw(10));
+    /// ```
+    pub fn random_stream(&self, name: &str) -> RandomStream {
+        let index = self.sim_state.borrow_mut().create_rand_stream(self.id, name);
+        RandomStream::new(index, self.sim_state.clone())
     }
 
     /// Creates new event with specified payload, destination and delay, returns event id.
@@ -165,10 +276,11 @@ impl SimulationContext {
     ///
     /// impl EventHandler for Component {
     ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
     ///         cast!(match event.data {
     ///             SomeEvent { some_field } => {
     ///                 assert_eq!(self.ctx.time(), 1.2);
-    ///                 assert_eq!(event.time, 1.2);
+    ///                 assert_eq!(time, 1.2);
     ///                 assert_eq!(event.id, 0);
     ///                 assert_eq!(some_field, 16);
     ///             }
@@ -207,6 +319,38 @@ impl SimulationContext {
         self.sim_state.borrow_mut().add_event(data, self.id, dst, delay)
     }
 
+    /// Same as [`emit`](Self::emit), but the delay is specified as an integer number of nanoseconds.
+    ///
+    /// With `integer_time` feature the delay is added to the simulation clock exactly,
+    /// otherwise it is converted to seconds (see [`time`](crate::time)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use dslab_core::Simulation;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct SomeEvent {
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp1_ctx = sim.create_context("comp1");
+    /// let comp2_ctx = sim.create_context("comp2");
+    /// comp1_ctx.emit_nanos(SomeEvent {}, comp2_ctx.id(), 250_000_000);
+    /// sim.step();
+    /// assert_eq!(sim.time(), 0.25);
+    /// assert_eq!(sim.time_nanos(), 250_000_000);
+    /// ```
+    pub fn emit_nanos<T>(&self, data: T, dst: Id, delay: u64) -> EventId
+    where
+        T: EventData,
+    {
+        self.sim_state
+            .borrow_mut()
+            .add_boxed_event_nanos(Box::new(data), self.id, dst, delay)
+    }
+
     /// This and all other `emit_ordered...` functions are special variants of normal `emit_...` functions
     /// that allow adding events to ordered event deque instead of heap, which may improve simulation performance.
     ///
@@ -314,10 +458,11 @@ impl SimulationContext {
     ///
     /// impl EventHandler for Component {
     ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
     ///         cast!(match event.data {
     ///             SomeEvent { some_field } => {
     ///                 assert_eq!(self.ctx.time(), 0.0);
-    ///                 assert_eq!(event.time, 0.0);
+    ///                 assert_eq!(time, 0.0);
     ///                 assert_eq!(event.id, 0);
     ///                 assert_eq!(some_field, 16);
     ///             }
@@ -379,10 +524,11 @@ impl SimulationContext {
     ///
     /// impl EventHandler for Component {
     ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
     ///         cast!(match event.data {
     ///             SomeEvent { some_field } => {
     ///                 assert_eq!(self.ctx.time(), 6.4);
-    ///                 assert_eq!(event.time, 6.4);
+    ///                 assert_eq!(time, 6.4);
     ///                 assert_eq!(event.id, 0);
     ///                 assert_eq!(event.src, self.ctx.id());
     ///                 assert_eq!(some_field, 16);
@@ -446,10 +592,11 @@ impl SimulationContext {
     ///
     /// impl EventHandler for Component {
     ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
     ///         cast!(match event.data {
     ///             SomeEvent { some_field } => {
     ///                 assert_eq!(self.ctx.time(), 0.0);
-    ///                 assert_eq!(event.time, 0.0);
+    ///                 assert_eq!(time, 0.0);
     ///                 assert_eq!(event.id, 0);
     ///                 assert_eq!(event.src, self.ctx.id());
     ///                 assert_eq!(some_field, 16);
@@ -505,10 +652,11 @@ impl SimulationContext {
     ///
     /// impl EventHandler for Component {
     ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
     ///         cast!(match event.data {
     ///             SomeEvent { some_field } => {
     ///                 assert_eq!(self.ctx.time(), 2.4);
-    ///                 assert_eq!(event.time, 2.4);
+    ///                 assert_eq!(time, 2.4);
     ///                 assert_eq!(event.id, 0);
     ///                 assert_eq!(event.src, 0);
     ///                 assert_eq!(self.ctx.id(), 1);
@@ -544,6 +692,83 @@ impl SimulationContext {
         self.sim_state.borrow_mut().add_ordered_event(data, src, dst, delay)
     }
 
+    /// Creates a series of periodic events for itself with specified payload, returns handle for controlling it.
+    ///
+    /// The first event is emitted after `start_delay`, the following ones are emitted every `period` after
+    /// the previous one. The next occurrence is scheduled when the previous one is delivered, so each occurrence has
+    /// a separate event id. The series can be paused, resumed, re-timed or cancelled via the returned
+    /// [`PeriodicEventHandle`]. Cancelling the pending occurrence via [`cancel_event`](Self::cancel_event) or
+    /// [`cancel_events`](Self::cancel_events) also stops the series.
+    ///
+    /// Note that an active series always has a pending event, so the simulation cannot run out of events
+    /// until the series is paused or cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use serde::Serialize;
+    /// use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Heartbeat {}
+    ///
+    /// struct Component {
+    ///     heartbeats: Vec<f64>,
+    ///     ctx: SimulationContext,
+    /// }
+    ///
+    /// impl EventHandler for Component {
+    ///     fn on(&mut self, event: Event) {
+    ///         cast!(match event.data {
+    ///             Heartbeat {} => {
+    ///                 self.heartbeats.push(self.ctx.time());
+    ///             }
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let ctx = sim.create_context("comp");
+    /// let handle = ctx.emit_periodic(Heartbeat {}, 2., 1.);
+    /// let comp = Rc::new(RefCell::new(Component { heartbeats: Vec::new(), ctx }));
+    /// sim.add_handler("comp", comp.clone());
+    ///
+    /// sim.step_until_time(6.);
+    /// assert_eq!(comp.borrow().heartbeats, vec![1., 3., 5.]);
+    ///
+    /// handle.pause();
+    /// sim.step_until_time(10.);
+    /// assert_eq!(comp.borrow().heartbeats.len(), 3);
+    ///
+    /// handle.set_period(0.5);
+    /// handle.resume();
+    /// sim.step_until_time(11.);
+    /// assert_eq!(comp.borrow().heartbeats, vec![1., 3., 5., 10.5, 11.]);
+    ///
+    /// handle.cancel();
+    /// assert!(!handle.is_active());
+    /// sim.step_until_no_events();
+    /// assert_eq!(comp.borrow().heartbeats.len(), 5);
+    /// ```
+    pub fn emit_periodic<T>(&self, data: T, period: f64, start_delay: f64) -> PeriodicEventHandle
+    where
+        T: EventData,
+    {
+        let id = self
+            .sim_state
+            .borrow_mut()
+            .add_periodic_event(Box::new(data), self.id, self.id, period, start_delay);
+        PeriodicEventHandle::new(id, self.sim_state.clone())
+    }
+
+    // Reserves id for event sent to another partition of parallel simulation,
+    // returns it along with the current time and the time of event with specified delay.
+    pub(crate) fn reserve_remote_event(&self, delay: f64) -> (EventId, Timestamp, Timestamp) {
+        self.sim_state.borrow_mut().reserve_remote_event(delay)
+    }
+
     /// Cancels the specified event.
     ///
     /// Use [`EventId`] obtained when creating the event to cancel it.
@@ -612,6 +837,53 @@ impl SimulationContext {
         self.sim_state.borrow_mut().cancel_heap_events(pred);
     }
 
+    /// Returns the interface for reporting metrics of the component.
+    ///
+    /// See [`metrics`](crate::metrics) for the description of supported metric types.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    /// use dslab_core::metrics::MetricValue;
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp_ctx = sim.create_context("comp");
+    /// comp_ctx.metrics().increment_counter("requests", 1);
+    /// comp_ctx.metrics().increment_counter("requests", 2);
+    /// comp_ctx.metrics().update_time_weighted("utilization", 1.);
+    /// sim.step_until_time(3.);
+    /// comp_ctx.metrics().update_time_weighted("utilization", 0.);
+    /// sim.step_until_time(4.);
+    ///
+    /// let snapshot = sim.metrics_snapshot();
+    /// assert_eq!(snapshot.time, 4.);
+    /// assert_eq!(snapshot.get("comp", "requests"), Some(&MetricValue::Counter { value: 3 }));
+    /// match snapshot.get("comp", "utilization").unwrap() {
+    ///     MetricValue::TimeWeighted { average, .. } => assert_eq!(*average, 0.75),
+    ///     _ => panic!("unexpected metric type"),
+    /// }
+    /// ```
+    pub fn metrics(&self) -> ComponentMetrics {
+        ComponentMetrics::new(self.id, self.sim_state.clone())
+    }
+
+    /// Starts a span with the specified name at the current time and returns its identifier.
+    ///
+    /// Spans represent the activities of component (e.g. task execution or disk operation) shown on the timeline
+    /// of the simulation trace. The spans of the same component can overlap. If tracing is not enabled via
+    /// [`Simulation::enable_tracing`](crate::Simulation::enable_tracing), spans are not recorded.
+    ///
+    /// See [`Simulation::enable_tracing`](crate::Simulation::enable_tracing) for example.
+    pub fn start_span(&self, name: &str) -> SpanId {
+        self.sim_state.borrow_mut().start_span(self.id, name)
+    }
+
+    /// Finishes the previously started span at the current time.
+    pub fn finish_span(&self, span_id: SpanId) {
+        self.sim_state.borrow_mut().finish_span(span_id);
+    }
+
     /// Returns component name by its identifier.
     ///
     /// # Examples
@@ -654,6 +926,26 @@ impl SimulationContext {
         self.sim_state.borrow().lookup_name(id)
     }
 
+    /// Returns the identifiers of components with names matching the glob pattern, ordered by Id.
+    ///
+    /// See [`Simulation::lookup_ids`](crate::Simulation::lookup_ids) for the pattern syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let host_ctx = sim.create_context("host");
+    /// let disk0_ctx = host_ctx.create_child_context("disk0");
+    /// let disk1_ctx = host_ctx.create_child_context("disk1");
+    /// let pattern = format!("{}/disk*", host_ctx.name());
+    /// assert_eq!(host_ctx.lookup_ids(&pattern), vec![disk0_ctx.id(), disk1_ctx.id()]);
+    /// ```
+    pub fn lookup_ids(&self, pattern: &str) -> Vec<Id> {
+        self.sim_state.borrow().lookup_ids(pattern)
+    }
+
     async_mode_enabled!(
         /// Spawns a new asynchronous task for component associated with this context.
         ///
@@ -662,6 +954,9 @@ impl SimulationContext {
         /// In order to spawn asynchronous tasks, component is required to be [registered](crate::Simulation::add_static_handler)
         /// as [`StaticEventHandler`](crate::StaticEventHandler). See the examples below.
         ///
+        /// Returns the handle which can be used to await the task result or to abort the task
+        /// (see [`JoinHandle`](crate::async_mode::JoinHandle)).
+        ///
         /// # Examples
         ///
         /// ```rust
@@ -834,8 +1129,8 @@ impl SimulationContext {
         /// // 1 + 2 + 3 + ... + 10 = 55
         /// assert_eq!(*comp.counter.borrow(), 55);
         /// ```
-        pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
-            self.sim_state.borrow_mut().spawn_component(self.id(), future);
+        pub fn spawn<T: 'static>(&self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+            self.sim_state.borrow_mut().spawn_component(self.id(), future)
         }
 
         /// Waits (asynchronously) until `duration` seconds have elapsed.
@@ -878,6 +1173,38 @@ impl SimulationContext {
                 .create_timer(self.id, duration, self.sim_state.clone())
         }
 
+        /// Creates a stream of periodic ticks with specified period, which is an async equivalent of
+        /// [`emit_periodic`](Self::emit_periodic).
+        ///
+        /// The first tick happens after one period from the current time. Ticks can be awaited one by one via
+        /// [`Interval::tick`] or consumed as a [`Stream`](futures::Stream).
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use futures::StreamExt;
+        /// use dslab_core::Simulation;
+        ///
+        /// let mut sim = Simulation::new(123);
+        ///
+        /// let ctx = sim.create_context("comp");
+        ///
+        /// sim.spawn(async move {
+        ///     let mut interval = ctx.interval(2.);
+        ///     assert_eq!(interval.tick().await, 2.);
+        ///     assert_eq!(interval.tick().await, 4.);
+        ///
+        ///     let ticks = interval.take(3).collect::<Vec<_>>().await;
+        ///     assert_eq!(ticks, vec![6., 8., 10.]);
+        /// });
+        ///
+        /// sim.step_until_no_events();
+        /// assert_eq!(sim.time(), 10.);
+        /// ```
+        pub fn interval(&self, period: f64) -> Interval {
+            Interval::new(self.id, period, self.sim_state.clone())
+        }
+
         /// Waits (asynchronously) until all events scheduled at the current time are processed.
         ///
         /// May be useful to execute some logic without a time delay but after all events have been processed.
@@ -943,7 +1270,7 @@ impl SimulationContext {
         pub async fn yield_now(&self) {
             let current_time = self.time();
             let need_yield = if let Some(next_event) = self.sim_state.borrow_mut().peek_event() {
-                next_event.time == current_time
+                next_event.time_seconds() == current_time
             } else {
                 false
             };
@@ -0,0 +1,296 @@
+//! Declarative specification of probability distributions.
+//!
+//! [`DistributionSpec`] describes a distribution of real values and can be deserialized from configuration files,
+//! e.g. the following YAML describes a mixture of exponential and log-normal distributions:
+//!
+//! ```yaml
+//! type: mixture
+//! components:
+//!   - weight: 0.8
+//!     distribution: { type: exponential, rate: 2.0 }
+//!   - weight: 0.2
+//!     distribution: { type: log_normal, mu: 1.0, sigma: 0.5 }
+//! ```
+//!
+//! The specification is validated and converted into [`DistributionSampler`] via [`DistributionSpec::build`].
+//! The sampler implements [`rand::distributions::Distribution`], so it can be sampled using the random number
+//! generator of component via
+//! [`SimulationContext::sample_from_distribution`](crate::SimulationContext::sample_from_distribution)
+//! or via named random streams (see [`random`](crate::random)), which keeps the simulation deterministic.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dslab_core::distribution::DistributionSpec;
+//! use dslab_core::Simulation;
+//!
+//! let spec: DistributionSpec = serde_json::from_str(r#"{"type": "weibull", "scale": 2.0, "shape": 1.5}"#).unwrap();
+//! let dist = spec.build().unwrap();
+//!
+//! let mut sim = Simulation::new(123);
+//! let ctx = sim.create_context("comp");
+//! let value = ctx.sample_from_distribution(&dist);
+//! assert!(value >= 0.);
+//! ```
+
+use std::f64::consts::PI;
+
+use rand::distributions::Distribution;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Specification of probability distribution of real values.
+///
+/// In serialized form the distribution type is specified by the `type` field in snake case,
+/// e.g. `{"type": "exponential", "rate": 0.5}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DistributionSpec {
+    /// Always returns the same value.
+    Constant {
+        /// Returned value.
+        value: f64,
+    },
+    /// Uniform distribution on `[min, max)`.
+    Uniform {
+        /// Lower bound.
+        min: f64,
+        /// Upper bound.
+        max: f64,
+    },
+    /// Exponential distribution with specified rate (the mean is `1 / rate`).
+    Exponential {
+        /// Rate parameter (lambda).
+        rate: f64,
+    },
+    /// Normal distribution.
+    Normal {
+        /// Mean.
+        mean: f64,
+        /// Standard deviation.
+        std_dev: f64,
+    },
+    /// Log-normal distribution, i.e. the distribution of `exp(X)` where `X` is normal with specified parameters.
+    LogNormal {
+        /// Mean of the underlying normal distribution.
+        mu: f64,
+        /// Standard deviation of the underlying normal distribution.
+        sigma: f64,
+    },
+    /// Weibull distribution.
+    Weibull {
+        /// Scale parameter (lambda).
+        scale: f64,
+        /// Shape parameter (k).
+        shape: f64,
+    },
+    /// Pareto distribution with values not less than `scale`.
+    Pareto {
+        /// Scale parameter (minimum value).
+        scale: f64,
+        /// Shape parameter (alpha).
+        shape: f64,
+    },
+    /// Empirical distribution specified by points `(value, cumulative probability)` of its CDF.
+    ///
+    /// The points should be ordered by value with non-decreasing probabilities, the last probability should be 1.
+    /// The values are sampled by inverting the piecewise linear CDF passing through the points.
+    Empirical {
+        /// CDF points.
+        points: Vec<(f64, f64)>,
+    },
+    /// Empirical distribution with CDF points read from CSV file without header,
+    /// where each line contains value and cumulative probability (see [`DistributionSpec::Empirical`]).
+    EmpiricalFile {
+        /// Path to CSV file.
+        path: String,
+    },
+    /// Mixture of distributions, where each component is chosen with probability proportional to its weight.
+    Mixture {
+        /// Mixture components.
+        components: Vec<MixtureComponent>,
+    },
+}
+
+/// Component of [`DistributionSpec::Mixture`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MixtureComponent {
+    /// Component weight.
+    pub weight: f64,
+    /// Component distribution.
+    pub distribution: DistributionSpec,
+}
+
+impl DistributionSpec {
+    /// Validates the specification and builds the corresponding sampler.
+    ///
+    /// For [`DistributionSpec::EmpiricalFile`] the CDF points are read from the file.
+    pub fn build(&self) -> Result<DistributionSampler, String> {
+        let sampler = match self {
+            Self::Constant { value } => {
+                check(value.is_finite(), "constant value should be finite")?;
+                DistributionSampler::Constant(*value)
+            }
+            Self::Uniform { min, max } => {
+                check(min.is_finite() && max.is_finite(), "uniform bounds should be finite")?;
+                check(min <= max, "uniform min should not exceed max")?;
+                DistributionSampler::Uniform(*min, *max)
+            }
+            Self::Exponential { rate } => {
+                check(*rate > 0. && rate.is_finite(), "exponential rate should be positive")?;
+                DistributionSampler::Exponential(*rate)
+            }
+            Self::Normal { mean, std_dev } => {
+                check(mean.is_finite(), "normal mean should be finite")?;
+                check(
+                    *std_dev >= 0. && std_dev.is_finite(),
+                    "normal std_dev should be non-negative",
+                )?;
+                DistributionSampler::Normal(*mean, *std_dev)
+            }
+            Self::LogNormal { mu, sigma } => {
+                check(mu.is_finite(), "log-normal mu should be finite")?;
+                check(
+                    *sigma >= 0. && sigma.is_finite(),
+                    "log-normal sigma should be non-negative",
+                )?;
+                DistributionSampler::LogNormal(*mu, *sigma)
+            }
+            Self::Weibull { scale, shape } => {
+                check(*scale > 0. && scale.is_finite(), "Weibull scale should be positive")?;
+                check(*shape > 0. && shape.is_finite(), "Weibull shape should be positive")?;
+                DistributionSampler::Weibull(*scale, *shape)
+            }
+            Self::Pareto { scale, shape } => {
+                check(*scale > 0. && scale.is_finite(), "Pareto scale should be positive")?;
+                check(*shape > 0. && shape.is_finite(), "Pareto shape should be positive")?;
+                DistributionSampler::Pareto(*scale, *shape)
+            }
+            Self::Empirical { points } => empirical(points.clone())?,
+            Self::EmpiricalFile { path } => empirical(read_cdf_points(path)?)?,
+            Self::Mixture { components } => {
+                check(!components.is_empty(), "mixture should have at least one component")?;
+                let mut cumulative_weights = Vec::with_capacity(components.len());
+                let mut samplers = Vec::with_capacity(components.len());
+                let mut total_weight = 0.;
+                for component in components {
+                    check(
+                        component.weight >= 0. && component.weight.is_finite(),
+                        "mixture weights should be non-negative",
+                    )?;
+                    total_weight += component.weight;
+                    cumulative_weights.push(total_weight);
+                    samplers.push(component.distribution.build()?);
+                }
+                check(total_weight > 0., "mixture weights should not be all zero")?;
+                DistributionSampler::Mixture(cumulative_weights, samplers)
+            }
+        };
+        Ok(sampler)
+    }
+}
+
+/// Distribution built from [`DistributionSpec`].
+#[derive(Clone, Debug)]
+pub enum DistributionSampler {
+    /// See [`DistributionSpec::Constant`].
+    Constant(f64),
+    /// See [`DistributionSpec::Uniform`].
+    Uniform(f64, f64),
+    /// See [`DistributionSpec::Exponential`].
+    Exponential(f64),
+    /// See [`DistributionSpec::Normal`].
+    Normal(f64, f64),
+    /// See [`DistributionSpec::LogNormal`].
+    LogNormal(f64, f64),
+    /// See [`DistributionSpec::Weibull`].
+    Weibull(f64, f64),
+    /// See [`DistributionSpec::Pareto`].
+    Pareto(f64, f64),
+    /// Empirical distribution with validated CDF points.
+    Empirical(Vec<(f64, f64)>),
+    /// Mixture with cumulative weights and component distributions.
+    Mixture(Vec<f64>, Vec<DistributionSampler>),
+}
+
+impl Distribution<f64> for DistributionSampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Uniform(min, max) => min + (max - min) * rng.gen::<f64>(),
+            Self::Exponential(rate) => -open_unit(rng).ln() / rate,
+            Self::Normal(mean, std_dev) => mean + std_dev * standard_normal(rng),
+            Self::LogNormal(mu, sigma) => (mu + sigma * standard_normal(rng)).exp(),
+            Self::Weibull(scale, shape) => scale * (-open_unit(rng).ln()).powf(1. / shape),
+            Self::Pareto(scale, shape) => scale / open_unit(rng).powf(1. / shape),
+            Self::Empirical(points) => {
+                let u = rng.gen::<f64>();
+                let idx = points.partition_point(|&(_, p)| p < u);
+                if idx == 0 {
+                    return points[0].0;
+                }
+                let (v0, p0) = points[idx - 1];
+                let (v1, p1) = points[idx.min(points.len() - 1)];
+                if p1 > p0 {
+                    v0 + (v1 - v0) * (u - p0) / (p1 - p0)
+                } else {
+                    v1
+                }
+            }
+            Self::Mixture(cumulative_weights, samplers) => {
+                let total_weight = cumulative_weights.last().unwrap();
+                let u = rng.gen::<f64>() * total_weight;
+                let idx = cumulative_weights.partition_point(|&w| w <= u).min(samplers.len() - 1);
+                samplers[idx].sample(rng)
+            }
+        }
+    }
+}
+
+fn check(condition: bool, message: &str) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(format!("Invalid distribution: {}", message))
+    }
+}
+
+fn empirical(points: Vec<(f64, f64)>) -> Result<DistributionSampler, String> {
+    check(!points.is_empty(), "empirical CDF should have at least one point")?;
+    check(
+        points.iter().all(|(v, p)| v.is_finite() && (0. ..=1.).contains(p)),
+        "empirical CDF points should have finite values and probabilities in [0, 1]",
+    )?;
+    check(
+        points.windows(2).all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1),
+        "empirical CDF points should be ordered by value with non-decreasing probabilities",
+    )?;
+    check(
+        points.last().unwrap().1 == 1.,
+        "last point of empirical CDF should have probability 1",
+    )?;
+    Ok(DistributionSampler::Empirical(points))
+}
+
+fn read_cdf_points(path: &str) -> Result<Vec<(f64, f64)>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| format!("Failed to open file {}: {}", path, e))?;
+    reader
+        .deserialize()
+        .collect::<Result<Vec<(f64, f64)>, _>>()
+        .map_err(|e| format!("Failed to read CDF points from {}: {}", path, e))
+}
+
+// Returns a random number from (0, 1].
+fn open_unit<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    1. - rng.gen::<f64>()
+}
+
+// Returns a random number from the standard normal distribution using Box-Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let r = (-2. * open_unit(rng).ln()).sqrt();
+    r * (2. * PI * rng.gen::<f64>()).cos()
+}
@@ -7,6 +7,7 @@ use dyn_clone::{clone_trait_object, DynClone};
 use serde::ser::Serialize;
 
 use crate::component::Id;
+use crate::time::{self, Timestamp};
 
 /// Event identifier.
 pub type EventId = u64;
@@ -23,14 +24,19 @@ erased_serde::serialize_trait_object!(EventData);
 impl<T: Serialize + DynClone + 'static> EventData for T {}
 
 /// Representation of event.
+///
+/// Events are ordered by the exact time of occurrence, which is fixed upon the event creation (see [`crate::time`]).
 #[derive(Clone)]
 pub struct Event {
     /// Unique event identifier.
     ///
     /// Events are numbered sequentially starting from 0.
     pub id: EventId,
-    /// Time of event occurrence.
-    pub time: f64,
+    /// Exact time of event occurrence.
+    ///
+    /// This is the time in seconds by default and the integer number of nanoseconds with `integer_time` feature.
+    /// Use [`time_seconds`](Self::time_seconds) to get the time in seconds regardless of the enabled features.
+    pub time: Timestamp,
     /// Identifier of event source.
     pub src: Id,
     /// Identifier of event destination.
@@ -39,6 +45,38 @@ pub struct Event {
     pub data: Box<dyn EventData>,
 }
 
+impl Event {
+    /// Creates a new event with specified time in seconds.
+    ///
+    /// With `integer_time` feature the time is rounded to the nearest nanosecond.
+    pub fn new(id: EventId, time: f64, src: Id, dst: Id, data: Box<dyn EventData>) -> Self {
+        Self::from_timestamp(id, time::timestamp(time), src, dst, data)
+    }
+
+    pub(crate) fn from_timestamp(id: EventId, time: Timestamp, src: Id, dst: Id, data: Box<dyn EventData>) -> Self {
+        Self {
+            id,
+            time,
+            src,
+            dst,
+            data,
+        }
+    }
+
+    /// Returns the time of event occurrence in seconds.
+    pub fn time_seconds(&self) -> f64 {
+        time::seconds(self.time)
+    }
+
+    /// Returns the time of event occurrence in nanoseconds.
+    ///
+    /// With `integer_time` feature this is the exact event time, otherwise the event time is rounded
+    /// to the nearest nanosecond.
+    pub fn time_nanos(&self) -> u64 {
+        time::nanos(self.time)
+    }
+}
+
 impl Eq for Event {}
 
 impl PartialEq for Event {
@@ -49,7 +87,7 @@ impl PartialEq for Event {
 
 impl Ord for Event {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.time.total_cmp(&self.time).then_with(|| other.id.cmp(&self.id))
+        time::compare(other.time, self.time).then_with(|| other.id.cmp(&self.id))
     }
 }
 
@@ -86,10 +124,11 @@ impl Event {
     where
         T: EventData,
     {
+        let time = e.time_seconds();
         match e.data.downcast::<T>() {
             Ok(data) => TypedEvent {
                 id: e.id,
-                time: e.time,
+                time,
                 src: e.src,
                 dst: e.dst,
                 data: *data,
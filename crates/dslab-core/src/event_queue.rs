@@ -0,0 +1,457 @@
+//! Future event set implementations.
+//!
+//! The simulation stores pending events in an event queue implementing the [`EventQueue`] trait. The queue should
+//! return events in the order defined by [`Event`] comparison, i.e. by event time and then by event id. The default
+//! implementation is [`BinaryHeapEventQueue`]. For workloads with a large number of pending events, such as
+//! many periodic timers, [`CalendarEventQueue`] or [`LadderEventQueue`] can be more efficient. The queue is
+//! selected when creating the simulation via [`Simulation::with_event_queue`](crate::Simulation::with_event_queue).
+//!
+//! Note that events emitted via `emit_ordered...` methods are stored separately and are not affected by
+//! the choice of event queue.
+
+use std::collections::BinaryHeap;
+
+use dyn_clone::{clone_trait_object, DynClone};
+
+use crate::event::Event;
+
+/// Trait for implementing the set of pending events.
+pub trait EventQueue: DynClone {
+    /// Inserts event into the queue.
+    fn push(&mut self, event: Event);
+
+    /// Removes the next event (the one with minimum time and then minimum id) from the queue and returns it.
+    fn pop(&mut self) -> Option<Event>;
+
+    /// Returns a reference to the next event without removing it from the queue.
+    ///
+    /// Takes `&mut self` to allow implementations to reorganize internal structures while searching for the event.
+    fn peek(&mut self) -> Option<&Event>;
+
+    /// Returns the number of events in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the queue contains no events.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over all events in the queue in arbitrary order.
+    fn iter(&self) -> Box<dyn Iterator<Item = &Event> + '_>;
+
+    /// Removes all events from the queue.
+    fn clear(&mut self);
+}
+
+clone_trait_object!(EventQueue);
+
+/// Type of event queue implementation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventQueueType {
+    /// [`BinaryHeapEventQueue`].
+    #[default]
+    BinaryHeap,
+    /// [`CalendarEventQueue`].
+    Calendar,
+    /// [`LadderEventQueue`].
+    Ladder,
+}
+
+impl EventQueueType {
+    /// Creates a new empty event queue of this type.
+    pub fn create(&self) -> Box<dyn EventQueue> {
+        match self {
+            EventQueueType::BinaryHeap => Box::new(BinaryHeapEventQueue::new()),
+            EventQueueType::Calendar => Box::new(CalendarEventQueue::new()),
+            EventQueueType::Ladder => Box::new(LadderEventQueue::new()),
+        }
+    }
+}
+
+// Sorted event lists --------------------------------------------------------------------------------------------------
+
+// Sorts events in ascending order of Event comparison, so the next event is at the end of vector.
+fn sort_events(events: &mut [Event]) {
+    events.sort_unstable();
+}
+
+// Inserts event into the vector sorted by sort_events.
+fn insert_sorted(events: &mut Vec<Event>, event: Event) {
+    let pos = events.partition_point(|e| e < &event);
+    events.insert(pos, event);
+}
+
+// Binary heap ---------------------------------------------------------------------------------------------------------
+
+/// Event queue based on binary heap.
+///
+/// Provides _O(log n)_ insertion and removal.
+#[derive(Clone, Default)]
+pub struct BinaryHeapEventQueue {
+    events: BinaryHeap<Event>,
+}
+
+impl BinaryHeapEventQueue {
+    /// Creates a new empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EventQueue for BinaryHeapEventQueue {
+    fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        self.events.pop()
+    }
+
+    fn peek(&mut self) -> Option<&Event> {
+        self.events.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Event> + '_> {
+        Box::new(self.events.iter())
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+// Calendar queue ------------------------------------------------------------------------------------------------------
+
+const CALENDAR_MIN_BUCKETS: usize = 16;
+const CALENDAR_WIDTH_SAMPLE: usize = 32;
+
+/// Event queue based on calendar queue by R. Brown (1988).
+///
+/// Events are distributed among the array of buckets (days) by their time, each bucket covers a fixed time interval.
+/// The number of buckets and their width are adjusted when the queue size changes significantly.
+/// Provides _O(1)_ amortized insertion and removal when event times are evenly distributed.
+#[derive(Clone)]
+pub struct CalendarEventQueue {
+    // Each bucket is sorted via sort_events.
+    buckets: Vec<Vec<Event>>,
+    width: f64,
+    // Number of the current bucket on the infinite time axis, i.e. floor(time / width).
+    current: u64,
+    len: usize,
+}
+
+impl CalendarEventQueue {
+    /// Creates a new empty queue.
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![Vec::new(); CALENDAR_MIN_BUCKETS],
+            width: 1.,
+            current: 0,
+            len: 0,
+        }
+    }
+
+    fn virtual_bucket(&self, time: f64) -> u64 {
+        (time / self.width).floor() as u64
+    }
+
+    // Finds the bucket containing the next event and moves the current position to it.
+    fn find_next(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let bucket_count = self.buckets.len() as u64;
+        for i in 0..bucket_count {
+            // the position saturates at u64::MAX for infinite or very large times
+            let virtual_bucket = self.current.saturating_add(i);
+            let idx = (virtual_bucket % bucket_count) as usize;
+            if let Some(event) = self.buckets[idx].last() {
+                if self.virtual_bucket(event.time_seconds()) <= virtual_bucket {
+                    self.current = virtual_bucket;
+                    return Some(idx);
+                }
+            }
+        }
+        // no events in the next year, perform direct search
+        let (idx, time) = self
+            .buckets
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, bucket)| bucket.last().map(|e| (idx, e)))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(idx, e)| (idx, e.time_seconds()))
+            .unwrap();
+        self.current = self.virtual_bucket(time);
+        Some(idx)
+    }
+
+    fn resize(&mut self, bucket_count: usize) {
+        let mut events = Vec::with_capacity(self.len);
+        for bucket in self.buckets.iter_mut() {
+            events.append(bucket);
+        }
+        sort_events(&mut events);
+        // estimate bucket width as several average intervals between the next events
+        let sample = events
+            .iter()
+            .rev()
+            .map(|e| e.time_seconds())
+            .filter(|time| time.is_finite())
+            .take(CALENDAR_WIDTH_SAMPLE)
+            .collect::<Vec<_>>();
+        if sample.len() > 1 {
+            let avg_interval = (sample[sample.len() - 1] - sample[0]) / (sample.len() - 1) as f64;
+            if avg_interval > 0. {
+                self.width = 3. * avg_interval;
+            }
+        }
+        self.buckets = vec![Vec::new(); bucket_count];
+        self.current = events.last().map_or(0, |e| self.virtual_bucket(e.time_seconds()));
+        // events are already sorted, so the buckets remain sorted without searching
+        for event in events.into_iter() {
+            let idx = (self.virtual_bucket(event.time_seconds()) % bucket_count as u64) as usize;
+            self.buckets[idx].push(event);
+        }
+    }
+}
+
+impl Default for CalendarEventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventQueue for CalendarEventQueue {
+    fn push(&mut self, event: Event) {
+        let virtual_bucket = self.virtual_bucket(event.time_seconds());
+        // event can precede the current position if it was moved by peek
+        if virtual_bucket < self.current {
+            self.current = virtual_bucket;
+        }
+        let idx = (virtual_bucket % self.buckets.len() as u64) as usize;
+        insert_sorted(&mut self.buckets[idx], event);
+        self.len += 1;
+        if self.len > 2 * self.buckets.len() {
+            self.resize(2 * self.buckets.len());
+        }
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        let idx = self.find_next()?;
+        let event = self.buckets[idx].pop();
+        self.len -= 1;
+        if self.buckets.len() > CALENDAR_MIN_BUCKETS && self.len < self.buckets.len() / 2 {
+            self.resize(self.buckets.len() / 2);
+        }
+        event
+    }
+
+    fn peek(&mut self) -> Option<&Event> {
+        let idx = self.find_next()?;
+        self.buckets[idx].last()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Event> + '_> {
+        Box::new(self.buckets.iter().flatten())
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+// Ladder queue --------------------------------------------------------------------------------------------------------
+
+const LADDER_THRESHOLD: usize = 50;
+const LADDER_MAX_RUNGS: usize = 8;
+
+#[derive(Clone)]
+struct Rung {
+    start: f64,
+    width: f64,
+    // Index of the first bucket which was not yet moved to the lower rung or bottom.
+    current: usize,
+    buckets: Vec<Vec<Event>>,
+}
+
+impl Rung {
+    fn new(start: f64, width: f64, events: Vec<Event>) -> Self {
+        let mut rung = Self {
+            start,
+            width,
+            current: 0,
+            buckets: vec![Vec::new(); events.len()],
+        };
+        for event in events {
+            rung.push(event);
+        }
+        rung
+    }
+
+    // Returns the (possibly negative or out of range) index of bucket corresponding to the time.
+    fn bucket_index(&self, time: f64) -> f64 {
+        ((time - self.start) / self.width).floor()
+    }
+
+    // Returns true if the time belongs to one of the buckets which were not yet moved from the rung.
+    fn accepts(&self, time: f64) -> bool {
+        self.bucket_index(time) >= self.current as f64
+    }
+
+    fn push(&mut self, event: Event) {
+        // clamp index to account for floating-point errors
+        let idx =
+            (self.bucket_index(event.time_seconds()).max(0.) as usize).clamp(self.current, self.buckets.len() - 1);
+        self.buckets[idx].push(event);
+    }
+}
+
+/// Event queue based on ladder queue by W.T. Tang, R.S.M. Goh and I.L.-J. Thng (2005).
+///
+/// New events are put into unsorted top list. When the events are needed, they are distributed among the buckets of
+/// a rung, which are further split into the lower rungs until the bucket is small enough to be sorted and moved into
+/// the bottom list, from which the events are taken. Provides _O(1)_ amortized insertion and removal and,
+/// unlike calendar queue, does not require resizing.
+#[derive(Clone)]
+pub struct LadderEventQueue {
+    top: Vec<Event>,
+    top_min: f64,
+    top_max: f64,
+    // Events with time not less than this value are put into top.
+    top_start: f64,
+    rungs: Vec<Rung>,
+    // Sorted via sort_events.
+    bottom: Vec<Event>,
+    len: usize,
+}
+
+impl LadderEventQueue {
+    /// Creates a new empty queue.
+    pub fn new() -> Self {
+        Self {
+            top: Vec::new(),
+            top_min: f64::INFINITY,
+            top_max: f64::NEG_INFINITY,
+            top_start: f64::NEG_INFINITY,
+            rungs: Vec::new(),
+            bottom: Vec::new(),
+            len: 0,
+        }
+    }
+
+    // Moves the next events into bottom, returns false if the queue is empty.
+    fn fill_bottom(&mut self) -> bool {
+        while self.bottom.is_empty() {
+            // skip empty buckets and remove exhausted rungs
+            while let Some(rung) = self.rungs.last_mut() {
+                while rung.current < rung.buckets.len() && rung.buckets[rung.current].is_empty() {
+                    rung.current += 1;
+                }
+                if rung.current < rung.buckets.len() {
+                    break;
+                }
+                self.rungs.pop();
+            }
+
+            if self.rungs.is_empty() {
+                if self.top.is_empty() {
+                    return false;
+                }
+                let events = std::mem::take(&mut self.top);
+                let width = (self.top_max - self.top_min) / events.len() as f64;
+                self.top_start = self.top_max;
+                if width > 0. {
+                    self.rungs.push(Rung::new(self.top_min, width, events));
+                } else {
+                    self.bottom = events;
+                    sort_events(&mut self.bottom);
+                }
+                self.top_min = f64::INFINITY;
+                self.top_max = f64::NEG_INFINITY;
+                continue;
+            }
+
+            let rung_count = self.rungs.len();
+            let rung = self.rungs.last_mut().unwrap();
+            let bucket_start = rung.start + rung.current as f64 * rung.width;
+            let bucket_width = rung.width;
+            let mut events = std::mem::take(&mut rung.buckets[rung.current]);
+            rung.current += 1;
+            // large buckets are split into a new rung unless all events have the same time
+            if events.len() > LADDER_THRESHOLD
+                && rung_count < LADDER_MAX_RUNGS
+                && events.iter().any(|e| e.time_seconds() != events[0].time_seconds())
+            {
+                let width = bucket_width / events.len() as f64;
+                if width > 0. {
+                    self.rungs.push(Rung::new(bucket_start, width, events));
+                    continue;
+                }
+            }
+            sort_events(&mut events);
+            self.bottom = events;
+        }
+        true
+    }
+}
+
+impl Default for LadderEventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventQueue for LadderEventQueue {
+    fn push(&mut self, event: Event) {
+        self.len += 1;
+        if event.time_seconds() >= self.top_start {
+            self.top_min = self.top_min.min(event.time_seconds());
+            self.top_max = self.top_max.max(event.time_seconds());
+            self.top.push(event);
+            return;
+        }
+        for rung in self.rungs.iter_mut() {
+            if rung.accepts(event.time_seconds()) {
+                rung.push(event);
+                return;
+            }
+        }
+        insert_sorted(&mut self.bottom, event);
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        if !self.fill_bottom() {
+            return None;
+        }
+        self.len -= 1;
+        self.bottom.pop()
+    }
+
+    fn peek(&mut self) -> Option<&Event> {
+        if !self.fill_bottom() {
+            return None;
+        }
+        self.bottom.last()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Event> + '_> {
+        let rungs = self.rungs.iter().flat_map(|r| r.buckets[r.current..].iter().flatten());
+        Box::new(self.top.iter().chain(rungs).chain(self.bottom.iter()))
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -3,13 +3,27 @@
 #![doc = include_str!("../readme.md")]
 
 pub mod async_mode;
+pub mod breakpoint;
+pub mod checkpoint;
 pub mod component;
 pub mod context;
+pub mod distribution;
 pub mod event;
+pub mod event_queue;
 pub mod handler;
 pub mod log;
+pub mod metrics;
+pub mod observer;
+pub mod parallel;
+pub mod periodic;
+pub mod profiling;
+pub mod random;
+pub mod realtime;
+pub mod recording;
 pub mod simulation;
 mod state;
+pub mod time;
+pub mod trace;
 
 pub use colored;
 pub use component::Id;
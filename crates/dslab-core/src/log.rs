@@ -162,7 +162,7 @@ pub fn log_unhandled_event(event: Event) {
     error!(
         target: "simulation",
         "[{:.3} {} simulation] Unhandled event: {}",
-        event.time,
+        event.time_seconds(),
         crate::log::get_colored("ERROR", colored::Color::Red),
         json!({"type": type_name(&event.data).unwrap(), "data": event.data, "src": event.src, "dst": event.dst})
     );
@@ -173,7 +173,7 @@ pub(crate) fn log_undelivered_event(event: Event) {
     error!(
         target: "simulation",
         "[{:.3} {} simulation] Undelivered event: {}",
-        event.time,
+        event.time_seconds(),
         crate::log::get_colored("ERROR", colored::Color::Red),
         json!({"type": type_name(&event.data).unwrap(), "data": event.data, "src": event.src, "dst": event.dst})
     );
@@ -184,7 +184,7 @@ pub(crate) fn log_incorrect_event(event: Event, msg: &str) {
     error!(
         target: "simulation",
         "[{:.3} {} simulation] Incorrect event ({}): {}",
-        event.time,
+        event.time_seconds(),
         crate::log::get_colored("ERROR", colored::Color::Red),
         msg,
         json!({"type": type_name(&event.data).unwrap(), "data": event.data, "src": event.src, "dst": event.dst})
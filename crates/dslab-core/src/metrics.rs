@@ -0,0 +1,524 @@
+//! Collection of metrics reported by simulation components.
+//!
+//! Components report metrics via [`ComponentMetrics`] obtained from
+//! [`SimulationContext::metrics`](crate::SimulationContext::metrics). Each metric is identified by the component name
+//! and the metric name and has one of the following types:
+//!
+//! - _counter_ is a non-decreasing integer value, e.g. the number of processed requests,
+//! - _gauge_ is an arbitrary value that can go up and down, e.g. the current queue length,
+//! - _histogram_ is a distribution of observed values, e.g. request latencies, summarized by the exact count, sum,
+//!   min and max and approximate percentiles,
+//! - _time-weighted_ metric is a piecewise-constant function of simulation time, for which the time-weighted average
+//!   is computed, e.g. resource utilization.
+//!
+//! The current values of all metrics can be obtained at any simulation time via
+//! [`Simulation::metrics_snapshot`](crate::Simulation::metrics_snapshot) and exported to CSV or JSON.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::component::Id;
+use crate::state::SimulationState;
+
+// Metric values stored in simulation state ----------------------------------------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct TimeWeightedMetric {
+    start_time: f64,
+    last_time: f64,
+    value: f64,
+    // Integral of metric value over [start_time, last_time]
+    integral: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TimeWeightedMetric {
+    fn new(time: f64, value: f64) -> Self {
+        Self {
+            start_time: time,
+            last_time: time,
+            value,
+            integral: 0.,
+            min: value,
+            max: value,
+        }
+    }
+
+    fn update(&mut self, time: f64, value: f64) {
+        self.integral += self.value * (time - self.last_time);
+        self.last_time = time;
+        self.value = value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    fn average(&self, time: f64) -> f64 {
+        let duration = time - self.start_time;
+        if duration > 0. {
+            (self.integral + self.value * (time - self.last_time)) / duration
+        } else {
+            self.value
+        }
+    }
+}
+
+// Relative accuracy of histogram percentiles.
+const HISTOGRAM_ACCURACY: f64 = 0.01;
+
+// Histogram which groups observed values into buckets with exponentially growing bounds (as in DDSketch),
+// so that each percentile is estimated with relative error of at most HISTOGRAM_ACCURACY. The number of buckets
+// depends only on the range of observed values, not on their count, e.g. about 2000 buckets cover [1e-9, 1e9].
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct HistogramMetric {
+    count: usize,
+    sum: f64,
+    min: f64,
+    max: f64,
+    zeros: usize,
+    // Bucket with key k contains absolute values from (gamma^(k-1), gamma^k]
+    positive: BTreeMap<i32, usize>,
+    negative: BTreeMap<i32, usize>,
+}
+
+impl HistogramMetric {
+    fn new(value: f64) -> Self {
+        let mut histogram = Self {
+            count: 0,
+            sum: 0.,
+            min: value,
+            max: value,
+            zeros: 0,
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+        };
+        histogram.add(value);
+        histogram
+    }
+
+    fn gamma() -> f64 {
+        (1. + HISTOGRAM_ACCURACY) / (1. - HISTOGRAM_ACCURACY)
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        if value == 0. {
+            self.zeros += 1;
+            return;
+        }
+        let key = (value.abs().ln() / Self::gamma().ln()).ceil() as i32;
+        let buckets = if value > 0. {
+            &mut self.positive
+        } else {
+            &mut self.negative
+        };
+        *buckets.entry(key).or_default() += 1;
+    }
+
+    // Returns the value with the specified rank (starting from 1) approximated by the middle of its bucket.
+    fn value_at_rank(&self, rank: usize) -> f64 {
+        let gamma = Self::gamma();
+        let bucket_value = |key: i32| 2. * gamma.powi(key) / (gamma + 1.);
+        let buckets = self
+            .negative
+            .iter()
+            .rev()
+            .map(|(key, count)| (-bucket_value(*key), *count))
+            .chain(std::iter::once((0., self.zeros)))
+            .chain(self.positive.iter().map(|(key, count)| (bucket_value(*key), *count)));
+        let mut seen = 0;
+        for (value, count) in buckets {
+            seen += count;
+            if seen >= rank {
+                return value.clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    fn summary(&self) -> HistogramSummary {
+        // nearest-rank method
+        let percentile = |p: f64| {
+            let rank = (p / 100. * self.count as f64).ceil() as usize;
+            self.value_at_rank(rank.clamp(1, self.count))
+        };
+        HistogramSummary {
+            count: self.count,
+            sum: self.sum,
+            mean: self.sum / self.count as f64,
+            min: self.min,
+            max: self.max,
+            p50: percentile(50.),
+            p90: percentile(90.),
+            p99: percentile(99.),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) enum Metric {
+    Counter(u64),
+    Gauge(f64),
+    Histogram(HistogramMetric),
+    TimeWeighted(TimeWeightedMetric),
+}
+
+impl Metric {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Metric::Counter(_) => "counter",
+            Metric::Gauge(_) => "gauge",
+            Metric::Histogram(_) => "histogram",
+            Metric::TimeWeighted(_) => "time_weighted",
+        }
+    }
+
+    fn value(&self, time: f64) -> MetricValue {
+        match self {
+            Metric::Counter(value) => MetricValue::Counter { value: *value },
+            Metric::Gauge(value) => MetricValue::Gauge { value: *value },
+            Metric::Histogram(histogram) => MetricValue::Histogram(histogram.summary()),
+            Metric::TimeWeighted(metric) => MetricValue::TimeWeighted {
+                value: metric.value,
+                average: metric.average(time),
+                min: metric.min,
+                max: metric.max,
+            },
+        }
+    }
+}
+
+// Stores metrics of all components indexed by component id and metric name.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct MetricsRegistry {
+    components: Vec<BTreeMap<String, Metric>>,
+}
+
+impl MetricsRegistry {
+    fn update<C, U>(&mut self, component_id: Id, component_name: &str, name: &str, create: C, update: U)
+    where
+        C: FnOnce() -> Metric,
+        U: FnOnce(&mut Metric) -> bool,
+    {
+        let idx = component_id as usize;
+        if self.components.len() <= idx {
+            self.components.resize(idx + 1, BTreeMap::new());
+        }
+        let metrics = &mut self.components[idx];
+        match metrics.get_mut(name) {
+            Some(metric) => {
+                let type_name = metric.type_name();
+                assert!(
+                    update(metric),
+                    "Metric {} of component {} is already registered with type {}",
+                    name,
+                    component_name,
+                    type_name
+                );
+            }
+            None => {
+                metrics.insert(name.to_owned(), create());
+            }
+        }
+    }
+
+    pub fn increment_counter(&mut self, component_id: Id, component_name: &str, name: &str, delta: u64) {
+        self.update(
+            component_id,
+            component_name,
+            name,
+            || Metric::Counter(delta),
+            |metric| match metric {
+                Metric::Counter(value) => {
+                    *value += delta;
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn set_gauge(&mut self, component_id: Id, component_name: &str, name: &str, new_value: f64) {
+        self.update(
+            component_id,
+            component_name,
+            name,
+            || Metric::Gauge(new_value),
+            |metric| match metric {
+                Metric::Gauge(value) => {
+                    *value = new_value;
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn observe(&mut self, component_id: Id, component_name: &str, name: &str, value: f64) {
+        self.update(
+            component_id,
+            component_name,
+            name,
+            || Metric::Histogram(HistogramMetric::new(value)),
+            |metric| match metric {
+                Metric::Histogram(histogram) => {
+                    histogram.add(value);
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn update_time_weighted(&mut self, component_id: Id, component_name: &str, name: &str, time: f64, value: f64) {
+        self.update(
+            component_id,
+            component_name,
+            name,
+            || Metric::TimeWeighted(TimeWeightedMetric::new(time, value)),
+            |metric| match metric {
+                Metric::TimeWeighted(metric) => {
+                    metric.update(time, value);
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn snapshot(&self, time: f64, component_names: &[String]) -> MetricsSnapshot {
+        let mut metrics = Vec::new();
+        for (component_id, component_metrics) in self.components.iter().enumerate() {
+            for (name, metric) in component_metrics.iter() {
+                metrics.push(MetricRecord {
+                    component: component_names[component_id].clone(),
+                    name: name.clone(),
+                    value: metric.value(time),
+                });
+            }
+        }
+        metrics.sort_by(|a, b| a.component.cmp(&b.component).then_with(|| a.name.cmp(&b.name)));
+        MetricsSnapshot { time, metrics }
+    }
+}
+
+// Public API ----------------------------------------------------------------------------------------------------------
+
+/// Provides methods for reporting the metrics of simulation component.
+///
+/// Obtained via [`SimulationContext::metrics`](crate::SimulationContext::metrics).
+/// Each metric has a fixed type determined by the first reported value,
+/// reporting a value of another type for the same metric name results in panic.
+pub struct ComponentMetrics {
+    component_id: Id,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl ComponentMetrics {
+    pub(crate) fn new(component_id: Id, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self {
+            component_id,
+            sim_state,
+        }
+    }
+
+    /// Increments the counter by the specified value.
+    pub fn increment_counter(&self, name: &str, delta: u64) {
+        self.sim_state
+            .borrow_mut()
+            .increment_counter(self.component_id, name, delta);
+    }
+
+    /// Sets the current value of gauge.
+    pub fn set_gauge(&self, name: &str, value: f64) {
+        self.sim_state.borrow_mut().set_gauge(self.component_id, name, value);
+    }
+
+    /// Adds the observed value to histogram.
+    ///
+    /// The values are not stored individually: the histogram memory grows only with the range of observed values,
+    /// and the percentiles reported in [`HistogramSummary`] are approximate.
+    pub fn observe(&self, name: &str, value: f64) {
+        self.sim_state.borrow_mut().observe(self.component_id, name, value);
+    }
+
+    /// Sets the value of time-weighted metric starting from the current simulation time.
+    ///
+    /// The averaging starts from the time of the first update.
+    pub fn update_time_weighted(&self, name: &str, value: f64) {
+        self.sim_state
+            .borrow_mut()
+            .update_time_weighted(self.component_id, name, value);
+    }
+}
+
+/// Summary statistics of histogram metric.
+///
+/// The count, sum, mean, min and max are exact, while the percentiles are estimated with relative error of at most 1%.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistogramSummary {
+    /// Number of observed values.
+    pub count: usize,
+    /// Sum of observed values.
+    pub sum: f64,
+    /// Mean of observed values.
+    pub mean: f64,
+    /// Minimum observed value.
+    pub min: f64,
+    /// Maximum observed value.
+    pub max: f64,
+    /// Median of observed values (approximate).
+    pub p50: f64,
+    /// 90th percentile of observed values (approximate).
+    pub p90: f64,
+    /// 99th percentile of observed values (approximate).
+    pub p99: f64,
+}
+
+/// Value of metric at the snapshot time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricValue {
+    /// Counter value.
+    Counter {
+        /// Current value.
+        value: u64,
+    },
+    /// Gauge value.
+    Gauge {
+        /// Current value.
+        value: f64,
+    },
+    /// Histogram summary.
+    Histogram(HistogramSummary),
+    /// Time-weighted metric value.
+    TimeWeighted {
+        /// Current value.
+        value: f64,
+        /// Time-weighted average from the first update to the snapshot time.
+        average: f64,
+        /// Minimum value.
+        min: f64,
+        /// Maximum value.
+        max: f64,
+    },
+}
+
+impl MetricValue {
+    /// Returns the metric type name.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            MetricValue::Counter { .. } => "counter",
+            MetricValue::Gauge { .. } => "gauge",
+            MetricValue::Histogram(_) => "histogram",
+            MetricValue::TimeWeighted { .. } => "time_weighted",
+        }
+    }
+
+    /// Returns the list of metric statistics as (name, value) pairs.
+    pub fn stats(&self) -> Vec<(&'static str, f64)> {
+        match self {
+            MetricValue::Counter { value } => vec![("value", *value as f64)],
+            MetricValue::Gauge { value } => vec![("value", *value)],
+            MetricValue::Histogram(h) => vec![
+                ("count", h.count as f64),
+                ("sum", h.sum),
+                ("mean", h.mean),
+                ("min", h.min),
+                ("max", h.max),
+                ("p50", h.p50),
+                ("p90", h.p90),
+                ("p99", h.p99),
+            ],
+            MetricValue::TimeWeighted {
+                value,
+                average,
+                min,
+                max,
+            } => vec![("value", *value), ("average", *average), ("min", *min), ("max", *max)],
+        }
+    }
+}
+
+/// Metric of simulation component stored in snapshot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricRecord {
+    /// Component name.
+    pub component: String,
+    /// Metric name.
+    pub name: String,
+    /// Metric value.
+    #[serde(flatten)]
+    pub value: MetricValue,
+}
+
+/// Values of all metrics at some simulation time.
+///
+/// Metrics are sorted by component name and then by metric name.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// Simulation time of the snapshot.
+    pub time: f64,
+    /// Metrics values.
+    pub metrics: Vec<MetricRecord>,
+}
+
+impl MetricsSnapshot {
+    /// Returns the value of the specified metric, if any.
+    pub fn get(&self, component: &str, name: &str) -> Option<&MetricValue> {
+        self.metrics
+            .iter()
+            .find(|m| m.component == component && m.name == name)
+            .map(|m| &m.value)
+    }
+
+    /// Saves snapshot to JSON file.
+    pub fn save_json(&self, path: &str) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| format!("Failed to write metrics: {}", e))?;
+        writer.flush().map_err(|e| format!("Failed to write metrics: {}", e))
+    }
+
+    /// Saves snapshot to CSV file, see [`save_snapshots_csv`] for the format description.
+    pub fn save_csv(&self, path: &str) -> Result<(), String> {
+        save_snapshots_csv(std::slice::from_ref(self), path)
+    }
+}
+
+/// Saves a sequence of snapshots to CSV file.
+///
+/// The file has one row per metric statistic with columns `time,component,metric,type,stat,value`,
+/// e.g. histogram metrics are represented by rows with `count`, `mean`, `p99` and other statistics.
+pub fn save_snapshots_csv(snapshots: &[MetricsSnapshot], path: &str) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+    let map_err = |e: csv::Error| format!("Failed to write metrics: {}", e);
+    writer
+        .write_record(["time", "component", "metric", "type", "stat", "value"])
+        .map_err(map_err)?;
+    for snapshot in snapshots {
+        for metric in snapshot.metrics.iter() {
+            for (stat, value) in metric.value.stats() {
+                writer
+                    .write_record([
+                        &snapshot.time.to_string(),
+                        &metric.component,
+                        &metric.name,
+                        metric.value.type_name(),
+                        stat,
+                        &value.to_string(),
+                    ])
+                    .map_err(map_err)?;
+            }
+        }
+    }
+    writer.flush().map_err(|e| format!("Failed to write metrics: {}", e))
+}
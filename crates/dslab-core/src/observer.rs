@@ -0,0 +1,40 @@
+//! Observing and intercepting event deliveries.
+
+use crate::event::Event;
+
+/// Identifier of registered observer.
+pub type ObserverId = u32;
+
+/// Action performed with the event after it is inspected by [`EventObserver::before_delivery`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObserverAction {
+    /// Deliver the event (possibly modified by observer) to its destination.
+    Deliver,
+    /// Discard the event without delivery.
+    Drop,
+    /// Postpone the event delivery by the specified delay.
+    ///
+    /// The postponed event is re-emitted with a new id, so it is passed to observers again when its new time comes.
+    Delay(f64),
+}
+
+/// Trait for observing the events flowing between simulation components.
+///
+/// Observers are registered via [`Simulation::add_observer`](crate::Simulation::add_observer) and are invoked
+/// for each event retrieved from the event queue in the order of registration. Besides tracing, observers can be used
+/// for fault injection by dropping, delaying or modifying the events before their delivery.
+///
+/// Both methods have default implementations, so it is enough to implement only the required one.
+pub trait EventObserver {
+    /// Called before the event is delivered to its destination.
+    ///
+    /// The observer can modify the event (e.g. its payload or destination) and decide whether the event
+    /// should be delivered, dropped or delayed. If the event is dropped or delayed, the remaining observers are not
+    /// invoked for it.
+    fn before_delivery(&mut self, _event: &mut Event) -> ObserverAction {
+        ObserverAction::Deliver
+    }
+
+    /// Called after the event is processed by its destination.
+    fn after_delivery(&mut self, _event: &Event) {}
+}
@@ -0,0 +1,467 @@
+//! Conservative parallel simulation with multiple partitions.
+//!
+//! Large simulations can be split into several partitions (logical processes), each represented by a separate
+//! [`Simulation`] instance running in its own thread. Components from different partitions communicate by sending
+//! events via [`PartitionLink`]. Such events must be delayed by at least the _lookahead_ value specified for the
+//! whole simulation, e.g. the minimum latency of network links connecting the partitions.
+//!
+//! The partitions are synchronized in windows. At the beginning of each window the partitions agree on the global
+//! minimum time of pending events `t`. Then each partition independently processes its events with time below
+//! `t + lookahead`, which is safe because no remote event can arrive earlier. Finally, the events sent between the
+//! partitions are exchanged and the next window begins.
+//!
+//! The results do not depend on thread scheduling. Remote events keep the exact time computed by the sender and are
+//! inserted into the destination partition in the order of the sequential run, i.e. by time and then by creation
+//! order (send time and event id). The source of remote event is the sending component, which is registered
+//! in the destination partition under its name. So the execution matches the sequential run of the same model with
+//! unique component names, unless a remote event has exactly the same time as some local event at a component.
+//!
+//! All partitions use the same seed and [component random streams](crate::random), so the random draws of components
+//! match those in the sequential run with component random streams enabled, regardless of the partitioning.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::component::Id;
+use crate::context::SimulationContext;
+use crate::event::{EventData, EventId};
+use crate::simulation::Simulation;
+use crate::time::{self, Timestamp};
+
+/// Trait that should be implemented by payload of events sent between partitions.
+pub trait RemoteEventData: EventData + Send {
+    /// Converts the payload into a local event payload.
+    fn into_event_data(self: Box<Self>) -> Box<dyn EventData>;
+}
+
+impl<T: EventData + Send> RemoteEventData for T {
+    fn into_event_data(self: Box<Self>) -> Box<dyn EventData> {
+        self
+    }
+}
+
+/// Address of a component in partitioned simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RemoteId {
+    /// Index of partition (in order of [`ParallelSimulation::add_partition`] calls).
+    pub partition: usize,
+    /// Identifier of component inside the partition.
+    pub id: Id,
+}
+
+impl RemoteId {
+    /// Creates a new component address.
+    pub fn new(partition: usize, id: Id) -> Self {
+        Self { partition, id }
+    }
+}
+
+struct RemoteEvent {
+    id: EventId,
+    time: Timestamp,
+    sent_at: Timestamp,
+    src: RemoteId,
+    src_name: String,
+    dst: Id,
+    data: Box<dyn RemoteEventData>,
+}
+
+/// Allows components to send events to components in other partitions.
+///
+/// Each partition has its own link which is passed to the partition builder
+/// (see [`ParallelSimulation::add_partition`]). The link can be cloned and stored in the partition's components.
+#[derive(Clone)]
+pub struct PartitionLink {
+    partition: usize,
+    lookahead: f64,
+    outbox: Rc<RefCell<Vec<(usize, RemoteEvent)>>>,
+    remote_ids: Rc<RefCell<HashMap<Id, RemoteId>>>,
+}
+
+impl PartitionLink {
+    /// Returns the index of partition this link belongs to.
+    pub fn partition(&self) -> usize {
+        self.partition
+    }
+
+    /// Returns the lookahead value, i.e. the minimum delay of events sent to other partitions.
+    pub fn lookahead(&self) -> f64 {
+        self.lookahead
+    }
+
+    /// Returns the address of component from this partition.
+    pub fn local_id(&self, id: Id) -> RemoteId {
+        RemoteId::new(self.partition, id)
+    }
+
+    /// Returns the address of component known in this partition under the specified identifier.
+    ///
+    /// Besides the partition's own components, these include the components from other partitions which have sent
+    /// events to this partition, so the source of any received event can be resolved to reply to it.
+    pub fn resolve(&self, id: Id) -> RemoteId {
+        self.remote_ids
+            .borrow()
+            .get(&id)
+            .copied()
+            .unwrap_or_else(|| self.local_id(id))
+    }
+
+    /// Creates new event with specified payload, destination and delay on behalf of component with context `ctx`,
+    /// returns the event id.
+    ///
+    /// If the destination belongs to the same partition, the event is emitted as usual.
+    /// Otherwise, the delay should be not less than the lookahead value and the event is delivered to the destination
+    /// partition, where the sending component is registered under its name and used as the event source
+    /// (see [`resolve`](Self::resolve)). Such events cannot be cancelled.
+    pub fn emit<T>(&self, ctx: &SimulationContext, data: T, dst: RemoteId, delay: f64) -> EventId
+    where
+        T: RemoteEventData,
+    {
+        if dst.partition == self.partition {
+            return ctx.emit(data, dst.id, delay);
+        }
+        assert!(
+            delay >= self.lookahead,
+            "Delay of event sent to another partition ({}) is less than lookahead ({})",
+            delay,
+            self.lookahead
+        );
+        let (id, sent_at, time) = ctx.reserve_remote_event(delay);
+        let event = RemoteEvent {
+            id,
+            time,
+            sent_at,
+            src: self.local_id(ctx.id()),
+            src_name: ctx.name().to_owned(),
+            dst: dst.id,
+            data: Box::new(data),
+        };
+        self.outbox.borrow_mut().push((dst.partition, event));
+        id
+    }
+}
+
+/// Statistics and user-defined result of partition execution returned by [`ParallelSimulation::run`].
+pub struct PartitionResult<R> {
+    /// Final simulation time in the partition.
+    pub time: f64,
+    /// Number of steps made in the partition.
+    pub steps: u64,
+    /// Number of events received from other partitions.
+    pub remote_events: u64,
+    /// Number of synchronization windows.
+    pub windows: u64,
+    /// Value returned by the partition result collector.
+    pub result: R,
+}
+
+type ResultCollector<R> = Box<dyn FnOnce(&Simulation) -> R>;
+type PartitionBuilder<R> = Box<dyn FnOnce(&mut Simulation, PartitionLink) -> ResultCollector<R> + Send>;
+
+/// Simulation consisting of several partitions executed in parallel threads.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use serde::Serialize;
+/// use dslab_core::{cast, Event, EventHandler, SimulationContext};
+/// use dslab_core::parallel::{ParallelSimulation, PartitionLink, RemoteId};
+///
+/// #[derive(Clone, Serialize)]
+/// struct Token {
+///     hops: u32,
+/// }
+///
+/// struct Node {
+///     next: RemoteId,
+///     received: u32,
+///     link: PartitionLink,
+///     ctx: SimulationContext,
+/// }
+///
+/// impl EventHandler for Node {
+///     fn on(&mut self, event: Event) {
+///         cast!(match event.data {
+///             Token { hops } => {
+///                 self.received += 1;
+///                 if hops > 0 {
+///                     self.link.emit(&self.ctx, Token { hops: hops - 1 }, self.next, 1.);
+///                 }
+///             }
+///         })
+///     }
+/// }
+///
+/// // two partitions with a single node in each, passing the token to each other
+/// let mut sim = ParallelSimulation::new(123, 1.);
+/// for partition in 0..2 {
+///     sim.add_partition(move |sim, link| {
+///         // component names should be unique across partitions
+///         let name = format!("node{}", partition);
+///         let node = Rc::new(RefCell::new(Node {
+///             next: RemoteId::new(1 - partition, 0),
+///             received: 0,
+///             link,
+///             ctx: sim.create_context(&name),
+///         }));
+///         sim.add_handler(&name, node.clone());
+///         if partition == 0 {
+///             node.borrow().ctx.emit_self(Token { hops: 9 }, 0.);
+///         }
+///         Box::new(move |_| node.borrow().received)
+///     });
+/// }
+///
+/// let results = sim.run();
+/// assert_eq!(results[0].result, 5);
+/// assert_eq!(results[1].result, 5);
+/// assert_eq!(results[0].time, 8.);
+/// assert_eq!(results[1].time, 9.);
+/// ```
+pub struct ParallelSimulation<R> {
+    seed: u64,
+    lookahead: f64,
+    builders: Vec<PartitionBuilder<R>>,
+}
+
+impl<R: Send> ParallelSimulation<R> {
+    /// Creates a new parallel simulation with specified random seed and lookahead value.
+    ///
+    /// All partitions use the same seed with [component random streams](Simulation::enable_component_rand_streams)
+    /// enabled, so the results of a model using random numbers do not depend on the partitioning. Note that the
+    /// simulation-wide generators of the partitions produce the same sequences, so they should not be used by
+    /// components.
+    pub fn new(seed: u64, lookahead: f64) -> Self {
+        assert!(lookahead > 0., "Lookahead must be positive");
+        Self {
+            seed,
+            lookahead,
+            builders: Vec::new(),
+        }
+    }
+
+    /// Adds a new partition, returns its index.
+    ///
+    /// The partition is created in its own thread by calling `builder`, which should create the partition's
+    /// components in the passed simulation. The builder returns a collector function, which is called after
+    /// the simulation run to obtain the partition result.
+    pub fn add_partition<F>(&mut self, builder: F) -> usize
+    where
+        F: FnOnce(&mut Simulation, PartitionLink) -> ResultCollector<R> + Send + 'static,
+    {
+        self.builders.push(Box::new(builder));
+        self.builders.len() - 1
+    }
+
+    /// Runs the simulation until there are no pending events in all partitions.
+    pub fn run(self) -> Vec<PartitionResult<R>> {
+        self.run_until(f64::INFINITY)
+    }
+
+    /// Runs the simulation until the specified time.
+    ///
+    /// Similar to [`Simulation::step_until_time`], the simulation time in all partitions is advanced to `time`
+    /// if it is finite.
+    pub fn run_until(self, time: f64) -> Vec<PartitionResult<R>> {
+        let partition_count = self.builders.len();
+        let sync = Synchronizer::new(partition_count);
+        let results = std::thread::scope(|scope| {
+            let handles = self
+                .builders
+                .into_iter()
+                .enumerate()
+                .map(|(partition, builder)| {
+                    let sync = &sync;
+                    let seed = self.seed;
+                    let lookahead = self.lookahead;
+                    scope.spawn(move || {
+                        let _guard = AbortOnPanic(sync);
+                        run_partition(partition, seed, lookahead, time, builder, sync)
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|h| h.join()).collect::<Vec<_>>()
+        });
+        // propagate the original panic instead of the ones caused by aborting other partitions
+        let mut aborted = None;
+        let mut partition_results = Vec::new();
+        for result in results {
+            match result {
+                Ok(result) => partition_results.push(result),
+                Err(payload) if payload.is::<PartitionAborted>() => aborted = Some(payload),
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        }
+        if let Some(payload) = aborted {
+            std::panic::resume_unwind(payload);
+        }
+        partition_results
+    }
+}
+
+// Panic payload used to stop partitions when some other partition panics.
+struct PartitionAborted;
+
+struct SyncState {
+    waiting: usize,
+    generation: u64,
+    aborted: bool,
+    next_times: Vec<f64>,
+}
+
+// Synchronizes partition threads. Unlike std::sync::Barrier, it releases the waiting threads
+// if some partition panics, so that the simulation is not blocked forever.
+struct Synchronizer {
+    partition_count: usize,
+    state: Mutex<SyncState>,
+    condvar: Condvar,
+    mailboxes: Vec<Mutex<Vec<RemoteEvent>>>,
+}
+
+impl Synchronizer {
+    fn new(partition_count: usize) -> Self {
+        Self {
+            partition_count,
+            state: Mutex::new(SyncState {
+                waiting: 0,
+                generation: 0,
+                aborted: false,
+                next_times: vec![f64::INFINITY; partition_count],
+            }),
+            condvar: Condvar::new(),
+            mailboxes: (0..partition_count).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, SyncState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn mailbox(&self, partition: usize) -> MutexGuard<'_, Vec<RemoteEvent>> {
+        self.mailboxes[partition].lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wait(&self) {
+        let mut state = self.state();
+        let generation = state.generation;
+        state.waiting += 1;
+        if state.waiting == self.partition_count {
+            state.waiting = 0;
+            state.generation += 1;
+            self.condvar.notify_all();
+        } else {
+            while state.generation == generation && !state.aborted {
+                state = self.condvar.wait(state).unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+        let aborted = state.aborted;
+        drop(state);
+        if aborted {
+            std::panic::panic_any(PartitionAborted);
+        }
+    }
+
+    fn abort(&self) {
+        self.state().aborted = true;
+        self.condvar.notify_all();
+    }
+}
+
+struct AbortOnPanic<'a>(&'a Synchronizer);
+
+impl Drop for AbortOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.abort();
+        }
+    }
+}
+
+fn run_partition<R>(
+    partition: usize,
+    seed: u64,
+    lookahead: f64,
+    until: f64,
+    builder: PartitionBuilder<R>,
+    sync: &Synchronizer,
+) -> PartitionResult<R> {
+    let mut sim = Simulation::new(seed);
+    sim.enable_component_rand_streams();
+    let outbox = Rc::new(RefCell::new(Vec::new()));
+    let remote_ids = Rc::new(RefCell::new(HashMap::new()));
+    let link = PartitionLink {
+        partition,
+        lookahead,
+        outbox: outbox.clone(),
+        remote_ids: remote_ids.clone(),
+    };
+    let collector = builder(&mut sim, link);
+    let mut local_ids = HashMap::new();
+
+    let mut steps = 0;
+    let mut remote_events = 0;
+    let mut windows = 0;
+    loop {
+        // agree on the global minimum time of pending events
+        let next_time = sim.next_event_time().unwrap_or(f64::INFINITY);
+        sync.state().next_times[partition] = next_time;
+        sync.wait();
+        let min_time = sync.state().next_times.iter().cloned().fold(f64::INFINITY, f64::min);
+        if min_time == f64::INFINITY || min_time > until {
+            break;
+        }
+        windows += 1;
+
+        // process local events which cannot be affected by remote events
+        let window_end = min_time + lookahead;
+        while let Some(time) = sim.next_event_time() {
+            if time >= window_end || time > until {
+                break;
+            }
+            sim.step();
+            steps += 1;
+        }
+        for (dst_partition, event) in outbox.borrow_mut().drain(..) {
+            sync.mailbox(dst_partition).push(event);
+        }
+        sync.wait();
+
+        // receive remote events in the order of sequential run, the partition index is used only to break ties
+        let mut incoming = std::mem::take(&mut *sync.mailbox(partition));
+        incoming.sort_by(|a, b| {
+            time::compare(a.time, b.time)
+                .then(time::compare(a.sent_at, b.sent_at))
+                .then(a.id.cmp(&b.id))
+                .then(a.src.partition.cmp(&b.src.partition))
+        });
+        remote_events += incoming.len() as u64;
+        for event in incoming {
+            let src = *local_ids.entry(event.src).or_insert_with(|| {
+                let id = sim.register_remote(&event.src_name).unwrap_or_else(|| {
+                    panic!(
+                        "Component name {} is used in several partitions, component names should be unique",
+                        event.src_name
+                    )
+                });
+                remote_ids.borrow_mut().insert(id, event.src);
+                id
+            });
+            sim.add_event_at(event.data.into_event_data(), src, event.dst, event.time);
+        }
+    }
+    if until.is_finite() {
+        sim.step_until_time(until);
+    }
+
+    PartitionResult {
+        time: sim.time(),
+        steps,
+        remote_events,
+        windows,
+        result: collector(&sim),
+    }
+}
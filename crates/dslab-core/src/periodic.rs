@@ -0,0 +1,97 @@
+//! Periodic events.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::event::{Event, EventId};
+use crate::state::SimulationState;
+
+/// Identifier of periodic event series.
+pub type PeriodicEventId = u64;
+
+// Series of periodic events stored in simulation state.
+#[derive(Clone)]
+pub(crate) struct PeriodicSeries {
+    // The last scheduled occurrence, which is pending if the series is not paused.
+    // Is used as a template for the next occurrence.
+    pub last_event: Event,
+    pub period: f64,
+    pub paused: bool,
+}
+
+/// Handle for controlling the series of periodic events created via
+/// [`SimulationContext::emit_periodic`](crate::SimulationContext::emit_periodic).
+///
+/// The series is stored in the simulation state, so dropping the handle does not cancel it.
+///
+/// The series is also stopped when its pending occurrence is cancelled, e.g. by
+/// [`cancel_events`](crate::SimulationContext::cancel_events), or when the handler of its destination is removed
+/// (the pending occurrence is then kept or cancelled according to
+/// [`EventCancellationPolicy`](crate::EventCancellationPolicy)).
+#[derive(Clone)]
+pub struct PeriodicEventHandle {
+    id: PeriodicEventId,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl PeriodicEventHandle {
+    pub(crate) fn new(id: PeriodicEventId, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self { id, sim_state }
+    }
+
+    /// Returns the identifier of the series.
+    pub fn id(&self) -> PeriodicEventId {
+        self.id
+    }
+
+    /// Returns `true` if the series is not cancelled (it can be paused though).
+    pub fn is_active(&self) -> bool {
+        self.sim_state.borrow_mut().is_periodic_event_active(self.id)
+    }
+
+    /// Returns `true` if the series is paused.
+    pub fn is_paused(&self) -> bool {
+        self.sim_state.borrow_mut().is_periodic_event_paused(self.id)
+    }
+
+    /// Returns the id of the pending occurrence, if any.
+    pub fn next_event_id(&self) -> Option<EventId> {
+        self.sim_state.borrow_mut().periodic_event_next_id(self.id)
+    }
+
+    /// Returns the current period of the series.
+    ///
+    /// Panics if the series is cancelled.
+    pub fn period(&self) -> f64 {
+        self.sim_state.borrow_mut().periodic_event_period(self.id)
+    }
+
+    /// Changes the period of the series.
+    ///
+    /// The already scheduled occurrence is not affected, the new period is used for the following ones.
+    /// Panics if the series is cancelled.
+    pub fn set_period(&self, period: f64) {
+        self.sim_state.borrow_mut().set_periodic_event_period(self.id, period);
+    }
+
+    /// Pauses the series by cancelling its pending occurrence.
+    ///
+    /// Does nothing if the series is already paused. Panics if the series is cancelled.
+    pub fn pause(&self) {
+        self.sim_state.borrow_mut().pause_periodic_event(self.id);
+    }
+
+    /// Resumes the paused series, the next occurrence is scheduled after one period from the current time.
+    ///
+    /// Does nothing if the series is not paused. Panics if the series is cancelled.
+    pub fn resume(&self) {
+        self.sim_state.borrow_mut().resume_periodic_event(self.id);
+    }
+
+    /// Cancels the series and its pending occurrence.
+    ///
+    /// Does nothing if the series is already cancelled.
+    pub fn cancel(&self) {
+        self.sim_state.borrow_mut().cancel_periodic_event(self.id);
+    }
+}
@@ -0,0 +1,239 @@
+//! Profiling of event processing cost.
+//!
+//! When profiling is enabled via [`Simulation::enable_profiling`](crate::Simulation::enable_profiling),
+//! the simulation measures the wall time spent in processing of events by each component, grouped by event type.
+//! For synchronous handlers this is the time spent in [`EventHandler::on`](crate::EventHandler::on).
+//! In async mode, the time of polling the task which receives the event via `recv_event` is also attributed to the
+//! event, while the time of other polls (e.g. after timers) is reported separately for the component which spawned
+//! the task. The simulation also samples the size of event queue over simulation time.
+//!
+//! The collected data is returned as [`ProfilingReport`] by
+//! [`Simulation::profiling_report`](crate::Simulation::profiling_report). The report lists the components sorted by
+//! the total processing time, which allows to find the models dominating the simulation run time.
+
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+
+use crate::async_mode_enabled;
+use crate::component::Id;
+
+/// Processing statistics of events of a single type.
+#[derive(Clone, Debug, Serialize)]
+pub struct EventTypeProfile {
+    /// Name of event type.
+    pub event_type: String,
+    /// Number of delivered events.
+    pub event_count: u64,
+    /// Total wall time spent in processing of the events, in seconds.
+    pub wall_time: f64,
+}
+
+/// Processing statistics of a single component.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComponentProfile {
+    /// Component identifier (None for tasks spawned via [`Simulation::spawn`](crate::Simulation::spawn)).
+    pub component_id: Option<Id>,
+    /// Component name (`simulation` for tasks spawned via [`Simulation::spawn`](crate::Simulation::spawn)).
+    pub component_name: String,
+    /// Total number of delivered events.
+    pub event_count: u64,
+    /// Total wall time spent in processing of events and polling of async tasks, in seconds.
+    pub wall_time: f64,
+    /// Number of async task polls not caused by event delivery.
+    pub poll_count: u64,
+    /// Wall time spent in async task polls not caused by event delivery, in seconds.
+    pub poll_time: f64,
+    /// Statistics for each event type, sorted by wall time in descending order.
+    pub event_types: Vec<EventTypeProfile>,
+}
+
+/// Report with event processing statistics collected during the simulation.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProfilingReport {
+    /// Statistics of components, sorted by wall time in descending order.
+    pub components: Vec<ComponentProfile>,
+    /// Total number of delivered events.
+    pub event_count: u64,
+    /// Total wall time spent in processing of events and polling of async tasks, in seconds.
+    pub wall_time: f64,
+    /// Maximum observed size of event queue.
+    pub max_queue_size: usize,
+    /// Samples of event queue size as (simulation time, queue size) pairs.
+    pub queue_size: Vec<(f64, usize)>,
+}
+
+impl ProfilingReport {
+    /// Returns the statistics of component with specified name.
+    pub fn component(&self, name: &str) -> Option<&ComponentProfile> {
+        self.components.iter().find(|c| c.component_name == name)
+    }
+}
+
+impl Display for ProfilingReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Processed {} events in {:.3} s, max queue size {}",
+            self.event_count, self.wall_time, self.max_queue_size
+        )?;
+        writeln!(
+            f,
+            "{:<40} {:>12} {:>12} {:>8} {:>14}",
+            "component / event type", "events", "time, s", "share", "per event, us"
+        )?;
+        for component in self.components.iter() {
+            write_row(
+                f,
+                &component.component_name,
+                component.event_count,
+                component.wall_time,
+                self.wall_time,
+            )?;
+            for event_type in component.event_types.iter() {
+                write_row(
+                    f,
+                    &format!("  {}", event_type.event_type),
+                    event_type.event_count,
+                    event_type.wall_time,
+                    self.wall_time,
+                )?;
+            }
+            if component.poll_count > 0 {
+                write_row(
+                    f,
+                    "  <async polls>",
+                    component.poll_count,
+                    component.poll_time,
+                    self.wall_time,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_row(f: &mut Formatter<'_>, name: &str, count: u64, time: f64, total_time: f64) -> std::fmt::Result {
+    let share = if total_time > 0. { time / total_time * 100. } else { 0. };
+    let per_event = if count > 0 { time / count as f64 * 1e6 } else { 0. };
+    writeln!(
+        f,
+        "{:<40} {:>12} {:>12.6} {:>7.2}% {:>14.3}",
+        name, count, time, share, per_event
+    )
+}
+
+#[derive(Default)]
+struct Counter {
+    count: u64,
+    time: Duration,
+}
+
+impl Counter {
+    fn add(&mut self, time: Duration) {
+        self.count += 1;
+        self.time += time;
+    }
+}
+
+pub(crate) struct Profiler {
+    // Event type names are stored as static strings to avoid allocations on each event.
+    events: FxHashMap<(Id, &'static str), Counter>,
+    polls: FxHashMap<Option<Id>, Counter>,
+    queue_sample_interval: f64,
+    next_queue_sample: f64,
+    queue_size: Vec<(f64, usize)>,
+    max_queue_size: usize,
+}
+
+impl Profiler {
+    pub fn new(queue_sample_interval: f64, time: f64) -> Self {
+        Self {
+            events: FxHashMap::default(),
+            polls: FxHashMap::default(),
+            queue_sample_interval,
+            next_queue_sample: time,
+            queue_size: Vec::new(),
+            max_queue_size: 0,
+        }
+    }
+
+    pub fn record_event(&mut self, component_id: Id, event_type: &'static str, time: Duration) {
+        self.events.entry((component_id, event_type)).or_default().add(time);
+    }
+
+    async_mode_enabled!(
+        pub fn record_poll(&mut self, component_id: Option<Id>, time: Duration) {
+            self.polls.entry(component_id).or_default().add(time);
+        }
+    );
+
+    // Samples the queue size if the sampling interval has passed since the previous sample.
+    pub fn observe_queue_size(&mut self, time: f64, queue_size: usize) {
+        self.max_queue_size = self.max_queue_size.max(queue_size);
+        if time >= self.next_queue_sample {
+            self.queue_size.push((time, queue_size));
+            let intervals = ((time - self.next_queue_sample) / self.queue_sample_interval).floor() + 1.;
+            self.next_queue_sample += intervals * self.queue_sample_interval;
+        }
+    }
+
+    pub fn report(&self, component_names: &[String]) -> ProfilingReport {
+        let mut components: FxHashMap<Option<Id>, ComponentProfile> = FxHashMap::default();
+        for ((id, event_type), counter) in self.events.iter() {
+            let component = component_profile(&mut components, Some(*id), component_names);
+            component.event_count += counter.count;
+            component.wall_time += counter.time.as_secs_f64();
+            component.event_types.push(EventTypeProfile {
+                event_type: event_type.to_string(),
+                event_count: counter.count,
+                wall_time: counter.time.as_secs_f64(),
+            });
+        }
+        for (id, counter) in self.polls.iter() {
+            let component = component_profile(&mut components, *id, component_names);
+            component.poll_count += counter.count;
+            component.poll_time += counter.time.as_secs_f64();
+            component.wall_time += counter.time.as_secs_f64();
+        }
+
+        let mut components: Vec<ComponentProfile> = components.into_values().collect();
+        for component in components.iter_mut() {
+            component.event_types.sort_by(|a, b| {
+                b.wall_time
+                    .total_cmp(&a.wall_time)
+                    .then_with(|| a.event_type.cmp(&b.event_type))
+            });
+        }
+        components.sort_by(|a, b| {
+            b.wall_time
+                .total_cmp(&a.wall_time)
+                .then_with(|| a.component_id.cmp(&b.component_id))
+        });
+        ProfilingReport {
+            event_count: components.iter().map(|c| c.event_count).sum(),
+            wall_time: components.iter().map(|c| c.wall_time).sum(),
+            components,
+            max_queue_size: self.max_queue_size,
+            queue_size: self.queue_size.clone(),
+        }
+    }
+}
+
+fn component_profile<'a>(
+    components: &'a mut FxHashMap<Option<Id>, ComponentProfile>,
+    id: Option<Id>,
+    component_names: &[String],
+) -> &'a mut ComponentProfile {
+    components.entry(id).or_insert_with(|| ComponentProfile {
+        component_id: id,
+        component_name: id.map_or("simulation".to_string(), |id| component_names[id as usize].clone()),
+        event_count: 0,
+        wall_time: 0.,
+        poll_count: 0,
+        poll_time: 0.,
+        event_types: Vec::new(),
+    })
+}
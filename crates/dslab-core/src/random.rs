@@ -0,0 +1,110 @@
+//! Random number streams.
+//!
+//! By default, all components draw random numbers from the single simulation-wide generator initialized with
+//! the simulation seed. Thus adding a component or an extra random draw in one component changes the random values
+//! observed by all other components. This complicates the comparison of alternative configurations using common
+//! random numbers.
+//!
+//! To avoid this, each component can use its own random stream derived from the simulation seed and the component
+//! name. Such streams are enabled via
+//! [`Simulation::enable_component_rand_streams`](crate::Simulation::enable_component_rand_streams) and are used by
+//! the random methods of [`SimulationContext`](crate::SimulationContext). In addition, components can request named
+//! sub-streams via [`SimulationContext::random_stream`](crate::SimulationContext::random_stream), e.g. to separate
+//! the randomness of arrivals and service times.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rand::distributions::uniform::{SampleRange, SampleUniform};
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+
+use crate::component::Id;
+use crate::state::SimulationState;
+
+// Identifies the random number generator used for sampling.
+#[derive(Clone, Copy)]
+pub(crate) enum RandStream {
+    // Simulation-wide generator.
+    Global,
+    // Generator of component (equals to global one if component streams are disabled).
+    Component(Id),
+    // Named sub-stream with the specified index.
+    Named(usize),
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+// Computes 64-bit FNV-1a hash of the seed followed by the key parts.
+fn fnv1a(seed: u64, parts: &[&str]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |byte: u8| {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    };
+    for byte in seed.to_le_bytes() {
+        write(byte);
+    }
+    for part in parts {
+        // separator which does not appear in UTF-8 strings
+        write(0xff);
+        for byte in part.bytes() {
+            write(byte);
+        }
+    }
+    hash
+}
+
+// Creates random number generator for the stream identified by key parts.
+pub(crate) fn derive_rng(seed: u64, key: &[&str]) -> Pcg64 {
+    Pcg64::seed_from_u64(fnv1a(seed, key))
+}
+
+/// Named random stream of simulation component created via
+/// [`SimulationContext::random_stream`](crate::SimulationContext::random_stream).
+///
+/// The stream is derived from the simulation seed, the component name and the stream name, so its values do not
+/// depend on other random draws in the simulation. The stream state is stored in the simulation, so all handles
+/// for the same stream share it, and it is included in simulation checkpoints.
+#[derive(Clone)]
+pub struct RandomStream {
+    index: usize,
+    sim_state: Rc<RefCell<SimulationState>>,
+}
+
+impl RandomStream {
+    pub(crate) fn new(index: usize, sim_state: Rc<RefCell<SimulationState>>) -> Self {
+        Self { index, sim_state }
+    }
+
+    /// Returns a random float in the range _[0, 1)_.
+    pub fn rand(&self) -> f64 {
+        self.sim_state.borrow_mut().rand(RandStream::Named(self.index))
+    }
+
+    /// Returns a random number in the specified range.
+    pub fn gen_range<T, R>(&self, range: R) -> T
+    where
+        T: SampleUniform,
+        R: SampleRange<T>,
+    {
+        self.sim_state
+            .borrow_mut()
+            .gen_range(RandStream::Named(self.index), range)
+    }
+
+    /// Returns a random value from the specified distribution.
+    pub fn sample_from_distribution<T, Dist: Distribution<T>>(&self, dist: &Dist) -> T {
+        self.sim_state
+            .borrow_mut()
+            .sample_from_distribution(RandStream::Named(self.index), dist)
+    }
+
+    /// Returns a random alphanumeric string of specified length.
+    pub fn random_string(&self, len: usize) -> String {
+        self.sim_state
+            .borrow_mut()
+            .random_string(RandStream::Named(self.index), len)
+    }
+}
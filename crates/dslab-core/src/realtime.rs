@@ -0,0 +1,86 @@
+//! Execution of simulation paced by the wall-clock time.
+//!
+//! By default, the simulation runs as fast as possible. In the real-time mode enabled via
+//! [`Simulation::enable_realtime_mode`](crate::Simulation::enable_realtime_mode), the processing of each event is
+//! delayed until the wall-clock time corresponding to the event time, so the simulation time advances
+//! synchronously with the wall-clock time multiplied by the specified speed factor.
+//!
+//! In addition, events can be injected into the running simulation from other threads via
+//! [`ExternalEventSender`]. Such events are delivered at the simulation time corresponding to the moment of their
+//! receipt (in the real-time mode) or at the current simulation time (otherwise).
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use crate::component::Id;
+use crate::event::EventData;
+
+// Maps the simulation time to the wall-clock time and vice versa.
+pub(crate) struct RealTimeClock {
+    // Simulation time units per second of wall-clock time.
+    speed: f64,
+    sim_start: f64,
+    wall_start: Instant,
+}
+
+impl RealTimeClock {
+    pub fn new(speed: f64, sim_start: f64) -> Self {
+        assert!(speed > 0., "Real-time speed factor must be positive");
+        Self {
+            speed,
+            sim_start,
+            wall_start: Instant::now(),
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn wall_time(&self, sim_time: f64) -> Instant {
+        self.wall_start + Duration::from_secs_f64(((sim_time - self.sim_start) / self.speed).max(0.))
+    }
+
+    pub fn sim_time(&self, wall_time: Instant) -> f64 {
+        self.sim_start + wall_time.duration_since(self.wall_start).as_secs_f64() * self.speed
+    }
+}
+
+pub(crate) struct ExternalEvent {
+    pub data: Box<dyn EventData + Send>,
+    pub src: Id,
+    pub dst: Id,
+}
+
+pub(crate) type ExternalEventChannel = (Sender<ExternalEvent>, Receiver<ExternalEvent>);
+
+/// Thread-safe handle for injecting events into the simulation from outside,
+/// obtained via [`Simulation::external_event_sender`](crate::Simulation::external_event_sender).
+///
+/// The injected events are received by the simulation when it performs the next step or while it waits for
+/// the next event in the real-time mode.
+#[derive(Clone)]
+pub struct ExternalEventSender {
+    sender: Sender<ExternalEvent>,
+}
+
+impl ExternalEventSender {
+    pub(crate) fn new(sender: Sender<ExternalEvent>) -> Self {
+        Self { sender }
+    }
+
+    /// Sends the event with specified payload, source and destination to the simulation.
+    ///
+    /// Returns an error if the simulation is dropped.
+    pub fn send<T>(&self, data: T, src: Id, dst: Id) -> Result<(), String>
+    where
+        T: EventData + Send,
+    {
+        let event = ExternalEvent {
+            data: Box::new(data),
+            src,
+            dst,
+        };
+        self.sender.send(event).map_err(|_| "Simulation is dropped".to_string())
+    }
+}
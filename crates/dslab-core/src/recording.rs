@@ -0,0 +1,255 @@
+//! Recording of processed events and verification of simulation runs against recorded traces.
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_type_name::type_name;
+
+use crate::component::Id;
+use crate::event::{Event, EventId};
+
+/// Information about the processed event stored in event trace.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    /// Event identifier.
+    pub id: EventId,
+    /// Time of event occurrence.
+    pub time: f64,
+    /// Identifier of event source.
+    pub src: Id,
+    /// Identifier of event destination.
+    pub dst: Id,
+    /// Name of event payload type.
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Event payload serialized to JSON.
+    pub data: Value,
+}
+
+impl EventRecord {
+    /// Creates event record from the event.
+    pub fn from_event(event: &Event) -> Self {
+        Self {
+            id: event.id,
+            time: event.time_seconds(),
+            src: event.src,
+            dst: event.dst,
+            type_name: type_name(&event.data).unwrap_or("unknown").to_string(),
+            data: serde_json::to_value(&event.data).unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl Display for EventRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{id: {}, time: {}, src: {}, dst: {}, type: {}, data: {}}}",
+            self.id, self.time, self.src, self.dst, self.type_name, self.data
+        )
+    }
+}
+
+/// Trait for consuming the stream of processed events.
+///
+/// Event recorder is set via [`Simulation::set_event_recorder`](crate::Simulation::set_event_recorder)
+/// and is invoked for each event retrieved from the event queue before its delivery to the destination component.
+pub trait EventRecorder {
+    /// Records the processed event.
+    fn record(&mut self, record: EventRecord);
+}
+
+/// Writes processed events to a file in JSON Lines format (one record per line).
+pub struct FileEventRecorder {
+    writer: BufWriter<File>,
+}
+
+impl FileEventRecorder {
+    /// Creates recorder which writes events to the specified file.
+    pub fn new(path: &str) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Flushes the buffered records to the file.
+    ///
+    /// The records are also flushed when the recorder is dropped.
+    pub fn flush(&mut self) {
+        self.writer.flush().expect("Failed to flush event trace");
+    }
+}
+
+impl EventRecorder for FileEventRecorder {
+    fn record(&mut self, record: EventRecord) {
+        serde_json::to_writer(&mut self.writer, &record).expect("Failed to write event trace");
+        self.writer.write_all(b"\n").expect("Failed to write event trace");
+    }
+}
+
+/// Reads the event trace written by [`FileEventRecorder`].
+pub fn read_event_trace(path: &str) -> Result<Vec<EventRecord>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file {}: {}", path, e))?;
+    let mut records = Vec::new();
+    for (line_num, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line)
+            .map_err(|e| format!("Failed to parse record at {}:{}: {}", path, line_num + 1, e))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Describes the first mismatch between the recorded and the actual event stream.
+#[derive(Clone, Debug)]
+pub struct TraceDivergence {
+    /// Position of the mismatched event in the stream (starting from 0).
+    pub position: usize,
+    /// Recorded event (None if the actual run produced more events than recorded).
+    pub expected: Option<EventRecord>,
+    /// Actual event (None if the actual run produced less events than recorded).
+    pub actual: Option<EventRecord>,
+}
+
+impl Display for TraceDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let fmt_record = |record: &Option<EventRecord>| match record {
+            Some(record) => record.to_string(),
+            None => "none".to_string(),
+        };
+        write!(
+            f,
+            "event stream diverged at position {}: expected {}, actual {}",
+            self.position,
+            fmt_record(&self.expected),
+            fmt_record(&self.actual)
+        )
+    }
+}
+
+/// Event recorder which compares the processed events with the previously recorded trace.
+///
+/// Verification stops at the first divergence, which can be obtained via [`divergence`](Self::divergence).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use serde::Serialize;
+/// use dslab_core::Simulation;
+/// use dslab_core::recording::{EventRecord, EventRecorder, TraceVerifier};
+///
+/// #[derive(Clone, Serialize)]
+/// struct Message {
+///     value: u32,
+/// }
+///
+/// #[derive(Default)]
+/// struct MemoryRecorder {
+///     records: Vec<EventRecord>,
+/// }
+///
+/// impl EventRecorder for MemoryRecorder {
+///     fn record(&mut self, record: EventRecord) {
+///         self.records.push(record);
+///     }
+/// }
+///
+/// fn run(sim: &mut Simulation, last_value: u32) {
+///     let ctx = sim.create_context("comp");
+///     for value in [1, 2, last_value] {
+///         ctx.emit_self(Message { value }, value as f64);
+///     }
+///     sim.step_until_no_events();
+/// }
+///
+/// let mut sim = Simulation::new(123);
+/// let recorder = Rc::new(RefCell::new(MemoryRecorder::default()));
+/// sim.set_event_recorder(recorder.clone());
+/// run(&mut sim, 3);
+///
+/// let mut sim = Simulation::new(123);
+/// let verifier = Rc::new(RefCell::new(TraceVerifier::new(recorder.borrow().records.clone())));
+/// sim.set_event_recorder(verifier.clone());
+/// run(&mut sim, 4);
+///
+/// let verifier = verifier.borrow();
+/// let divergence = verifier.divergence().unwrap();
+/// assert_eq!(divergence.position, 2);
+/// assert_eq!(divergence.expected.as_ref().unwrap().data["value"], 3);
+/// assert_eq!(divergence.actual.as_ref().unwrap().data["value"], 4);
+/// ```
+pub struct TraceVerifier {
+    expected: Vec<EventRecord>,
+    position: usize,
+    divergence: Option<TraceDivergence>,
+}
+
+impl TraceVerifier {
+    /// Creates verifier from the recorded events.
+    pub fn new(expected: Vec<EventRecord>) -> Self {
+        Self {
+            expected,
+            position: 0,
+            divergence: None,
+        }
+    }
+
+    /// Creates verifier from the trace file written by [`FileEventRecorder`].
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        Ok(Self::new(read_event_trace(path)?))
+    }
+
+    /// Returns the first divergence between recorded and actual events, if any.
+    ///
+    /// Note that if the actual run is shorter than the recorded one, this is detected only by
+    /// [`finish`](Self::finish).
+    pub fn divergence(&self) -> Option<&TraceDivergence> {
+        self.divergence.as_ref()
+    }
+
+    /// Returns the number of events that matched the recorded trace.
+    pub fn verified_count(&self) -> usize {
+        self.position
+    }
+
+    /// Checks that all recorded events were processed and returns the first divergence, if any.
+    ///
+    /// Should be called after the simulation run is completed.
+    pub fn finish(&mut self) -> Option<&TraceDivergence> {
+        if self.divergence.is_none() && self.position < self.expected.len() {
+            self.divergence = Some(TraceDivergence {
+                position: self.position,
+                expected: Some(self.expected[self.position].clone()),
+                actual: None,
+            });
+        }
+        self.divergence.as_ref()
+    }
+}
+
+impl EventRecorder for TraceVerifier {
+    fn record(&mut self, record: EventRecord) {
+        if self.divergence.is_some() {
+            return;
+        }
+        let expected = self.expected.get(self.position);
+        if expected != Some(&record) {
+            self.divergence = Some(TraceDivergence {
+                position: self.position,
+                expected: expected.cloned(),
+                actual: Some(record),
+            });
+            return;
+        }
+        self.position += 1;
+    }
+}
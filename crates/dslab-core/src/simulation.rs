@@ -1,29 +1,47 @@
 //! Simulation configuration and execution.
 
 use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::Instant;
 
 use log::Level::Trace;
 use log::{debug, log_enabled, trace};
 use rand::distributions::uniform::{SampleRange, SampleUniform};
 use rand::prelude::Distribution;
-use serde_json::json;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
 use serde_type_name::type_name;
 
+use crate::breakpoint::{BreakpointFn, BreakpointHit, BreakpointId};
+use crate::checkpoint::{Checkpointable, EventTypeRegistry, SimulationCheckpoint};
 use crate::component::Id;
 use crate::context::SimulationContext;
+use crate::event_queue::{BinaryHeapEventQueue, EventQueue};
 use crate::handler::{EventCancellationPolicy, EventHandler};
 use crate::log::log_undelivered_event;
+use crate::metrics::MetricsSnapshot;
+use crate::observer::{EventObserver, ObserverAction, ObserverId};
+use crate::profiling::{Profiler, ProfilingReport};
+use crate::random::RandStream;
+use crate::realtime::{ExternalEvent, ExternalEventChannel, ExternalEventSender, RealTimeClock};
+use crate::recording::{EventRecord, EventRecorder};
 use crate::state::SimulationState;
-use crate::{async_mode_disabled, async_mode_enabled, Event};
+use crate::time::{self, Timestamp};
+use crate::{async_mode_disabled, async_mode_enabled, Event, EventData, EventId};
 
 async_mode_enabled!(
     use futures::Future;
 
-    use crate::event::EventData;
     use crate::async_mode::channel::channel;
+    use crate::async_mode::diagnostics::{stall_report, SuspendedTask};
     use crate::async_mode::executor::Executor;
-    use crate::async_mode::{UnboundedQueue, EventKey};
+    use crate::async_mode::task::Task;
+    use crate::async_mode::{BoundedQueue, JoinHandle, UnboundedQueue, EventKey};
+    use crate::async_mode::sync::{Barrier, Mutex, Notify, Semaphore};
     use crate::handler::StaticEventHandler;
 );
 
@@ -32,6 +50,22 @@ async_mode_disabled!(
     pub struct Simulation {
         sim_state: Rc<RefCell<SimulationState>>,
         handlers: Vec<Option<Rc<RefCell<dyn EventHandler>>>>,
+        checkpointables: FxHashMap<Id, Rc<RefCell<dyn Checkpointable>>>,
+        event_types: EventTypeRegistry,
+        event_recorder: Option<Rc<RefCell<dyn EventRecorder>>>,
+        observers: Vec<(ObserverId, Rc<RefCell<dyn EventObserver>>)>,
+        observer_count: ObserverId,
+        breakpoints: Vec<(BreakpointId, BreakpointFn)>,
+        breakpoint_count: BreakpointId,
+        breakpoints_armed: bool,
+        breakpoint_hit: RefCell<Option<BreakpointHit>>,
+        // The event of the last breakpoint hit, which should not stop the simulation again
+        resumed_event: Option<EventId>,
+        realtime_clock: Option<RealTimeClock>,
+        external_events: Option<ExternalEventChannel>,
+        profiler: RefCell<Option<Profiler>>,
+        // Checked before tracing each event delivery to avoid borrowing the simulation state
+        tracing: bool,
     }
 );
 
@@ -45,8 +79,25 @@ async_mode_enabled!(
     pub struct Simulation {
         sim_state: Rc<RefCell<SimulationState>>,
         handlers: Vec<Option<EventHandlerImpl>>,
+        checkpointables: FxHashMap<Id, Rc<RefCell<dyn Checkpointable>>>,
+        event_types: EventTypeRegistry,
+        event_recorder: Option<Rc<RefCell<dyn EventRecorder>>>,
+        observers: Vec<(ObserverId, Rc<RefCell<dyn EventObserver>>)>,
+        observer_count: ObserverId,
+        breakpoints: Vec<(BreakpointId, BreakpointFn)>,
+        breakpoint_count: BreakpointId,
+        breakpoints_armed: bool,
+        breakpoint_hit: RefCell<Option<BreakpointHit>>,
+        // The event of the last breakpoint hit, which should not stop the simulation again
+        resumed_event: Option<EventId>,
+        realtime_clock: Option<RealTimeClock>,
+        external_events: Option<ExternalEventChannel>,
+        profiler: RefCell<Option<Profiler>>,
+        // Checked before tracing each event delivery to avoid borrowing the simulation state
+        tracing: bool,
         // Specific to async mode
         executor: Executor,
+        strict_stall_mode: bool,
     }
 );
 
@@ -54,9 +105,43 @@ impl Simulation {
     async_mode_disabled!(
         /// Creates a new simulation with specified random seed.
         pub fn new(seed: u64) -> Self {
+            Self::with_event_queue(seed, Box::new(BinaryHeapEventQueue::new()))
+        }
+
+        /// Creates a new simulation with specified random seed and event queue implementation.
+        ///
+        /// See [`event_queue`](crate::event_queue) for available implementations.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use dslab_core::Simulation;
+        /// use dslab_core::event_queue::CalendarEventQueue;
+        ///
+        /// let mut sim = Simulation::with_event_queue(123, Box::new(CalendarEventQueue::new()));
+        /// let comp_ctx = sim.create_context("comp");
+        /// comp_ctx.emit_self((), 5.);
+        /// sim.step();
+        /// assert_eq!(sim.time(), 5.);
+        /// ```
+        pub fn with_event_queue(seed: u64, event_queue: Box<dyn EventQueue>) -> Self {
             Self {
-                sim_state: Rc::new(RefCell::new(SimulationState::new(seed))),
+                sim_state: Rc::new(RefCell::new(SimulationState::new(seed, event_queue))),
                 handlers: Vec::new(),
+                checkpointables: FxHashMap::default(),
+                event_types: EventTypeRegistry::default(),
+                event_recorder: None,
+                observers: Vec::new(),
+                observer_count: 0,
+                breakpoints: Vec::new(),
+                breakpoint_count: 0,
+                breakpoints_armed: false,
+                breakpoint_hit: RefCell::new(None),
+                resumed_event: None,
+                realtime_clock: None,
+                external_events: None,
+                profiler: RefCell::new(None),
+                tracing: false,
             }
         }
     );
@@ -64,20 +149,43 @@ impl Simulation {
     async_mode_enabled!(
         /// Creates a new simulation with specified random seed.
         pub fn new(seed: u64) -> Self {
+            Self::with_event_queue(seed, Box::new(BinaryHeapEventQueue::new()))
+        }
+
+        /// Creates a new simulation with specified random seed and event queue implementation.
+        ///
+        /// See [`event_queue`](crate::event_queue) for available implementations.
+        pub fn with_event_queue(seed: u64, event_queue: Box<dyn EventQueue>) -> Self {
             let (task_sender, task_receiver) = channel();
             Self {
-                sim_state: Rc::new(RefCell::new(SimulationState::new(seed, task_sender))),
+                sim_state: Rc::new(RefCell::new(SimulationState::new(seed, event_queue, task_sender))),
                 handlers: Vec::new(),
+                checkpointables: FxHashMap::default(),
+                event_types: EventTypeRegistry::default(),
+                event_recorder: None,
+                observers: Vec::new(),
+                observer_count: 0,
+                breakpoints: Vec::new(),
+                breakpoint_count: 0,
+                breakpoints_armed: false,
+                breakpoint_hit: RefCell::new(None),
+                resumed_event: None,
+                realtime_clock: None,
+                external_events: None,
+                profiler: RefCell::new(None),
+                tracing: false,
                 // Specific to async mode
                 executor: Executor::new(task_receiver),
+                strict_stall_mode: false,
             }
         }
     );
 
     fn register(&mut self, name: &str) -> Id {
         let id = self.sim_state.borrow_mut().register(name);
-        if id as usize == self.handlers.len() {
-            self.handlers.push(None);
+        // components can also be registered via SimulationContext::create_child_context
+        if id as usize >= self.handlers.len() {
+            self.handlers.resize_with(id as usize + 1, || None);
         }
         id
     }
@@ -134,6 +242,31 @@ impl Simulation {
         self.sim_state.borrow().lookup_name(id)
     }
 
+    /// Returns the identifiers of components with names matching the glob pattern, ordered by Id.
+    ///
+    /// The pattern is matched against the hierarchical component names segment by segment
+    /// (see [`component`](crate::component) module). Within a segment, `*` matches any sequence of characters
+    /// and `?` matches any single character. A segment consisting of `**` matches any number of segments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let rack_ctx = sim.create_context("rack1");
+    /// let host_ctx = rack_ctx.create_child_context("host1");
+    /// let disk0_ctx = host_ctx.create_child_context("disk0");
+    /// let disk1_ctx = host_ctx.create_child_context("disk1");
+    /// assert_eq!(sim.lookup_ids("rack1/*/disk?"), vec![disk0_ctx.id(), disk1_ctx.id()]);
+    /// assert_eq!(sim.lookup_ids("rack1/*"), vec![host_ctx.id()]);
+    /// assert_eq!(sim.lookup_ids("rack1/**"), vec![rack_ctx.id(), host_ctx.id(), disk0_ctx.id(), disk1_ctx.id()]);
+    /// assert!(sim.lookup_ids("rack2/**").is_empty());
+    /// ```
+    pub fn lookup_ids(&self, pattern: &str) -> Vec<Id> {
+        self.sim_state.borrow().lookup_ids(pattern)
+    }
+
     /// Creates a new simulation context with specified name.
     ///
     /// # Examples
@@ -290,8 +423,11 @@ impl Simulation {
     /// Removes the event handler for component with specified name.
     ///
     /// All subsequent events destined for this component will not be delivered until the handler is added again.
+    /// The handlers of child components (see [`SimulationContext::create_child_context`]) are not affected,
+    /// use [`Simulation::remove_subtree`] to remove them as well.
     ///
     /// Pending events to be cancelled upon the handler removal are specified via [`EventCancellationPolicy`].
+    /// The series of periodic events destined for this component are stopped regardless of the policy.
     ///
     /// If async mode is enabled, all pending asynchronous tasks and activities related to this component are cancelled.
     /// To continue receiving events asynchronously after the handler is re-added, spawn new asynchronous tasks
@@ -325,25 +461,78 @@ impl Simulation {
         S: AsRef<str>,
     {
         let id = self.lookup_id(name.as_ref());
-        self.handlers[id as usize] = None;
-        self.sim_state.borrow_mut().on_static_handler_removed(id);
-        self.remove_handler_inner(id);
+        self.remove_handlers(vec![id], cancel_policy);
+    }
 
-        // cancel pending events related to the removed component based on the cancellation policy
+    /// Removes the event handlers for component with specified name and all its child components.
+    ///
+    /// The subtree consists of the component itself and all components with names starting with `<name>/`
+    /// (see [`SimulationContext::create_child_context`]). The handlers are removed as in [`Simulation::remove_handler`],
+    /// the cancellation policy is applied to the events of all components in the subtree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use dslab_core::{Event, EventCancellationPolicy, EventHandler, Simulation};
+    ///
+    /// struct Component {
+    /// }
+    ///
+    /// impl EventHandler for Component {
+    ///     fn on(&mut self, event: Event) {
+    ///     }
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let host = sim.create_context("host");
+    /// let disk = host.create_child_context("disk");
+    /// sim.add_handler(host.name(), Rc::new(RefCell::new(Component {})));
+    /// sim.add_handler(disk.name(), Rc::new(RefCell::new(Component {})));
+    /// sim.remove_subtree("host", EventCancellationPolicy::None);
+    /// ```
+    pub fn remove_subtree<S>(&mut self, name: S, cancel_policy: EventCancellationPolicy)
+    where
+        S: AsRef<str>,
+    {
+        // ensure that the component exists
+        self.lookup_id(name.as_ref());
+        let ids = self.sim_state.borrow().subtree_ids(name.as_ref());
+        self.remove_handlers(ids, cancel_policy);
+    }
+
+    fn remove_handlers(&mut self, ids: Vec<Id>, cancel_policy: EventCancellationPolicy) {
+        for &id in ids.iter() {
+            if let Some(handler) = self.handlers.get_mut(id as usize) {
+                *handler = None;
+            }
+            self.sim_state.borrow_mut().on_static_handler_removed(id);
+            self.remove_handler_inner(id);
+        }
+
+        // cancel pending events related to the removed components based on the cancellation policy
+        let removed: FxHashSet<Id> = ids.iter().copied().collect();
         match cancel_policy {
-            EventCancellationPolicy::All => self.cancel_events(|e| e.src == id || e.dst == id),
-            EventCancellationPolicy::Incoming => self.cancel_events(|e| e.dst == id),
-            EventCancellationPolicy::Outgoing => self.cancel_events(|e| e.src == id),
+            EventCancellationPolicy::All => {
+                self.cancel_events(|e| removed.contains(&e.src) || removed.contains(&e.dst))
+            }
+            EventCancellationPolicy::Incoming => self.cancel_events(|e| removed.contains(&e.dst)),
+            EventCancellationPolicy::Outgoing => self.cancel_events(|e| removed.contains(&e.src)),
             _ => {}
         }
+        // periodic events are not delivered to the removed components anymore
+        self.sim_state.borrow_mut().stop_periodic_events_to(&removed);
 
-        debug!(
-            target: "simulation",
-            "[{:.3} {} simulation] Removed handler: {}",
-            self.time(),
-            crate::log::get_colored("DEBUG", colored::Color::Blue),
-            json!({"name": name.as_ref(), "id": id})
-        );
+        for id in ids {
+            debug!(
+                target: "simulation",
+                "[{:.3} {} simulation] Removed handler: {}",
+                self.time(),
+                crate::log::get_colored("DEBUG", colored::Color::Blue),
+                json!({"name": self.lookup_name(id), "id": id})
+            );
+        }
     }
 
     async_mode_disabled!(
@@ -381,6 +570,14 @@ impl Simulation {
         self.sim_state.borrow().time()
     }
 
+    /// Returns the current simulation time in nanoseconds.
+    ///
+    /// With `integer_time` feature this is the exact value of simulation clock, otherwise the current time
+    /// is rounded to the nearest nanosecond (see [`time`](crate::time)).
+    pub fn time_nanos(&self) -> u64 {
+        self.sim_state.borrow().time_nanos()
+    }
+
     /// Performs a single step through the simulation.
     ///
     /// Takes the next event from the queue, advances the simulation time to event time and tries to process it
@@ -411,15 +608,31 @@ impl Simulation {
     /// assert!(!status);
     /// ```
     pub fn step(&self) -> bool {
+        if self.realtime_clock.is_some() {
+            self.wait_for_next_activity();
+        } else {
+            self.receive_external_events();
+        }
         self.step_inner()
     }
 
     async_mode_disabled!(
         fn step_inner(&self) -> bool {
+            if self.check_breakpoints() {
+                return true;
+            }
             let event_opt = self.sim_state.borrow_mut().next_event();
             match event_opt {
                 Some(event) => {
-                    self.deliver_event_via_handler(event);
+                    self.record_event(&event);
+                    if let Some(event) = self.observe_before_delivery(event) {
+                        self.trace_delivery(&event);
+                        let observed_event = self.clone_for_observers(&event);
+                        let profiled_event = self.profiled_event(&event);
+                        self.deliver_event_via_handler(event);
+                        self.profile_event(profiled_event);
+                        self.observe_after_delivery(observed_event);
+                    }
                     true
                 }
                 None => false,
@@ -449,6 +662,7 @@ impl Simulation {
             let has_timer = self.sim_state.borrow_mut().peek_timer().is_some();
             let has_event = self.sim_state.borrow_mut().peek_event().is_some();
             if !has_timer && !has_event {
+                self.check_stall();
                 return false;
             }
             if !has_timer {
@@ -472,23 +686,61 @@ impl Simulation {
         }
 
         fn process_event(&self) {
+            if self.check_breakpoints() {
+                return;
+            }
             let event = self.sim_state.borrow_mut().next_event().unwrap();
+            self.record_event(&event);
+            let event = match self.observe_before_delivery(event) {
+                Some(event) => event,
+                None => return,
+            };
+            self.trace_delivery(&event);
+            let observed_event = self.clone_for_observers(&event);
             let event_key = self
                 .sim_state
                 .borrow()
                 .get_key_getter(event.data.type_id())
                 .map(|getter| getter(event.data.as_ref()));
+            let profiled_event = self.profiled_event(&event);
             if self.sim_state.borrow().has_event_promise_for(&event, event_key) {
                 self.log_event(&event);
                 self.sim_state.borrow_mut().complete_event_promise(event, event_key);
-                self.process_task();
+                // the task polling is attributed to the event processing
+                if let Some(task) = self.executor.next_task() {
+                    self.poll_task(task);
+                }
             } else {
                 self.deliver_event_via_handler(event);
             }
+            self.profile_event(profiled_event);
+            self.observe_after_delivery(observed_event);
         }
 
         fn process_task(&self) -> bool {
-            self.executor.process_task()
+            let Some(task) = self.executor.next_task() else {
+                return false;
+            };
+            if self.profiler.borrow().is_none() {
+                self.poll_task(task);
+                return true;
+            }
+            let component_id = task.component_id();
+            let start = Instant::now();
+            self.poll_task(task);
+            let elapsed = start.elapsed();
+            if let Some(profiler) = self.profiler.borrow_mut().as_mut() {
+                profiler.record_poll(component_id, elapsed);
+            }
+            true
+        }
+
+        fn poll_task(&self, task: Rc<Task>) {
+            // the waits on synchronization primitives started during polling are attributed to the task component
+            self.sim_state
+                .borrow_mut()
+                .set_polled_task_component(task.component_id());
+            task.poll();
         }
 
         fn process_timer(&self) {
@@ -516,6 +768,141 @@ impl Simulation {
         }
     );
 
+    async_mode_disabled!(
+        // Returns the time of the next event.
+        fn next_activity_time(&self) -> Option<f64> {
+            self.sim_state
+                .borrow_mut()
+                .peek_event()
+                .map(|event| event.time_seconds())
+        }
+    );
+
+    async_mode_enabled!(
+        // Returns the time of the next event or timer, or the current time if there are scheduled tasks.
+        fn next_activity_time(&self) -> Option<f64> {
+            if self.executor.has_tasks() {
+                return Some(self.time());
+            }
+            let mut sim_state = self.sim_state.borrow_mut();
+            let event_time = sim_state.peek_event().map(|event| event.time_seconds());
+            let timer_time = sim_state.peek_timer().map(|timer| time::seconds(timer.time));
+            match (event_time, timer_time) {
+                (Some(event_time), Some(timer_time)) => Some(event_time.min(timer_time)),
+                (event_time, timer_time) => event_time.or(timer_time),
+            }
+        }
+    );
+
+    // Returns the event destination and type to be recorded by profiler, if profiling is enabled.
+    // Returns the event destination and type along with the processing start time if profiling is enabled.
+    fn profiled_event(&self, event: &Event) -> Option<(Id, &'static str, Instant)> {
+        self.profiler
+            .borrow()
+            .as_ref()
+            .map(|_| (event.dst, type_name(&event.data).unwrap_or("unknown"), Instant::now()))
+    }
+
+    fn profile_event(&self, profiled_event: Option<(Id, &'static str, Instant)>) {
+        if let Some((dst, event_type, start)) = profiled_event {
+            let elapsed = start.elapsed();
+            let sim_state = self.sim_state.borrow();
+            if let Some(profiler) = self.profiler.borrow_mut().as_mut() {
+                profiler.record_event(dst, event_type, elapsed);
+                profiler.observe_queue_size(sim_state.time(), sim_state.queue_size());
+            }
+        }
+    }
+
+    // Checks whether the next event matches some breakpoint, stores the breakpoint hit if so.
+    fn check_breakpoints(&self) -> bool {
+        if !self.breakpoints_armed {
+            return false;
+        }
+        let mut sim_state = self.sim_state.borrow_mut();
+        let event = match sim_state.peek_event() {
+            Some(event) if Some(event.id) != self.resumed_event => event,
+            _ => return false,
+        };
+        for (id, breakpoint) in self.breakpoints.iter() {
+            if breakpoint(event) {
+                *self.breakpoint_hit.borrow_mut() = Some(BreakpointHit {
+                    breakpoint_id: *id,
+                    event: event.clone(),
+                });
+                return true;
+            }
+        }
+        false
+    }
+
+    fn trace_delivery(&self, event: &Event) {
+        if self.tracing {
+            self.sim_state.borrow_mut().trace_delivery(event);
+        }
+    }
+
+    fn record_event(&self, event: &Event) {
+        if let Some(recorder) = self.event_recorder.as_ref() {
+            recorder.borrow_mut().record(EventRecord::from_event(event));
+        }
+    }
+
+    // Passes the event to observers, returns the event if it should be delivered.
+    fn observe_before_delivery(&self, mut event: Event) -> Option<Event> {
+        if self.observers.is_empty() {
+            return Some(event);
+        }
+        for (_, observer) in self.observers.iter() {
+            let action = observer.borrow_mut().before_delivery(&mut event);
+            match action {
+                ObserverAction::Deliver => {}
+                ObserverAction::Drop => {
+                    debug!(
+                        target: "simulation",
+                        "[{:.3} {} simulation] Event is dropped by observer: {}",
+                        self.time(),
+                        crate::log::get_colored("DEBUG", colored::Color::Blue),
+                        json!({"id": event.id, "type": type_name(&event.data).unwrap(), "src": event.src, "dst": event.dst})
+                    );
+                    return None;
+                }
+                ObserverAction::Delay(delay) => {
+                    let new_id = self
+                        .sim_state
+                        .borrow_mut()
+                        .add_boxed_event(event.data, event.src, event.dst, delay);
+                    debug!(
+                        target: "simulation",
+                        "[{:.3} {} simulation] Event is delayed by observer: {}",
+                        self.time(),
+                        crate::log::get_colored("DEBUG", colored::Color::Blue),
+                        json!({"id": event.id, "new_id": new_id, "delay": delay, "src": event.src, "dst": event.dst})
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(event)
+    }
+
+    // Event is moved to its handler on delivery, so a copy is needed for after_delivery hooks.
+    fn clone_for_observers(&self, event: &Event) -> Option<Event> {
+        if self.observers.is_empty() {
+            None
+        } else {
+            Some(event.clone())
+        }
+    }
+
+    fn observe_after_delivery(&self, event: Option<Event>) {
+        if let Some(event) = event {
+            for (_, observer) in self.observers.iter() {
+                observer.borrow_mut().after_delivery(&event);
+            }
+        }
+    }
+
     fn log_event(&self, event: &Event) {
         if log_enabled!(Trace) {
             let src_name = self.lookup_name(event.src);
@@ -523,7 +910,7 @@ impl Simulation {
             trace!(
                 target: &dst_name,
                 "[{:.3} {} {}] {}",
-                event.time,
+                event.time_seconds(),
                 crate::log::get_colored("EVENT", colored::Color::BrightBlack),
                 dst_name,
                 json!({"type": type_name(&event.data).unwrap(), "data": event.data, "src": src_name})
@@ -539,6 +926,9 @@ impl Simulation {
         ///
         /// To spawn methods inside simulation components use [`SimulationContext::spawn`].
         ///
+        /// Returns the handle which can be used to await the task result or to abort the task
+        /// (see [`JoinHandle`](crate::async_mode::JoinHandle)).
+        ///
         /// # Examples
         ///
         /// ```rust
@@ -557,8 +947,79 @@ impl Simulation {
         /// sim.step_until_no_events();
         /// assert_eq!(sim.time(), 5.);
         /// ```
-        pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
-            self.sim_state.borrow_mut().spawn(future);
+        pub fn spawn<T: 'static>(&self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+            self.sim_state.borrow_mut().spawn(future)
+        }
+
+        /// Returns the tasks suspended while waiting for events or
+        /// [synchronization primitives](crate::async_mode::sync), ordered by the suspension time.
+        ///
+        /// If there are suspended tasks when there are no pending events and timers, these tasks will never be
+        /// resumed, which usually indicates a deadlock or a lost event.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use serde::Serialize;
+        /// use dslab_core::Simulation;
+        ///
+        /// #[derive(Clone, Serialize)]
+        /// struct Response {}
+        ///
+        /// let mut sim = Simulation::new(123);
+        /// let client = sim.create_context("client");
+        /// let server = sim.create_context("server");
+        /// let server_id = server.id();
+        /// sim.spawn(async move {
+        ///     client.sleep(5.).await;
+        ///     client.recv_event_from::<Response>(server_id).await;
+        /// });
+        /// sim.step_until_no_events();
+        ///
+        /// let tasks = sim.suspended_tasks();
+        /// assert_eq!(tasks.len(), 1);
+        /// assert_eq!(tasks[0].component_name.as_deref(), Some("client"));
+        /// assert_eq!(tasks[0].src_name.as_deref(), Some("server"));
+        /// assert_eq!(tasks[0].suspended_at, 5.);
+        /// assert!(tasks[0].to_string().ends_with("Response from server since 5.000"));
+        /// ```
+        pub fn suspended_tasks(&self) -> Vec<SuspendedTask> {
+            self.sim_state.borrow().suspended_tasks()
+        }
+
+        /// Enables or disables the strict stall mode.
+        ///
+        /// In this mode the simulation panics with the report listing the [suspended tasks](Self::suspended_tasks)
+        /// if it runs out of pending events and timers while some tasks are still waiting for events or
+        /// synchronization primitives.
+        ///
+        /// ```should_panic
+        /// use serde::Serialize;
+        /// use dslab_core::Simulation;
+        ///
+        /// #[derive(Clone, Serialize)]
+        /// struct Response {}
+        ///
+        /// let mut sim = Simulation::new(123);
+        /// sim.set_strict_stall_mode(true);
+        /// let client = sim.create_context("client");
+        /// sim.spawn(async move {
+        ///     client.recv_event::<Response>().await;
+        /// });
+        /// // panics with "Simulation stalled at time 0.000 with 1 suspended task(s)"
+        /// sim.step_until_no_events();
+        /// ```
+        pub fn set_strict_stall_mode(&mut self, enabled: bool) {
+            self.strict_stall_mode = enabled;
+        }
+
+        fn check_stall(&self) {
+            if self.strict_stall_mode {
+                let tasks = self.suspended_tasks();
+                if !tasks.is_empty() {
+                    panic!("{}", stall_report(self.time(), &tasks));
+                }
+            }
         }
 
         /// Registers a function that extracts [`EventKey`] from events of a type `T`.
@@ -640,6 +1101,34 @@ impl Simulation {
         {
             UnboundedQueue::new(self.create_context(name))
         }
+
+        /// Creates a [`BoundedQueue`] with the specified capacity.
+        ///
+        /// Unlike [`UnboundedQueue`], the producers are blocked while the queue is full, which allows modeling
+        /// the backpressure. See [`BoundedQueue`] for example.
+        pub fn create_bounded_queue<T>(&self, capacity: usize) -> BoundedQueue<T> {
+            BoundedQueue::new(capacity, self.sim_state.clone())
+        }
+
+        /// Creates a [`Semaphore`] with the specified number of permits.
+        pub fn create_semaphore(&self, permits: usize) -> Semaphore {
+            Semaphore::new(permits, self.sim_state.clone())
+        }
+
+        /// Creates a [`Mutex`] protecting the specified value.
+        pub fn create_mutex<T>(&self, value: T) -> Mutex<T> {
+            Mutex::new(value, self.sim_state.clone())
+        }
+
+        /// Creates a [`Barrier`] which releases the waiting tasks when the specified number of tasks reach it.
+        pub fn create_barrier(&self, count: usize) -> Barrier {
+            Barrier::new(count, self.sim_state.clone())
+        }
+
+        /// Creates a [`Notify`] primitive for notifying the waiting tasks.
+        pub fn create_notify(&self) -> Notify {
+            Notify::new(self.sim_state.clone())
+        }
     );
 
     /// Performs the specified number of steps through the simulation.
@@ -708,6 +1197,121 @@ impl Simulation {
         while self.step() {}
     }
 
+    /// Steps through the simulation until the specified condition holds or there are no pending events left.
+    ///
+    /// The condition is checked before each step. Returns `true` if the condition holds and `false` if the simulation
+    /// ran out of events before that.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use dslab_core::Simulation;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct SomeEvent {}
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp_ctx = sim.create_context("comp");
+    /// for i in 1..=5 {
+    ///     comp_ctx.emit_self(SomeEvent {}, i as f64);
+    /// }
+    /// assert!(sim.step_until(|sim| sim.time() >= 3.));
+    /// assert_eq!(sim.time(), 3.);
+    /// assert!(!sim.step_until(|sim| sim.time() > 10.));
+    /// assert_eq!(sim.time(), 5.);
+    /// ```
+    pub fn step_until<F>(&mut self, mut condition: F) -> bool
+    where
+        F: FnMut(&Simulation) -> bool,
+    {
+        while !condition(self) {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Adds the breakpoint which matches the events satisfying the given predicate, returns breakpoint id.
+    ///
+    /// Breakpoints are checked only by [`step_until_breakpoint`](Self::step_until_breakpoint).
+    pub fn add_breakpoint<F>(&mut self, pred: F) -> BreakpointId
+    where
+        F: Fn(&Event) -> bool + 'static,
+    {
+        let id = self.breakpoint_count;
+        self.breakpoint_count += 1;
+        self.breakpoints.push((id, Box::new(pred)));
+        id
+    }
+
+    /// Adds the breakpoint which matches the events of type `T` delivered to the specified component,
+    /// returns breakpoint id.
+    ///
+    /// See [`step_until_breakpoint`](Self::step_until_breakpoint) for example.
+    pub fn add_event_breakpoint<T: EventData>(&mut self, dst: Id) -> BreakpointId {
+        self.add_breakpoint(move |event| event.dst == dst && event.data.is::<T>())
+    }
+
+    /// Removes the breakpoint with specified id.
+    pub fn remove_breakpoint(&mut self, id: BreakpointId) {
+        self.breakpoints.retain(|(breakpoint_id, _)| *breakpoint_id != id);
+    }
+
+    /// Steps through the simulation until the next event matches some breakpoint or there are no pending events left.
+    ///
+    /// The simulation is paused before the delivery of matched event, so the current time is not advanced to the event
+    /// time. Returns the information about the matched breakpoint and event, or `None` if there are no pending events
+    /// left. If several breakpoints match the event, the earliest added one is returned. The next call continues
+    /// the simulation starting from the matched event.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use dslab_core::Simulation;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Ping {}
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Pong {}
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp1_ctx = sim.create_context("comp1");
+    /// let comp2_ctx = sim.create_context("comp2");
+    /// comp1_ctx.emit(Ping {}, comp2_ctx.id(), 1.);
+    /// comp1_ctx.emit(Pong {}, comp2_ctx.id(), 2.);
+    /// comp1_ctx.emit(Pong {}, comp2_ctx.id(), 3.);
+    ///
+    /// let breakpoint = sim.add_event_breakpoint::<Pong>(comp2_ctx.id());
+    /// let hit = sim.step_until_breakpoint().unwrap();
+    /// assert_eq!(hit.breakpoint_id, breakpoint);
+    /// assert_eq!(hit.event.time_seconds(), 2.);
+    /// assert_eq!(sim.time(), 1.);
+    ///
+    /// let hit = sim.step_until_breakpoint().unwrap();
+    /// assert_eq!(hit.event.time_seconds(), 3.);
+    /// assert_eq!(sim.time(), 2.);
+    ///
+    /// assert!(sim.step_until_breakpoint().is_none());
+    /// assert_eq!(sim.time(), 3.);
+    /// ```
+    pub fn step_until_breakpoint(&mut self) -> Option<BreakpointHit> {
+        self.breakpoints_armed = true;
+        let mut hit = None;
+        while self.step() {
+            hit = self.breakpoint_hit.borrow_mut().take();
+            if hit.is_some() {
+                break;
+            }
+        }
+        self.breakpoints_armed = false;
+        self.resumed_event = hit.as_ref().map(|hit| hit.event.id);
+        hit
+    }
+
     /// Steps through the simulation with duration limit.
     ///
     /// This is a convenient wrapper around [`step`](Self::step), which invokes this method until the next event
@@ -780,7 +1384,30 @@ impl Simulation {
     /// assert!(!status); // there are no more events
     /// ```
     pub fn step_until_time(&mut self, time: f64) -> bool {
-        self.step_until_time_inner(time)
+        if self.realtime_clock.is_some() {
+            self.step_until_time_realtime(time)
+        } else {
+            self.step_until_time_inner(time)
+        }
+    }
+
+    // Unlike step_until_time_inner, waits until the specified time even if there are no pending events,
+    // since external events can arrive meanwhile.
+    fn step_until_time_realtime(&mut self, time: f64) -> bool {
+        loop {
+            match self.next_activity_time() {
+                Some(next_time) if next_time <= time => {
+                    self.step();
+                }
+                _ => {
+                    if self.wait_realtime(time) {
+                        break;
+                    }
+                }
+            }
+        }
+        self.sim_state.borrow_mut().set_time(time);
+        self.next_activity_time().is_some()
     }
 
     async_mode_disabled!(
@@ -788,7 +1415,7 @@ impl Simulation {
             let mut result = true;
             loop {
                 if let Some(event) = self.sim_state.borrow_mut().peek_event() {
-                    if event.time > time {
+                    if event.time > time::timestamp(time) {
                         break;
                     }
                 } else {
@@ -813,14 +1440,14 @@ impl Simulation {
 
                 if let Some(event) = self.sim_state.borrow_mut().peek_event() {
                     result = true;
-                    if event.time <= time {
+                    if event.time <= time::timestamp(time) {
                         step = true;
                     }
                 }
 
                 if let Some(timer) = self.sim_state.borrow_mut().peek_timer() {
                     result = true;
-                    if timer.time <= time {
+                    if timer.time <= time::timestamp(time) {
                         step = true;
                     }
                 }
@@ -836,6 +1463,34 @@ impl Simulation {
         }
     );
 
+    /// Enables independent random streams for components.
+    ///
+    /// After this call, random methods of [`SimulationContext`] use the generator of the component, which is derived
+    /// from the simulation seed and the component name, instead of the simulation-wide generator. This way the random
+    /// values observed by a component do not depend on the presence of other components or their random draws.
+    /// Should be called before the components draw any random numbers. See [`random`](crate::random) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dslab_core::Simulation;
+    ///
+    /// let draw = |with_other_comp: bool| {
+    ///     let mut sim = Simulation::new(123);
+    ///     sim.enable_component_rand_streams();
+    ///     if with_other_comp {
+    ///         let other_ctx = sim.create_context("other");
+    ///         other_ctx.rand();
+    ///     }
+    ///     let comp_ctx = sim.create_context("comp");
+    ///     comp_ctx.rand()
+    /// };
+    /// assert_eq!(draw(false), draw(true));
+    /// ```
+    pub fn enable_component_rand_streams(&mut self) {
+        self.sim_state.borrow_mut().enable_component_rand_streams();
+    }
+
     /// Returns a random float in the range _[0, 1)_
     /// using the simulation-wide random number generator.
     ///
@@ -849,7 +1504,7 @@ impl Simulation {
     /// assert!(f >= 0.0 && f < 1.0);
     /// ```
     pub fn rand(&mut self) -> f64 {
-        self.sim_state.borrow_mut().rand()
+        self.sim_state.borrow_mut().rand(RandStream::Global)
     }
 
     /// Returns a random number in the specified range
@@ -871,19 +1526,139 @@ impl Simulation {
         T: SampleUniform,
         R: SampleRange<T>,
     {
-        self.sim_state.borrow_mut().gen_range(range)
+        self.sim_state.borrow_mut().gen_range(RandStream::Global, range)
     }
 
     /// Returns a random value from the specified distribution
     /// using the simulation-wide random number generator.
     pub fn sample_from_distribution<T, Dist: Distribution<T>>(&mut self, dist: &Dist) -> T {
-        self.sim_state.borrow_mut().sample_from_distribution(dist)
+        self.sim_state
+            .borrow_mut()
+            .sample_from_distribution(RandStream::Global, dist)
     }
 
     /// Returns a random alphanumeric string of specified length
     /// using the simulation-wide random number generator.
     pub fn random_string(&mut self, len: usize) -> String {
-        self.sim_state.borrow_mut().random_string(len)
+        self.sim_state.borrow_mut().random_string(RandStream::Global, len)
+    }
+
+    /// Returns the values of all metrics reported by components at the current simulation time.
+    ///
+    /// See [`SimulationContext::metrics`] for the example.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.sim_state.borrow().metrics_snapshot()
+    }
+
+    /// Enables recording of the simulation trace in Chrome Trace Event format.
+    ///
+    /// The trace includes the events delivered to components and the spans started by components via
+    /// [`SimulationContext::start_span`]. Only the activity after this call is recorded.
+    /// See [`trace`](crate::trace) for the details of trace format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use dslab_core::Simulation;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Request {}
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// sim.enable_tracing();
+    /// let comp_ctx = sim.create_context("comp");
+    /// let span = comp_ctx.start_span("processing");
+    /// comp_ctx.emit_self(Request {}, 1.5);
+    /// sim.step();
+    /// comp_ctx.finish_span(span);
+    ///
+    /// let trace = sim.trace().unwrap();
+    /// let events = trace["traceEvents"].as_array().unwrap();
+    /// // process name, span start, event delivery, span end
+    /// assert_eq!(events.len(), 4);
+    /// assert_eq!(events[2]["name"], "Request");
+    /// assert_eq!(events[2]["ts"], 1.5e6);
+    /// assert_eq!(events[3]["ph"], "e");
+    /// assert_eq!(events[3]["ts"], 1.5e6);
+    /// ```
+    pub fn enable_tracing(&mut self) {
+        self.tracing = true;
+        self.sim_state.borrow_mut().enable_tracing();
+    }
+
+    /// Returns the recorded trace in Chrome Trace Event JSON format, or `None` if tracing is not enabled.
+    pub fn trace(&self) -> Option<Value> {
+        self.sim_state.borrow().trace()
+    }
+
+    /// Saves the recorded trace in Chrome Trace Event JSON format to the specified file.
+    ///
+    /// The file can be opened in [Perfetto](https://ui.perfetto.dev) or `chrome://tracing`.
+    pub fn save_trace(&self, path: &str) -> Result<(), String> {
+        let trace = self.trace().ok_or("Tracing is not enabled")?;
+        crate::trace::save_trace(&trace, path)
+    }
+
+    /// Enables profiling of event processing cost.
+    ///
+    /// The simulation measures the wall time spent in processing of events by each component and event type,
+    /// and samples the event queue size each `queue_sample_interval` units of simulation time.
+    /// Only the activity after this call is profiled. See [`profiling`](crate::profiling) for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use serde::Serialize;
+    /// use dslab_core::{Event, EventHandler, Simulation};
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Request {}
+    ///
+    /// struct Server {}
+    ///
+    /// impl EventHandler for Server {
+    ///     fn on(&mut self, event: Event) {}
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// sim.enable_profiling(1.0);
+    /// let client_ctx = sim.create_context("client");
+    /// let server_id = sim.add_handler("server", Rc::new(RefCell::new(Server {})));
+    /// for i in 0..10 {
+    ///     client_ctx.emit(Request {}, server_id, i as f64);
+    /// }
+    /// sim.step_until_no_events();
+    ///
+    /// let report = sim.profiling_report().unwrap();
+    /// assert_eq!(report.event_count, 10);
+    /// let server = report.component("server").unwrap();
+    /// assert_eq!(server.event_types[0].event_type, "Request");
+    /// assert_eq!(server.event_types[0].event_count, 10);
+    /// assert_eq!(report.queue_size.len(), 10);
+    /// println!("{}", report);
+    /// ```
+    pub fn enable_profiling(&mut self, queue_sample_interval: f64) {
+        assert!(queue_sample_interval > 0., "Queue sample interval should be positive");
+        *self.profiler.borrow_mut() = Some(Profiler::new(queue_sample_interval, self.time()));
+    }
+
+    /// Returns the report with event processing statistics, or `None` if profiling is not enabled.
+    pub fn profiling_report(&self) -> Option<ProfilingReport> {
+        let sim_state = self.sim_state.borrow();
+        self.profiler
+            .borrow()
+            .as_ref()
+            .map(|profiler| profiler.report(sim_state.component_names()))
+    }
+
+    /// Prints the profiling report, if profiling is enabled.
+    pub fn print_profiling_report(&self) {
+        if let Some(report) = self.profiling_report() {
+            print!("{}", report);
+        }
     }
 
     /// Returns the total number of created events.
@@ -997,11 +1772,453 @@ impl Simulation {
     /// let event3 = ctx1.emit(SomeEvent {}, ctx2.id(), 2.0);
     /// let events = sim.dump_events();
     /// assert_eq!(events.len(), 3);
-    /// assert_eq!((events[0].id, events[0].time), (event1, 1.0));
-    /// assert_eq!((events[1].id, events[1].time), (event2, 1.0));
-    /// assert_eq!((events[2].id, events[2].time), (event3, 2.0));
+    /// assert_eq!((events[0].id, events[0].time_seconds()), (event1, 1.0));
+    /// assert_eq!((events[1].id, events[1].time_seconds()), (event2, 1.0));
+    /// assert_eq!((events[2].id, events[2].time_seconds()), (event3, 2.0));
     /// ```
     pub fn dump_events(&self) -> Vec<Event> {
         self.sim_state.borrow().dump_events()
     }
+
+    /// Enables the real-time mode, in which the simulation time advances synchronously with the wall-clock time.
+    ///
+    /// The `speed` factor specifies the number of simulation time units per second of wall-clock time.
+    /// The simulation and wall-clock times are synchronized starting from the current moment. If the event processing
+    /// is slower than the wall-clock time, the simulation runs as fast as possible until it catches up.
+    ///
+    /// In the real-time mode [`step_until_time`](Self::step_until_time) and
+    /// [`step_for_duration`](Self::step_for_duration) wait until the specified time even if there are no pending
+    /// events, receiving the external events meanwhile. Other methods return when there are no pending events.
+    /// See [`realtime`](crate::realtime) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Instant;
+    /// use dslab_core::Simulation;
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let comp_ctx = sim.create_context("comp");
+    /// comp_ctx.emit_self((), 0.5);
+    ///
+    /// sim.enable_realtime_mode(10.);
+    /// let start = Instant::now();
+    /// sim.step_until_no_events();
+    /// assert!(start.elapsed().as_secs_f64() >= 0.05);
+    /// assert_eq!(sim.time(), 0.5);
+    /// ```
+    pub fn enable_realtime_mode(&mut self, speed: f64) {
+        self.realtime_clock = Some(RealTimeClock::new(speed, self.time()));
+    }
+
+    /// Disables the real-time mode, so the simulation runs as fast as possible.
+    pub fn disable_realtime_mode(&mut self) {
+        self.realtime_clock = None;
+    }
+
+    /// Returns the speed factor of the real-time mode, or `None` if this mode is disabled.
+    pub fn realtime_speed(&self) -> Option<f64> {
+        self.realtime_clock.as_ref().map(|clock| clock.speed())
+    }
+
+    /// Returns the sender for injecting events into the simulation from other threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use serde::Serialize;
+    /// use dslab_core::{cast, Event, EventHandler, Simulation};
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Command {
+    ///     value: u32,
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Device {
+    ///     commands: Vec<u32>,
+    /// }
+    ///
+    /// impl EventHandler for Device {
+    ///     fn on(&mut self, event: Event) {
+    ///         cast!(match event.data {
+    ///             Command { value } => {
+    ///                 self.commands.push(value);
+    ///             }
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let device = Rc::new(RefCell::new(Device::default()));
+    /// let device_id = sim.add_handler("device", device.clone());
+    /// let operator_id = sim.create_context("operator").id();
+    ///
+    /// let sender = sim.external_event_sender();
+    /// let operator = std::thread::spawn(move || {
+    ///     for value in 0..3 {
+    ///         sender.send(Command { value }, operator_id, device_id).unwrap();
+    ///     }
+    /// });
+    ///
+    /// sim.enable_realtime_mode(100.);
+    /// operator.join().unwrap();
+    /// sim.step_until_time(10.);
+    /// assert_eq!(device.borrow().commands, vec![0, 1, 2]);
+    /// ```
+    pub fn external_event_sender(&mut self) -> ExternalEventSender {
+        let (sender, _) = self.external_events.get_or_insert_with(mpsc::channel);
+        ExternalEventSender::new(sender.clone())
+    }
+
+    // Adds the received external events at the current time (the wall-clock one in the real-time mode).
+    fn receive_external_events(&self) {
+        if let Some((_, receiver)) = self.external_events.as_ref() {
+            while let Ok(event) = receiver.try_recv() {
+                let time = match self.realtime_clock.as_ref() {
+                    Some(clock) => clock.sim_time(Instant::now()),
+                    None => self.time(),
+                };
+                self.add_external_event(event, time);
+            }
+        }
+    }
+
+    fn add_external_event(&self, event: ExternalEvent, time: f64) {
+        let mut sim_state = self.sim_state.borrow_mut();
+        let time = time.max(sim_state.time());
+        sim_state.add_boxed_event_at(event.data, event.src, event.dst, time);
+    }
+
+    // Waits until the wall-clock time of the next event or timer, receiving the external events meanwhile.
+    fn wait_for_next_activity(&self) {
+        self.receive_external_events();
+        while let Some(time) = self.next_activity_time() {
+            if self.wait_realtime(time) {
+                break;
+            }
+        }
+    }
+
+    // Waits until the wall-clock time corresponding to the specified simulation time.
+    // Returns false if the waiting was interrupted by an external event.
+    fn wait_realtime(&self, time: f64) -> bool {
+        let clock = self.realtime_clock.as_ref().unwrap();
+        let deadline = clock.wall_time(time);
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        match self.external_events.as_ref() {
+            Some((_, receiver)) => match receiver.recv_timeout(deadline - now) {
+                Ok(event) => {
+                    self.add_external_event(event, clock.sim_time(Instant::now()));
+                    false
+                }
+                Err(_) => true,
+            },
+            None => {
+                std::thread::sleep(deadline - now);
+                true
+            }
+        }
+    }
+
+    /// Sets the recorder which is invoked for each processed event.
+    ///
+    /// The recorder receives events in the order they are retrieved from the event queue, before their delivery
+    /// to the destination component. This can be used to save the event stream to a file via
+    /// [`FileEventRecorder`](crate::recording::FileEventRecorder) or to compare it with the previously recorded one via
+    /// [`TraceVerifier`](crate::recording::TraceVerifier) in order to detect non-determinism.
+    ///
+    /// See [`TraceVerifier`](crate::recording::TraceVerifier) for example.
+    pub fn set_event_recorder(&mut self, recorder: Rc<RefCell<dyn EventRecorder>>) {
+        self.event_recorder = Some(recorder);
+    }
+
+    /// Removes the previously set event recorder.
+    pub fn remove_event_recorder(&mut self) {
+        self.event_recorder = None;
+    }
+
+    /// Registers the observer which is invoked before and after the delivery of each event, returns observer id.
+    ///
+    /// Observers are invoked in the order of registration after the event is passed to the event recorder.
+    /// See [`EventObserver`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use serde::Serialize;
+    /// use dslab_core::{cast, Event, EventHandler, Simulation};
+    /// use dslab_core::observer::{EventObserver, ObserverAction};
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Message {
+    ///     value: u32,
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Receiver {
+    ///     received: Vec<(f64, u32)>,
+    /// }
+    ///
+    /// impl EventHandler for Receiver {
+    ///     fn on(&mut self, event: Event) {
+    ///         let time = event.time_seconds();
+    ///         cast!(match event.data {
+    ///             Message { value } => {
+    ///                 self.received.push((time, value));
+    ///             }
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// // Drops odd messages and delays even ones.
+    /// struct FaultInjector {
+    ///     observed: u32,
+    /// }
+    ///
+    /// impl EventObserver for FaultInjector {
+    ///     fn before_delivery(&mut self, event: &mut Event) -> ObserverAction {
+    ///         match event.data.downcast_ref::<Message>() {
+    ///             Some(msg) if msg.value % 2 == 1 => ObserverAction::Drop,
+    ///             Some(msg) if msg.value < 10 => {
+    ///                 event.data = Box::new(Message { value: msg.value * 10 });
+    ///                 ObserverAction::Delay(1.)
+    ///             }
+    ///             _ => ObserverAction::Deliver,
+    ///         }
+    ///     }
+    ///
+    ///     fn after_delivery(&mut self, _event: &Event) {
+    ///         self.observed += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let receiver = Rc::new(RefCell::new(Receiver::default()));
+    /// let receiver_id = sim.add_handler("receiver", receiver.clone());
+    /// let injector = Rc::new(RefCell::new(FaultInjector { observed: 0 }));
+    /// sim.add_observer(injector.clone());
+    ///
+    /// let ctx = sim.create_context("sender");
+    /// for value in 1..=4 {
+    ///     ctx.emit(Message { value }, receiver_id, value as f64);
+    /// }
+    /// sim.step_until_no_events();
+    /// assert_eq!(receiver.borrow().received, vec![(3., 20), (5., 40)]);
+    /// assert_eq!(injector.borrow().observed, 2);
+    /// ```
+    pub fn add_observer(&mut self, observer: Rc<RefCell<dyn EventObserver>>) -> ObserverId {
+        let id = self.observer_count;
+        self.observer_count += 1;
+        self.observers.push((id, observer));
+        id
+    }
+
+    /// Removes the observer with specified id.
+    pub fn remove_observer(&mut self, id: ObserverId) {
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+    }
+
+    /// Registers the component which state should be included in simulation checkpoints, returns the component Id.
+    ///
+    /// This method does not register the event handler for component, use [`add_handler`](Self::add_handler) for it.
+    ///
+    /// See [`checkpoint`](Self::checkpoint) for example.
+    pub fn add_checkpointable<S>(&mut self, name: S, component: Rc<RefCell<dyn Checkpointable>>) -> Id
+    where
+        S: AsRef<str>,
+    {
+        let id = self.register(name.as_ref());
+        self.checkpointables.insert(id, component);
+        id
+    }
+
+    /// Registers the event type `T`, which allows saving pending events of this type to checkpoint files.
+    ///
+    /// All event types used in the simulation should be registered before calling
+    /// [`save_checkpoint`](Self::save_checkpoint) or [`load_checkpoint`](Self::load_checkpoint).
+    pub fn register_event_type<T>(&mut self)
+    where
+        T: EventData + DeserializeOwned,
+    {
+        self.event_types.register::<T>();
+    }
+
+    /// Creates a checkpoint of the current simulation state.
+    ///
+    /// The checkpoint includes the simulation clock, random number generators, pending events and the state of
+    /// components registered via [`add_checkpointable`](Self::add_checkpointable).
+    ///
+    /// In async mode, checkpoints can be created only when there are no pending timers or awaited events,
+    /// because the state of suspended asynchronous tasks cannot be saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use serde::Serialize;
+    /// use serde_json::{json, Value};
+    /// use dslab_core::{Event, EventHandler, Simulation, SimulationContext};
+    /// use dslab_core::checkpoint::Checkpointable;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Tick {}
+    ///
+    /// struct Counter {
+    ///     ticks: u64,
+    ///     ctx: SimulationContext,
+    /// }
+    ///
+    /// impl EventHandler for Counter {
+    ///     fn on(&mut self, _event: Event) {
+    ///         self.ticks += 1;
+    ///         self.ctx.emit_self(Tick {}, 1.);
+    ///     }
+    /// }
+    ///
+    /// impl Checkpointable for Counter {
+    ///     fn save_state(&self) -> Value {
+    ///         json!({"ticks": self.ticks})
+    ///     }
+    ///
+    ///     fn restore_state(&mut self, state: &Value) {
+    ///         self.ticks = state["ticks"].as_u64().unwrap();
+    ///     }
+    /// }
+    ///
+    /// let mut sim = Simulation::new(123);
+    /// let counter = Rc::new(RefCell::new(Counter { ticks: 0, ctx: sim.create_context("counter") }));
+    /// sim.add_handler("counter", counter.clone());
+    /// sim.add_checkpointable("counter", counter.clone());
+    /// counter.borrow().ctx.emit_self(Tick {}, 1.);
+    ///
+    /// sim.step_until_time(10.);
+    /// let checkpoint = sim.checkpoint();
+    /// assert_eq!(counter.borrow().ticks, 10);
+    ///
+    /// sim.step_until_time(20.);
+    /// assert_eq!(counter.borrow().ticks, 20);
+    ///
+    /// sim.restore(&checkpoint);
+    /// assert_eq!(sim.time(), 10.);
+    /// assert_eq!(counter.borrow().ticks, 10);
+    /// sim.step_until_time(15.);
+    /// assert_eq!(counter.borrow().ticks, 15);
+    /// ```
+    pub fn checkpoint(&self) -> SimulationCheckpoint {
+        self.assert_no_async_activities();
+        let mut checkpoint = self.sim_state.borrow().save_checkpoint();
+        for (id, component) in self.checkpointables.iter() {
+            checkpoint
+                .components
+                .insert(self.lookup_name(*id), component.borrow().save_state());
+        }
+        debug!(
+            target: "simulation",
+            "[{:.3} {} simulation] Created checkpoint: {}",
+            self.time(),
+            crate::log::get_colored("DEBUG", colored::Color::Blue),
+            json!({"pending_events": checkpoint.pending_event_count(), "components": checkpoint.components.len()})
+        );
+        checkpoint
+    }
+
+    /// Restores the simulation state from the checkpoint.
+    ///
+    /// All pending events are replaced with the events stored in the checkpoint. The state of components registered
+    /// via [`add_checkpointable`](Self::add_checkpointable) is restored using [`Checkpointable::restore_state`].
+    /// The state of other components is not changed.
+    ///
+    /// The checkpoint can be restored into a different simulation instance, but the components should be created in
+    /// the same order as in the original simulation, so that the component Ids are preserved.
+    ///
+    /// See [`checkpoint`](Self::checkpoint) for example.
+    pub fn restore(&mut self, checkpoint: &SimulationCheckpoint) {
+        self.assert_no_async_activities();
+        self.sim_state.borrow_mut().restore_checkpoint(checkpoint);
+        for (id, component) in self.checkpointables.iter() {
+            if let Some(state) = checkpoint.components.get(&self.lookup_name(*id)) {
+                component.borrow_mut().restore_state(state);
+            }
+        }
+        debug!(
+            target: "simulation",
+            "[{:.3} {} simulation] Restored checkpoint: {}",
+            self.time(),
+            crate::log::get_colored("DEBUG", colored::Color::Blue),
+            json!({"pending_events": checkpoint.pending_event_count(), "components": checkpoint.components.len()})
+        );
+    }
+
+    /// Saves the checkpoint to the file in JSON format.
+    ///
+    /// The types of all pending events should be registered via [`register_event_type`](Self::register_event_type).
+    pub fn save_checkpoint(&self, checkpoint: &SimulationCheckpoint, path: &str) -> Result<(), String> {
+        let serialized = self.event_types.serialize(checkpoint)?;
+        let file = File::create(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+        serde_json::to_writer(BufWriter::new(file), &serialized)
+            .map_err(|e| format!("Failed to write checkpoint to {}: {}", path, e))
+    }
+
+    /// Loads the checkpoint previously saved by [`save_checkpoint`](Self::save_checkpoint) from the file.
+    ///
+    /// The types of all pending events should be registered via [`register_event_type`](Self::register_event_type).
+    pub fn load_checkpoint(&self, path: &str) -> Result<SimulationCheckpoint, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open file {}: {}", path, e))?;
+        let serialized = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to read checkpoint from {}: {}", path, e))?;
+        self.event_types.deserialize(serialized)
+    }
+
+    // Inserts event with already boxed payload at the specified time, used by parallel simulation.
+    pub(crate) fn add_event_at(&self, data: Box<dyn EventData>, src: Id, dst: Id, time: Timestamp) -> EventId {
+        self.sim_state
+            .borrow_mut()
+            .add_boxed_event_at_timestamp(data, src, dst, time)
+    }
+
+    // Registers component from another partition of parallel simulation, returns None if the name is already used.
+    pub(crate) fn register_remote(&mut self, name: &str) -> Option<Id> {
+        let component_count = self.sim_state.borrow().component_names().len();
+        let id = self.register(name);
+        (id as usize == component_count).then_some(id)
+    }
+
+    async_mode_disabled!(
+        // Returns the time of the next pending event, if any.
+        pub(crate) fn next_event_time(&self) -> Option<f64> {
+            self.sim_state.borrow_mut().peek_event().map(|e| e.time_seconds())
+        }
+
+        fn assert_no_async_activities(&self) {}
+    );
+
+    async_mode_enabled!(
+        // Returns the time of the next pending event or timer, if any.
+        pub(crate) fn next_event_time(&self) -> Option<f64> {
+            while self.process_task() {}
+            let mut state = self.sim_state.borrow_mut();
+            let event_time = state.peek_event().map(|e| e.time_seconds());
+            let timer_time = state.peek_timer().map(|t| time::seconds(t.time));
+            match (event_time, timer_time) {
+                (Some(e), Some(t)) => Some(e.min(t)),
+                (e, t) => e.or(t),
+            }
+        }
+
+        fn assert_no_async_activities(&self) {
+            assert!(
+                !self.sim_state.borrow_mut().has_async_activities(),
+                "Checkpoints are not supported while there are pending timers or awaited events"
+            );
+        }
+    );
 }
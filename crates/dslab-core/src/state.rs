@@ -1,27 +1,39 @@
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::VecDeque;
 
 use rand::distributions::uniform::{SampleRange, SampleUniform};
 use rand::distributions::{Alphanumeric, DistString};
 use rand::prelude::*;
 use rand_pcg::Pcg64;
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::Value;
 
-use crate::component::Id;
+use crate::checkpoint::SimulationCheckpoint;
+use crate::component::{in_subtree, matches_pattern, Id};
 use crate::event::{Event, EventData, EventId};
+use crate::event_queue::EventQueue;
 use crate::log::log_incorrect_event;
+use crate::metrics::{MetricsRegistry, MetricsSnapshot};
+use crate::periodic::{PeriodicEventId, PeriodicSeries};
+use crate::random::{derive_rng, RandStream};
+use crate::time::{self, Timestamp};
+use crate::trace::{SpanId, Tracer};
 use crate::{async_mode_disabled, async_mode_enabled};
 
 async_mode_enabled!(
     use std::any::TypeId;
+    use std::collections::BinaryHeap;
     use std::cell::RefCell;
     use std::rc::Rc;
 
     use futures::Future;
 
     use crate::async_mode::EventKey;
+    use std::collections::BTreeMap;
+    use crate::async_mode::diagnostics::{SuspendedTask, SyncWait};
     use crate::async_mode::channel::Sender;
     use crate::async_mode::promise_store::EventPromiseStore;
     use crate::async_mode::event_future::{EventFuture, EventPromise};
+    use crate::async_mode::join_handle::JoinHandle;
     use crate::async_mode::task::Task;
     use crate::async_mode::timer_future::{TimerPromise, TimerId, TimerFuture};
 );
@@ -32,15 +44,30 @@ pub const EPSILON: f64 = 1e-12;
 async_mode_disabled!(
     #[derive(Clone)]
     pub struct SimulationState {
-        clock: f64,
+        clock: Timestamp,
+        seed: u64,
         rand: Pcg64,
-        events: BinaryHeap<Event>,
+        component_rand_streams: bool,
+        component_rands: Vec<Option<Pcg64>>,
+        named_rands: Vec<(Id, String, Pcg64)>,
+        named_rand_ids: FxHashMap<(Id, String), usize>,
+        events: Box<dyn EventQueue>,
         ordered_events: VecDeque<Event>,
         canceled_events: FxHashSet<EventId>,
         event_count: u64,
 
+        periodic_events: FxHashMap<PeriodicEventId, PeriodicSeries>,
+        // Maps pending occurrence of periodic event to its series
+        periodic_event_ids: FxHashMap<EventId, PeriodicEventId>,
+        periodic_event_count: u64,
+
         component_name_to_id: FxHashMap<String, Id>,
         component_names: Vec<String>,
+
+        metrics: MetricsRegistry,
+
+        tracer: Option<Tracer>,
+        span_count: SpanId,
     }
 );
 
@@ -49,16 +76,31 @@ async_mode_enabled!(
 
     #[derive(Clone)]
     pub struct SimulationState {
-        clock: f64,
+        clock: Timestamp,
+        seed: u64,
         rand: Pcg64,
-        events: BinaryHeap<Event>,
+        component_rand_streams: bool,
+        component_rands: Vec<Option<Pcg64>>,
+        named_rands: Vec<(Id, String, Pcg64)>,
+        named_rand_ids: FxHashMap<(Id, String), usize>,
+        events: Box<dyn EventQueue>,
         ordered_events: VecDeque<Event>,
         canceled_events: FxHashSet<EventId>,
         event_count: u64,
 
+        periodic_events: FxHashMap<PeriodicEventId, PeriodicSeries>,
+        // Maps pending occurrence of periodic event to its series
+        periodic_event_ids: FxHashMap<EventId, PeriodicEventId>,
+        periodic_event_count: u64,
+
         component_name_to_id: FxHashMap<String, Id>,
         component_names: Vec<String>,
 
+        metrics: MetricsRegistry,
+
+        tracer: Option<Tracer>,
+        span_count: SpanId,
+
         // Specific to async mode
         registered_static_handlers: Vec<bool>,
 
@@ -69,36 +111,64 @@ async_mode_enabled!(
         canceled_timers: FxHashSet<TimerId>,
         timer_count: u64,
 
+        // Tasks waiting on synchronization primitives by wait id
+        sync_waits: BTreeMap<u64, SyncWait>,
+        sync_wait_count: u64,
+        // Component which spawned the currently polled task
+        polled_task_component: Option<Id>,
+
         executor: Sender<Rc<Task>>,
     }
 );
 
 impl SimulationState {
     async_mode_disabled!(
-        pub fn new(seed: u64) -> Self {
+        pub fn new(seed: u64, events: Box<dyn EventQueue>) -> Self {
             Self {
-                clock: 0.0,
+                clock: Timestamp::default(),
+                seed,
                 rand: Pcg64::seed_from_u64(seed),
-                events: BinaryHeap::new(),
+                component_rand_streams: false,
+                component_rands: Vec::new(),
+                named_rands: Vec::new(),
+                named_rand_ids: FxHashMap::default(),
+                events,
                 ordered_events: VecDeque::new(),
                 canceled_events: FxHashSet::default(),
                 event_count: 0,
+                periodic_events: FxHashMap::default(),
+                periodic_event_ids: FxHashMap::default(),
+                periodic_event_count: 0,
                 component_name_to_id: FxHashMap::default(),
                 component_names: Vec::new(),
+                metrics: MetricsRegistry::default(),
+                tracer: None,
+                span_count: 0,
             }
         }
     );
     async_mode_enabled!(
-        pub fn new(seed: u64, executor: Sender<Rc<Task>>) -> Self {
+        pub fn new(seed: u64, events: Box<dyn EventQueue>, executor: Sender<Rc<Task>>) -> Self {
             Self {
-                clock: 0.0,
+                clock: Timestamp::default(),
+                seed,
                 rand: Pcg64::seed_from_u64(seed),
-                events: BinaryHeap::new(),
+                component_rand_streams: false,
+                component_rands: Vec::new(),
+                named_rands: Vec::new(),
+                named_rand_ids: FxHashMap::default(),
+                events,
                 ordered_events: VecDeque::new(),
                 canceled_events: FxHashSet::default(),
                 event_count: 0,
+                periodic_events: FxHashMap::default(),
+                periodic_event_ids: FxHashMap::default(),
+                periodic_event_count: 0,
                 component_name_to_id: FxHashMap::default(),
                 component_names: Vec::new(),
+                metrics: MetricsRegistry::default(),
+                tracer: None,
+                span_count: 0,
                 // Specific to async mode
                 registered_static_handlers: Vec::new(),
                 event_promises: EventPromiseStore::new(),
@@ -106,6 +176,9 @@ impl SimulationState {
                 timers: BinaryHeap::new(),
                 canceled_timers: FxHashSet::default(),
                 timer_count: 0,
+                sync_waits: BTreeMap::new(),
+                sync_wait_count: 0,
+                polled_task_component: None,
                 executor,
             }
         }
@@ -130,46 +203,97 @@ impl SimulationState {
         self.component_names[id as usize].clone()
     }
 
+    pub fn lookup_ids(&self, pattern: &str) -> Vec<Id> {
+        self.find_components(|name| matches_pattern(pattern, name))
+    }
+
+    pub fn subtree_ids(&self, root: &str) -> Vec<Id> {
+        self.find_components(|name| in_subtree(name, root))
+    }
+
+    fn find_components<F: Fn(&str) -> bool>(&self, pred: F) -> Vec<Id> {
+        (0..self.component_names.len() as Id)
+            .filter(|&id| pred(&self.component_names[id as usize]))
+            .collect()
+    }
+
     pub fn time(&self) -> f64 {
-        self.clock
+        time::seconds(self.clock)
+    }
+
+    pub fn time_nanos(&self) -> u64 {
+        time::nanos(self.clock)
     }
 
     pub fn set_time(&mut self, time: f64) {
-        self.clock = time;
+        self.clock = time::timestamp(time);
+    }
+
+    // Random numbers --------------------------------------------------------------------------------------------------
+
+    pub fn enable_component_rand_streams(&mut self) {
+        self.component_rand_streams = true;
+    }
+
+    pub fn create_rand_stream(&mut self, component_id: Id, name: &str) -> usize {
+        let key = (component_id, name.to_owned());
+        if let Some(&index) = self.named_rand_ids.get(&key) {
+            return index;
+        }
+        let index = self.named_rands.len();
+        let rng = derive_rng(self.seed, &[&self.component_names[component_id as usize], name]);
+        self.named_rands.push((component_id, name.to_owned(), rng));
+        self.named_rand_ids.insert(key, index);
+        index
+    }
+
+    fn rng(&mut self, stream: RandStream) -> &mut Pcg64 {
+        match stream {
+            RandStream::Component(id) if self.component_rand_streams => {
+                let idx = id as usize;
+                if self.component_rands.len() <= idx {
+                    self.component_rands.resize(idx + 1, None);
+                }
+                let (seed, name) = (self.seed, &self.component_names[idx]);
+                self.component_rands[idx].get_or_insert_with(|| derive_rng(seed, &[name]))
+            }
+            RandStream::Named(index) => &mut self.named_rands[index].2,
+            _ => &mut self.rand,
+        }
     }
 
-    pub fn rand(&mut self) -> f64 {
-        self.rand.gen_range(0.0..1.0)
+    pub fn rand(&mut self, stream: RandStream) -> f64 {
+        self.rng(stream).gen_range(0.0..1.0)
     }
 
-    pub fn gen_range<T, R>(&mut self, range: R) -> T
+    pub fn gen_range<T, R>(&mut self, stream: RandStream, range: R) -> T
     where
         T: SampleUniform,
         R: SampleRange<T>,
     {
-        self.rand.gen_range(range)
+        self.rng(stream).gen_range(range)
     }
 
-    pub fn sample_from_distribution<T, Dist: Distribution<T>>(&mut self, dist: &Dist) -> T {
-        dist.sample(&mut self.rand)
+    pub fn sample_from_distribution<T, Dist: Distribution<T>>(&mut self, stream: RandStream, dist: &Dist) -> T {
+        dist.sample(self.rng(stream))
     }
 
-    pub fn random_string(&mut self, len: usize) -> String {
-        Alphanumeric.sample_string(&mut self.rand, len)
+    pub fn random_string(&mut self, stream: RandStream, len: usize) -> String {
+        Alphanumeric.sample_string(self.rng(stream), len)
     }
 
+    // Events ----------------------------------------------------------------------------------------------------------
+
     pub fn add_event<T>(&mut self, data: T, src: Id, dst: Id, delay: f64) -> EventId
     where
         T: EventData,
     {
+        self.add_boxed_event(Box::new(data), src, dst, delay)
+    }
+
+    pub fn add_boxed_event(&mut self, data: Box<dyn EventData>, src: Id, dst: Id, delay: f64) -> EventId {
         let event_id = self.event_count;
-        let event = Event {
-            id: event_id,
-            time: self.clock + delay.max(0.),
-            src,
-            dst,
-            data: Box::new(data),
-        };
+        let event = Event::from_timestamp(event_id, time::add_delay(self.clock, delay.max(0.)), src, dst, data);
         if delay >= -EPSILON {
             self.events.push(event);
             self.event_count += 1;
@@ -180,6 +304,50 @@ impl SimulationState {
         }
     }
 
+    pub fn add_boxed_event_nanos(&mut self, data: Box<dyn EventData>, src: Id, dst: Id, delay: u64) -> EventId {
+        self.push_event(data, src, dst, time::add_delay_nanos(self.clock, delay))
+    }
+
+    // Unlike add_event, uses the specified event time as is to avoid floating-point errors.
+    pub fn add_boxed_event_at(&mut self, data: Box<dyn EventData>, src: Id, dst: Id, time: f64) -> EventId {
+        assert!(
+            time >= self.time() - EPSILON,
+            "Event time is in the past! It is not allowed to add events from the past."
+        );
+        self.push_event(data, src, dst, time::timestamp(time).max(self.clock))
+    }
+
+    // Unlike add_boxed_event_at, uses the exact event time computed by another simulation instance.
+    pub fn add_boxed_event_at_timestamp(
+        &mut self,
+        data: Box<dyn EventData>,
+        src: Id,
+        dst: Id,
+        timestamp: Timestamp,
+    ) -> EventId {
+        assert!(
+            !time::is_before(timestamp, self.clock),
+            "Event time is in the past! It is not allowed to add events from the past."
+        );
+        self.push_event(data, src, dst, timestamp.max(self.clock))
+    }
+
+    // Reserves id for event delivered by another simulation instance,
+    // returns it along with the current time and the time of event with specified delay.
+    pub fn reserve_remote_event(&mut self, delay: f64) -> (EventId, Timestamp, Timestamp) {
+        let event_id = self.event_count;
+        self.event_count += 1;
+        (event_id, self.clock, time::add_delay(self.clock, delay.max(0.)))
+    }
+
+    fn push_event(&mut self, data: Box<dyn EventData>, src: Id, dst: Id, timestamp: Timestamp) -> EventId {
+        let event_id = self.event_count;
+        self.events
+            .push(Event::from_timestamp(event_id, timestamp, src, dst, data));
+        self.event_count += 1;
+        event_id
+    }
+
     pub fn add_ordered_event<T>(&mut self, data: T, src: Id, dst: Id, delay: f64) -> EventId
     where
         T: EventData,
@@ -187,16 +355,11 @@ impl SimulationState {
         if !self.can_add_ordered_event(delay) {
             panic!("Event order is broken! Ordered events should be added in non-decreasing order of their time.");
         }
-        let last_time = self.ordered_events.back().map_or(f64::MIN, |x| x.time);
+        let last_time = self.ordered_events.back().map_or(self.clock, |x| x.time);
         let event_id = self.event_count;
-        let event = Event {
-            id: event_id,
-            // max is used to enforce time order despite the floating-point errors
-            time: last_time.max(self.clock + delay),
-            src,
-            dst,
-            data: Box::new(data),
-        };
+        // max is used to enforce time order despite the floating-point errors
+        let time = last_time.max(time::add_delay(self.clock, delay.max(0.)));
+        let event = Event::from_timestamp(event_id, time, src, dst, Box::new(data));
         if delay >= 0. {
             self.ordered_events.push_back(event);
             self.event_count += 1;
@@ -210,7 +373,7 @@ impl SimulationState {
     pub fn can_add_ordered_event(&self, delay: f64) -> bool {
         if let Some(evt) = self.ordered_events.back() {
             // small epsilon is used to account for floating-point errors
-            if delay + self.clock < evt.time - EPSILON {
+            if time::is_before(time::add_delay(self.clock, delay), evt.time) {
                 return false;
             }
         }
@@ -225,8 +388,10 @@ impl SimulationState {
                 let event = self.events.pop().unwrap();
                 if !self.canceled_events.remove(&event.id) {
                     self.clock = event.time;
+                    self.on_periodic_event_delivered(&event);
                     return Some(event);
                 }
+                self.on_periodic_event_discarded(event.id);
             } else if maybe_deque.is_some() {
                 let event = self.ordered_events.pop_front().unwrap();
                 if !self.canceled_events.remove(&event.id) {
@@ -249,6 +414,7 @@ impl SimulationState {
             if heap_event.is_some() && (deque_event.is_none() || heap_event.unwrap() > deque_event.unwrap()) {
                 if self.canceled_events.remove(&heap_event_id) {
                     self.events.pop().unwrap();
+                    self.on_periodic_event_discarded(heap_event_id);
                 } else {
                     return self.events.peek();
                 }
@@ -272,6 +438,7 @@ impl SimulationState {
     where
         F: Fn(&Event) -> bool,
     {
+        self.cancel_paused_periodic_events(&pred);
         for event in self.events.iter() {
             if pred(event) {
                 self.canceled_events.insert(event.id);
@@ -288,6 +455,7 @@ impl SimulationState {
     where
         F: Fn(&Event) -> bool,
     {
+        self.cancel_paused_periodic_events(&pred);
         let mut events = Vec::new();
         for event in self.events.iter() {
             if pred(event) {
@@ -309,6 +477,7 @@ impl SimulationState {
     where
         F: Fn(&Event) -> bool,
     {
+        self.cancel_paused_periodic_events(&pred);
         for event in self.events.iter() {
             if pred(event) {
                 self.canceled_events.insert(event.id);
@@ -320,6 +489,15 @@ impl SimulationState {
         self.event_count
     }
 
+    // Returns the number of events in the queue, including cancelled events which are not removed yet.
+    pub fn queue_size(&self) -> usize {
+        self.events.len() + self.ordered_events.len()
+    }
+
+    pub fn component_names(&self) -> &[String] {
+        &self.component_names
+    }
+
     pub fn dump_events(&self) -> Vec<Event> {
         let mut output = Vec::new();
         for event in self.events.iter() {
@@ -338,6 +516,285 @@ impl SimulationState {
         output
     }
 
+    // Periodic events -------------------------------------------------------------------------------------------------
+
+    pub fn add_periodic_event(
+        &mut self,
+        data: Box<dyn EventData>,
+        src: Id,
+        dst: Id,
+        period: f64,
+        start_delay: f64,
+    ) -> PeriodicEventId {
+        assert!(period > 0., "Period of periodic event must be positive");
+        let event_id = self.add_boxed_event(data.clone(), src, dst, start_delay);
+        let series_id = self.periodic_event_count;
+        self.periodic_event_count += 1;
+        let last_event = Event::from_timestamp(
+            event_id,
+            time::add_delay(self.clock, start_delay.max(0.)),
+            src,
+            dst,
+            data,
+        );
+        self.periodic_events.insert(
+            series_id,
+            PeriodicSeries {
+                last_event,
+                period,
+                paused: false,
+            },
+        );
+        self.periodic_event_ids.insert(event_id, series_id);
+        series_id
+    }
+
+    // Returns the series if it is not cancelled, cleans up the series if its pending occurrence was cancelled.
+    fn get_periodic_event(&mut self, series_id: PeriodicEventId) -> Option<&mut PeriodicSeries> {
+        let series = self.periodic_events.get(&series_id)?;
+        if !series.paused && self.canceled_events.contains(&series.last_event.id) {
+            self.remove_periodic_event(series_id);
+            return None;
+        }
+        self.periodic_events.get_mut(&series_id)
+    }
+
+    fn get_active_periodic_event(&mut self, series_id: PeriodicEventId) -> &mut PeriodicSeries {
+        self.get_periodic_event(series_id)
+            .unwrap_or_else(|| panic!("Periodic event {} is cancelled", series_id))
+    }
+
+    fn remove_periodic_event(&mut self, series_id: PeriodicEventId) -> Option<PeriodicSeries> {
+        let series = self.periodic_events.remove(&series_id)?;
+        self.periodic_event_ids.remove(&series.last_event.id);
+        Some(series)
+    }
+
+    // Schedules the next occurrence of periodic event
+    fn schedule_periodic_event(&mut self, series_id: PeriodicEventId, time: Timestamp) {
+        let series = self.periodic_events.get(&series_id).unwrap();
+        let (data, src, dst) = (
+            series.last_event.data.clone(),
+            series.last_event.src,
+            series.last_event.dst,
+        );
+        let event_id = self.push_event(data.clone(), src, dst, time);
+        let series = self.periodic_events.get_mut(&series_id).unwrap();
+        series.last_event = Event::from_timestamp(event_id, time, src, dst, data);
+        series.paused = false;
+        self.periodic_event_ids.insert(event_id, series_id);
+    }
+
+    fn on_periodic_event_delivered(&mut self, event: &Event) {
+        if let Some(series_id) = self.periodic_event_ids.remove(&event.id) {
+            let period = self.periodic_events[&series_id].period;
+            self.schedule_periodic_event(series_id, time::add_delay(event.time, period));
+        }
+    }
+
+    fn on_periodic_event_discarded(&mut self, event_id: EventId) {
+        if let Some(series_id) = self.periodic_event_ids.remove(&event_id) {
+            self.periodic_events.remove(&series_id);
+        }
+    }
+
+    // Paused series do not have pending events, so they are matched using the last occurrence.
+    fn cancel_paused_periodic_events<F>(&mut self, pred: &F)
+    where
+        F: Fn(&Event) -> bool,
+    {
+        self.periodic_events
+            .retain(|_, series| !series.paused || !pred(&series.last_event));
+    }
+
+    // Stops the series destined for the removed components. The pending occurrences are kept,
+    // they are handled according to the cancellation policy of handler removal.
+    pub fn stop_periodic_events_to(&mut self, removed: &FxHashSet<Id>) {
+        let periodic_event_ids = &mut self.periodic_event_ids;
+        self.periodic_events.retain(|_, series| {
+            if !removed.contains(&series.last_event.dst) {
+                return true;
+            }
+            if !series.paused {
+                periodic_event_ids.remove(&series.last_event.id);
+            }
+            false
+        });
+    }
+
+    pub fn is_periodic_event_active(&mut self, series_id: PeriodicEventId) -> bool {
+        self.get_periodic_event(series_id).is_some()
+    }
+
+    pub fn is_periodic_event_paused(&mut self, series_id: PeriodicEventId) -> bool {
+        self.get_periodic_event(series_id).is_some_and(|series| series.paused)
+    }
+
+    pub fn periodic_event_next_id(&mut self, series_id: PeriodicEventId) -> Option<EventId> {
+        self.get_periodic_event(series_id)
+            .filter(|series| !series.paused)
+            .map(|series| series.last_event.id)
+    }
+
+    pub fn periodic_event_period(&mut self, series_id: PeriodicEventId) -> f64 {
+        self.get_active_periodic_event(series_id).period
+    }
+
+    pub fn set_periodic_event_period(&mut self, series_id: PeriodicEventId, period: f64) {
+        assert!(period > 0., "Period of periodic event must be positive");
+        self.get_active_periodic_event(series_id).period = period;
+    }
+
+    pub fn pause_periodic_event(&mut self, series_id: PeriodicEventId) {
+        let series = self.get_active_periodic_event(series_id);
+        if !series.paused {
+            series.paused = true;
+            let event_id = series.last_event.id;
+            self.periodic_event_ids.remove(&event_id);
+            self.canceled_events.insert(event_id);
+        }
+    }
+
+    pub fn resume_periodic_event(&mut self, series_id: PeriodicEventId) {
+        let clock = self.clock;
+        let series = self.get_active_periodic_event(series_id);
+        if series.paused {
+            let time = time::add_delay(clock, series.period);
+            self.schedule_periodic_event(series_id, time);
+        }
+    }
+
+    pub fn cancel_periodic_event(&mut self, series_id: PeriodicEventId) {
+        if let Some(series) = self.remove_periodic_event(series_id) {
+            if !series.paused {
+                self.canceled_events.insert(series.last_event.id);
+            }
+        }
+    }
+
+    // Metrics ---------------------------------------------------------------------------------------------------------
+
+    pub fn increment_counter(&mut self, component_id: Id, name: &str, delta: u64) {
+        let component_name = &self.component_names[component_id as usize];
+        self.metrics
+            .increment_counter(component_id, component_name, name, delta);
+    }
+
+    pub fn set_gauge(&mut self, component_id: Id, name: &str, value: f64) {
+        let component_name = &self.component_names[component_id as usize];
+        self.metrics.set_gauge(component_id, component_name, name, value);
+    }
+
+    pub fn observe(&mut self, component_id: Id, name: &str, value: f64) {
+        let component_name = &self.component_names[component_id as usize];
+        self.metrics.observe(component_id, component_name, name, value);
+    }
+
+    pub fn update_time_weighted(&mut self, component_id: Id, name: &str, value: f64) {
+        let component_name = &self.component_names[component_id as usize];
+        self.metrics
+            .update_time_weighted(component_id, component_name, name, self.time(), value);
+    }
+
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot(self.time(), &self.component_names)
+    }
+
+    // Tracing ---------------------------------------------------------------------------------------------------------
+
+    pub fn enable_tracing(&mut self) {
+        if self.tracer.is_none() {
+            self.tracer = Some(Tracer::default());
+        }
+    }
+
+    pub fn trace_delivery(&mut self, event: &Event) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.record_delivery(event);
+        }
+    }
+
+    pub fn start_span(&mut self, component_id: Id, name: &str) -> SpanId {
+        let span_id = self.span_count;
+        self.span_count += 1;
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.start_span(span_id, component_id, name, time::seconds(self.clock));
+        }
+        span_id
+    }
+
+    pub fn finish_span(&mut self, span_id: SpanId) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.finish_span(span_id, time::seconds(self.clock));
+        }
+    }
+
+    pub fn trace(&self) -> Option<Value> {
+        self.tracer.as_ref().map(|tracer| tracer.to_json(&self.component_names))
+    }
+
+    // Checkpoints -----------------------------------------------------------------------------------------------------
+
+    pub fn save_checkpoint(&self) -> SimulationCheckpoint {
+        let is_pending = |e: &&Event| !self.canceled_events.contains(&e.id);
+        let mut periodic_events = self
+            .periodic_events
+            .iter()
+            .filter(|(_, series)| series.paused || is_pending(&&series.last_event))
+            .map(|(id, series)| (*id, series.clone()))
+            .collect::<Vec<_>>();
+        periodic_events.sort_by_key(|(id, _)| *id);
+        SimulationCheckpoint {
+            time: self.clock,
+            rand: self.rand.clone(),
+            component_rands: self.component_rands.clone(),
+            named_rands: self.named_rands.clone(),
+            event_count: self.event_count,
+            component_names: self.component_names.clone(),
+            events: self.events.iter().filter(is_pending).cloned().collect(),
+            ordered_events: self.ordered_events.iter().filter(is_pending).cloned().collect(),
+            periodic_events,
+            periodic_event_count: self.periodic_event_count,
+            metrics: self.metrics.clone(),
+            components: Default::default(),
+        }
+    }
+
+    pub fn restore_checkpoint(&mut self, checkpoint: &SimulationCheckpoint) {
+        assert!(
+            self.component_names.starts_with(&checkpoint.component_names),
+            "Components registered in simulation do not match the checkpoint"
+        );
+        self.clock = checkpoint.time;
+        self.rand = checkpoint.rand.clone();
+        self.component_rands = checkpoint.component_rands.clone();
+        // streams created after the checkpoint are reset to their initial state
+        for (component_id, name, rng) in self.named_rands.iter_mut() {
+            *rng = derive_rng(self.seed, &[&self.component_names[*component_id as usize], name]);
+        }
+        for (component_id, name, rng) in checkpoint.named_rands.iter() {
+            let index = self.create_rand_stream(*component_id, name);
+            self.named_rands[index].2 = rng.clone();
+        }
+        self.event_count = checkpoint.event_count;
+        self.events.clear();
+        for event in checkpoint.events.iter() {
+            self.events.push(event.clone());
+        }
+        self.ordered_events = checkpoint.ordered_events.iter().cloned().collect();
+        self.canceled_events.clear();
+        self.periodic_events.clear();
+        self.periodic_event_ids.clear();
+        for (series_id, series) in checkpoint.periodic_events.iter() {
+            if !series.paused {
+                self.periodic_event_ids.insert(series.last_event.id, *series_id);
+            }
+            self.periodic_events.insert(*series_id, series.clone());
+        }
+        self.periodic_event_count = checkpoint.periodic_event_count;
+        self.metrics = checkpoint.metrics.clone();
+    }
+
     async_mode_disabled!(
         fn on_register(&mut self) {}
         pub fn on_static_handler_removed(&mut self, _id: Id) {}
@@ -346,6 +803,45 @@ impl SimulationState {
     async_mode_enabled!(
         // Components --------------------------------------------------------------------------------------------------
 
+        pub fn has_async_activities(&mut self) -> bool {
+            self.peek_timer().is_some() || !self.event_promises.is_empty()
+        }
+
+        pub fn suspended_tasks(&self) -> Vec<SuspendedTask> {
+            let mut tasks: Vec<_> = self
+                .event_promises
+                .iter()
+                .map(|promise| SuspendedTask {
+                    component_id: Some(promise.dst),
+                    component_name: Some(self.component_names[promise.dst as usize].clone()),
+                    awaited: promise.event_type.to_string(),
+                    src: promise.src,
+                    src_name: promise.src.map(|src| self.component_names[src as usize].clone()),
+                    event_key: promise.event_key,
+                    suspended_at: promise.created_at,
+                })
+                .collect();
+            tasks.extend(self.sync_waits.values().map(|wait| SuspendedTask {
+                component_id: wait.component_id,
+                component_name: wait.component_id.map(|id| self.component_names[id as usize].clone()),
+                awaited: wait.operation.to_string(),
+                src: None,
+                src_name: None,
+                event_key: None,
+                suspended_at: wait.suspended_at,
+            }));
+            // the sort is stable, so the waits on synchronization primitives remain in the order of their start
+            tasks.sort_by(|a, b| {
+                a.suspended_at
+                    .total_cmp(&b.suspended_at)
+                    .then(a.component_id.cmp(&b.component_id))
+                    .then_with(|| a.awaited.cmp(&b.awaited))
+                    .then(a.src.cmp(&b.src))
+                    .then(a.event_key.cmp(&b.event_key))
+            });
+            tasks
+        }
+
         fn on_register(&mut self) {
             self.registered_static_handlers.push(false)
         }
@@ -364,20 +860,49 @@ impl SimulationState {
                 .map_or_else(|| false, |flag| *flag)
         }
 
+        // Waits on synchronization primitives -------------------------------------------------------------------------
+
+        pub fn set_polled_task_component(&mut self, component_id: Option<Id>) {
+            self.polled_task_component = component_id;
+        }
+
+        // Registers the wait of the currently polled task and returns the wait id.
+        pub fn add_sync_wait(&mut self, operation: &'static str) -> u64 {
+            let wait_id = self.sync_wait_count;
+            self.sync_wait_count += 1;
+            self.sync_waits.insert(
+                wait_id,
+                SyncWait {
+                    component_id: self.polled_task_component,
+                    operation,
+                    suspended_at: self.time(),
+                },
+            );
+            wait_id
+        }
+
+        pub fn remove_sync_wait(&mut self, wait_id: u64) {
+            self.sync_waits.remove(&wait_id);
+        }
+
         // Spawning async tasks ----------------------------------------------------------------------------------------
 
-        pub fn spawn(&mut self, future: impl Future<Output = ()> + 'static) {
-            Task::spawn(future, self.executor.clone());
+        pub fn spawn<T: 'static>(&mut self, future: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+            JoinHandle::spawn(future, None, self.executor.clone())
         }
 
-        pub fn spawn_component(&mut self, component_id: Id, future: impl Future<Output = ()> + 'static) {
+        pub fn spawn_component<T: 'static>(
+            &mut self,
+            component_id: Id,
+            future: impl Future<Output = T> + 'static,
+        ) -> JoinHandle<T> {
             assert!(
                 self.has_registered_static_handler(component_id),
                 "Spawning async tasks for component without registered static event handler is not supported. \
                 Register static handler for component {} before spawning tasks for it (empty impl StaticEventHandler is OK).",
                 component_id,
             );
-            Task::spawn(future, self.executor.clone());
+            JoinHandle::spawn(future, Some(component_id), self.executor.clone())
         }
 
         // Timers ------------------------------------------------------------------------------------------------------
@@ -388,7 +913,7 @@ impl SimulationState {
             timeout: f64,
             sim_state: Rc<RefCell<SimulationState>>,
         ) -> TimerFuture {
-            let timer_promise = TimerPromise::new(self.timer_count, component_id, self.time() + timeout);
+            let timer_promise = TimerPromise::new(self.timer_count, component_id, time::add_delay(self.clock, timeout));
             let timer_future = timer_promise.future(sim_state);
             self.timers.push(timer_promise);
             self.timer_count += 1;
@@ -461,7 +986,7 @@ impl SimulationState {
             key: Option<EventKey>,
             sim_state: Rc<RefCell<SimulationState>>,
         ) -> Result<EventFuture<T>, (EventFuture<T>, String)> {
-            let (promise, future) = EventPromise::contract(dst, src, key, sim_state);
+            let (promise, future) = EventPromise::contract(dst, src, key, self.time(), sim_state);
             match self.event_promises.insert::<T>(dst, src, key, promise) {
                 Ok(()) => Ok(future),
                 // return future back to the caller in order to release BorrowMut of the
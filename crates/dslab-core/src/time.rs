@@ -0,0 +1,132 @@
+//! Representation of simulation time.
+//!
+//! By default the simulation clock is a floating-point number of seconds, so the event times are computed as
+//! `current time + delay` in floating-point arithmetic. In long simulations the rounding errors accumulate,
+//! and the resulting event times (and therefore the order of events) may depend on the exact sequence of operations.
+//!
+//! The `integer_time` cargo feature switches the core clock to an integer number of nanoseconds.
+//! In this mode each delay is rounded to the nearest nanosecond before it is added to the current time, and all
+//! comparisons of event times are performed on integers, which makes long runs exactly reproducible across platforms.
+//! The public API still uses time in seconds as `f64`, which is derived from the integer clock.
+//! The exact integer values are available via [`SimulationContext::time_nanos`](crate::SimulationContext::time_nanos),
+//! [`Simulation::time_nanos`](crate::Simulation::time_nanos) and [`Event::time_nanos`](crate::Event::time_nanos),
+//! and the delays can be specified in nanoseconds via
+//! [`SimulationContext::emit_nanos`](crate::SimulationContext::emit_nanos).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use dslab_core::time::{from_nanos, to_nanos};
+//!
+//! assert_eq!(to_nanos(1.5), 1_500_000_000);
+//! assert_eq!(to_nanos(0.1 + 0.2), 300_000_000);
+//! assert_eq!(from_nanos(2_250_000_000), 2.25);
+//! ```
+
+use std::cmp::Ordering;
+
+/// Number of nanoseconds in one second.
+pub const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Whether the simulation clock is an integer number of nanoseconds (`integer_time` feature is enabled).
+pub const INTEGER_TIME: bool = cfg!(feature = "integer_time");
+
+/// Converts time in seconds to the nearest integer number of nanoseconds.
+///
+/// Negative values are converted to zero, and values exceeding the range of `u64` (including infinity)
+/// are converted to `u64::MAX`.
+pub fn to_nanos(time: f64) -> u64 {
+    (time * NANOS_PER_SECOND as f64).round() as u64
+}
+
+/// Converts integer number of nanoseconds to time in seconds.
+pub fn from_nanos(nanos: u64) -> f64 {
+    nanos as f64 / NANOS_PER_SECOND as f64
+}
+
+/// Exact representation of time instants: time in seconds by default
+/// and integer number of nanoseconds with `integer_time` feature (see the module docs).
+#[cfg(not(feature = "integer_time"))]
+pub type Timestamp = f64;
+
+/// Exact representation of time instants: time in seconds by default
+/// and integer number of nanoseconds with `integer_time` feature (see the module docs).
+#[cfg(feature = "integer_time")]
+pub type Timestamp = u64;
+
+// Converts time in seconds to timestamp.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn timestamp(time: f64) -> Timestamp {
+    time
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn timestamp(time: f64) -> Timestamp {
+    to_nanos(time)
+}
+
+// Converts timestamp to time in seconds.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn seconds(timestamp: Timestamp) -> f64 {
+    timestamp
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn seconds(timestamp: Timestamp) -> f64 {
+    from_nanos(timestamp)
+}
+
+// Returns the timestamp after the delay specified in seconds.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn add_delay(timestamp: Timestamp, delay: f64) -> Timestamp {
+    timestamp + delay
+}
+
+// The timestamp saturates at u64::MAX, so too large (or infinite) delays result in the maximum time instead of overflow.
+#[cfg(feature = "integer_time")]
+pub(crate) fn add_delay(timestamp: Timestamp, delay: f64) -> Timestamp {
+    timestamp.saturating_add(to_nanos(delay))
+}
+
+// Returns the timestamp after the delay specified in nanoseconds.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn add_delay_nanos(timestamp: Timestamp, delay: u64) -> Timestamp {
+    timestamp + from_nanos(delay)
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn add_delay_nanos(timestamp: Timestamp, delay: u64) -> Timestamp {
+    timestamp.saturating_add(delay)
+}
+
+// Returns the number of nanoseconds corresponding to timestamp.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn nanos(timestamp: Timestamp) -> u64 {
+    to_nanos(timestamp)
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn nanos(timestamp: Timestamp) -> u64 {
+    timestamp
+}
+
+// Checks whether the first timestamp is before the second one, accounting for floating-point errors if needed.
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn is_before(a: Timestamp, b: Timestamp) -> bool {
+    a < b - crate::EPSILON
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn is_before(a: Timestamp, b: Timestamp) -> bool {
+    a < b
+}
+
+#[cfg(not(feature = "integer_time"))]
+pub(crate) fn compare(a: Timestamp, b: Timestamp) -> Ordering {
+    a.total_cmp(&b)
+}
+
+#[cfg(feature = "integer_time")]
+pub(crate) fn compare(a: Timestamp, b: Timestamp) -> Ordering {
+    a.cmp(&b)
+}
@@ -0,0 +1,116 @@
+//! Export of simulation activity in Chrome Trace Event format.
+//!
+//! When tracing is enabled via [`Simulation::enable_tracing`](crate::Simulation::enable_tracing), the simulation
+//! records the delivered events and the spans started and finished by components via
+//! [`SimulationContext::start_span`](crate::SimulationContext::start_span) and
+//! [`SimulationContext::finish_span`](crate::SimulationContext::finish_span). The collected trace can be saved via
+//! [`Simulation::save_trace`](crate::Simulation::save_trace) and inspected on a timeline in
+//! [Perfetto](https://ui.perfetto.dev) or `chrome://tracing`.
+//!
+//! Each component is represented as a separate process in the trace. Event deliveries are represented as instant
+//! events of the destination component, and spans are represented as async slices, so the spans of a component
+//! are allowed to overlap. Simulation time is converted to trace time by treating one time unit as one second.
+
+use std::fs::File;
+use std::io::BufWriter;
+
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use serde_json::{json, Value};
+use serde_type_name::type_name;
+
+use crate::component::Id;
+use crate::event::Event;
+
+/// Identifier of span.
+pub type SpanId = u64;
+
+// Trace time is measured in microseconds.
+const TIME_SCALE: f64 = 1e6;
+
+#[derive(Clone, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: f64,
+    pid: Id,
+    tid: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<SpanId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<&'static str>,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    args: Value,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Tracer {
+    events: Vec<TraceEvent>,
+    // Started but not yet finished spans: span id -> (component id, span name)
+    open_spans: FxHashMap<SpanId, (Id, String)>,
+}
+
+impl Tracer {
+    pub fn record_delivery(&mut self, event: &Event) {
+        self.events.push(TraceEvent {
+            name: type_name(&event.data).unwrap_or("unknown").to_string(),
+            cat: "event",
+            ph: "i",
+            ts: event.time_seconds() * TIME_SCALE,
+            pid: event.dst,
+            tid: event.dst,
+            id: None,
+            s: Some("t"),
+            args: json!({"id": event.id, "src": event.src}),
+        });
+    }
+
+    pub fn start_span(&mut self, span_id: SpanId, component_id: Id, name: &str, time: f64) {
+        self.events.push(TraceEvent {
+            name: name.to_string(),
+            cat: "span",
+            ph: "b",
+            ts: time * TIME_SCALE,
+            pid: component_id,
+            tid: component_id,
+            id: Some(span_id),
+            s: None,
+            args: Value::Null,
+        });
+        self.open_spans.insert(span_id, (component_id, name.to_string()));
+    }
+
+    // Spans started before the tracing was enabled are ignored.
+    pub fn finish_span(&mut self, span_id: SpanId, time: f64) {
+        if let Some((component_id, name)) = self.open_spans.remove(&span_id) {
+            self.events.push(TraceEvent {
+                name,
+                cat: "span",
+                ph: "e",
+                ts: time * TIME_SCALE,
+                pid: component_id,
+                tid: component_id,
+                id: Some(span_id),
+                s: None,
+                args: Value::Null,
+            });
+        }
+    }
+
+    pub fn to_json(&self, component_names: &[String]) -> Value {
+        let mut trace_events = Vec::with_capacity(component_names.len() + self.events.len());
+        for (id, name) in component_names.iter().enumerate() {
+            trace_events.push(json!({"name": "process_name", "ph": "M", "pid": id, "args": {"name": name}}));
+        }
+        for event in self.events.iter() {
+            trace_events.push(serde_json::to_value(event).unwrap());
+        }
+        json!({"traceEvents": trace_events, "displayTimeUnit": "ms"})
+    }
+}
+
+pub(crate) fn save_trace(trace: &Value, path: &str) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+    serde_json::to_writer(BufWriter::new(file), trace).map_err(|e| format!("Failed to write trace: {}", e))
+}
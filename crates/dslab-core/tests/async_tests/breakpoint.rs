@@ -0,0 +1,40 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::Simulation;
+
+#[derive(Clone, Serialize)]
+struct Message {
+    value: u32,
+}
+
+#[test]
+fn test_breakpoint_on_awaited_event() {
+    let mut sim = Simulation::new(123);
+    let sender = sim.create_context("sender");
+    let receiver = sim.create_context("receiver");
+    let receiver_id = receiver.id();
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let task_received = received.clone();
+    sim.spawn(async move {
+        for _ in 0..2 {
+            let event = receiver.recv_event::<Message>().await;
+            task_received.borrow_mut().push(event.data.value);
+            receiver.sleep(1.).await;
+        }
+    });
+    sender.emit(Message { value: 1 }, receiver_id, 1.);
+    sender.emit(Message { value: 2 }, receiver_id, 5.);
+
+    sim.add_breakpoint(|e| e.data.downcast_ref::<Message>().is_some_and(|m| m.value == 2));
+    let hit = sim.step_until_breakpoint().unwrap();
+    assert_eq!(hit.event.time_seconds(), 5.);
+    // the timer of the task is processed before the breakpoint
+    assert_eq!(sim.time(), 2.);
+    assert_eq!(*received.borrow(), vec![1]);
+
+    assert!(sim.step_until_breakpoint().is_none());
+    assert_eq!(*received.borrow(), vec![1, 2]);
+}
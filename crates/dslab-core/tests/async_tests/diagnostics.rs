@@ -0,0 +1,174 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::async_mode::diagnostics::SuspendedTask;
+use dslab_core::{Event, Simulation, SimulationContext, StaticEventHandler};
+
+#[derive(Clone, Serialize)]
+struct Request {
+    id: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct Response {
+    id: u64,
+}
+
+fn build_stalled_simulation() -> Simulation {
+    let mut sim = Simulation::new(123);
+    sim.register_key_getter_for::<Response>(|e| e.id);
+    let client = sim.create_context("client");
+    let server = sim.create_context("server");
+    let (client_id, server_id) = (client.id(), server.id());
+
+    sim.spawn(async move {
+        client.emit(Request { id: 1 }, server_id, 1.);
+        // the server never responds
+        client.recv_event_by_key_from::<Response>(server_id, 1).await;
+    });
+    sim.spawn(async move {
+        server.recv_event_from::<Request>(client_id).await;
+        server.sleep(2.).await;
+        server.recv_event::<Request>().await;
+    });
+    sim
+}
+
+#[test]
+fn test_suspended_tasks() {
+    let mut sim = build_stalled_simulation();
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 3.);
+
+    let tasks = sim.suspended_tasks();
+    assert_eq!(
+        tasks,
+        vec![
+            SuspendedTask {
+                component_id: Some(0),
+                component_name: Some("client".to_string()),
+                awaited: std::any::type_name::<Response>().to_string(),
+                src: Some(1),
+                src_name: Some("server".to_string()),
+                event_key: Some(1),
+                suspended_at: 0.,
+            },
+            SuspendedTask {
+                component_id: Some(1),
+                component_name: Some("server".to_string()),
+                awaited: std::any::type_name::<Request>().to_string(),
+                src: None,
+                src_name: None,
+                event_key: None,
+                suspended_at: 3.,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_no_suspended_tasks_after_completion() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    let comp_id = ctx.id();
+    sim.spawn(async move {
+        ctx.emit_self(Request { id: 1 }, 1.);
+        ctx.recv_event_from::<Request>(comp_id).await;
+    });
+    sim.set_strict_stall_mode(true);
+    sim.step_until_no_events();
+    assert!(sim.suspended_tasks().is_empty());
+}
+
+#[test]
+fn test_strict_stall_mode() {
+    let mut sim = build_stalled_simulation();
+    sim.set_strict_stall_mode(true);
+    let err = catch_unwind(AssertUnwindSafe(|| sim.step_until_no_events())).unwrap_err();
+    let report = err.downcast_ref::<String>().unwrap();
+    let lines: Vec<_> = report.lines().collect();
+    assert_eq!(lines[0], "Simulation stalled at time 3.000 with 2 suspended task(s):");
+    assert!(lines[1].starts_with("  - client awaits "));
+    assert!(lines[1].ends_with("Response from server with key 1 since 0.000"));
+    assert!(lines[2].ends_with("Request since 3.000"));
+}
+
+struct Worker {
+    ctx: SimulationContext,
+}
+
+impl StaticEventHandler for Worker {
+    fn on(self: Rc<Self>, _event: Event) {}
+}
+
+#[test]
+fn test_suspended_tasks_on_primitives() {
+    let mut sim = Simulation::new(123);
+    let queue = Rc::new(sim.create_bounded_queue::<u32>(1));
+    let barrier = Rc::new(sim.create_barrier(2));
+    let ctx = sim.create_context("comp");
+    let first_barrier = barrier.clone();
+    sim.spawn(async move {
+        ctx.sleep(1.).await;
+        first_barrier.wait().await;
+    });
+    sim.spawn(async move {
+        barrier.wait().await;
+        queue.take().await;
+    });
+    sim.step_until_no_events();
+
+    // the completed wait on barrier is not reported
+    assert_eq!(
+        sim.suspended_tasks(),
+        vec![SuspendedTask {
+            component_id: None,
+            component_name: None,
+            awaited: "BoundedQueue::take".to_string(),
+            src: None,
+            src_name: None,
+            event_key: None,
+            suspended_at: 1.,
+        }]
+    );
+    assert_eq!(
+        sim.suspended_tasks()[0].to_string(),
+        "task awaits BoundedQueue::take since 1.000"
+    );
+}
+
+#[test]
+fn test_mutex_deadlock() {
+    let mut sim = Simulation::new(123);
+    sim.set_strict_stall_mode(true);
+    let first = Rc::new(sim.create_mutex(0));
+    let second = Rc::new(sim.create_mutex(0));
+    for (name, locks) in [
+        ("worker1", [first.clone(), second.clone()]),
+        ("worker2", [second, first]),
+    ] {
+        let worker = Rc::new(Worker {
+            ctx: sim.create_context(name),
+        });
+        sim.add_static_handler(name, worker.clone());
+        worker.ctx.spawn({
+            let worker = worker.clone();
+            async move {
+                let _first = locks[0].lock().await;
+                worker.ctx.sleep(1.).await;
+                let _second = locks[1].lock().await;
+            }
+        });
+    }
+
+    let err = catch_unwind(AssertUnwindSafe(|| sim.step_until_no_events())).unwrap_err();
+    let report = err.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        report,
+        "Simulation stalled at time 1.000 with 2 suspended task(s):\n  \
+        - worker1 awaits Mutex::lock since 1.000\n  \
+        - worker2 awaits Mutex::lock since 1.000"
+    );
+}
@@ -0,0 +1,48 @@
+use futures::{select, FutureExt, StreamExt};
+
+use dslab_core::Simulation;
+
+#[test]
+fn test_interval() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+
+    sim.spawn(async move {
+        let mut interval = ctx.interval(1.5);
+        assert_eq!(interval.tick().await, 1.5);
+        assert_eq!(interval.tick().await, 3.);
+
+        // missed ticks complete immediately
+        ctx.sleep(4.).await;
+        assert_eq!(interval.tick().await, 7.);
+        assert_eq!(interval.tick().await, 7.);
+        assert_eq!(interval.tick().await, 7.5);
+
+        interval.set_period(2.);
+        let ticks = interval.by_ref().take(2).collect::<Vec<_>>().await;
+        assert_eq!(ticks, vec![9., 11.]);
+        assert_eq!(interval.period(), 2.);
+    });
+
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 11.);
+}
+
+#[test]
+fn test_interval_drop() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+
+    sim.spawn(async move {
+        let mut interval = ctx.interval(1.);
+        select! {
+            _ = interval.tick().fuse() => panic!("interval should not tick before sleep"),
+            _ = ctx.sleep(0.5).fuse() => {},
+        }
+        drop(interval);
+        ctx.sleep(10.).await;
+    });
+
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 10.5);
+}
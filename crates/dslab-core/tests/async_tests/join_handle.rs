@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::async_mode::{JoinError, JoinHandle};
+use dslab_core::{cast, Event, Simulation, SimulationContext, StaticEventHandler};
+
+#[derive(Clone, Serialize)]
+struct Message {
+    value: u32,
+}
+
+struct Worker {
+    ctx: SimulationContext,
+    handler_messages: RefCell<Vec<u32>>,
+    own_task: RefCell<Option<JoinHandle<()>>>,
+}
+
+impl Worker {
+    fn new(ctx: SimulationContext) -> Self {
+        Self {
+            ctx,
+            handler_messages: RefCell::new(Vec::new()),
+            own_task: RefCell::new(None),
+        }
+    }
+
+    async fn receive(self: Rc<Self>) -> u32 {
+        self.ctx.recv_event::<Message>().await.data.value
+    }
+
+    async fn sleep(self: Rc<Self>, duration: f64) -> f64 {
+        self.ctx.sleep(duration).await;
+        self.ctx.time()
+    }
+
+    async fn abort_itself(self: Rc<Self>) {
+        self.ctx.sleep(1.).await;
+        self.own_task.borrow().as_ref().unwrap().abort();
+        self.ctx.sleep(1.).await;
+        panic!("This code must be unreachable");
+    }
+}
+
+impl StaticEventHandler for Worker {
+    fn on(self: Rc<Self>, event: Event) {
+        cast!(match event.data {
+            Message { value } => {
+                self.handler_messages.borrow_mut().push(value);
+            }
+        })
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<Worker>) {
+    let mut sim = Simulation::new(123);
+    let worker = Rc::new(Worker::new(sim.create_context("worker")));
+    sim.add_static_handler("worker", worker.clone());
+    (sim, worker)
+}
+
+#[test]
+fn test_join_result() {
+    let (mut sim, worker) = build_simulation();
+    let sender = sim.create_context("sender");
+    sender.emit(Message { value: 7 }, worker.ctx.id(), 3.);
+
+    let receive = worker.ctx.spawn(worker.clone().receive());
+    let sleep = worker.ctx.spawn(worker.clone().sleep(5.));
+    let result = Rc::new(RefCell::new(None));
+    let task_result = result.clone();
+    sim.spawn(async move {
+        let value = receive.await.unwrap();
+        let time = sleep.await.unwrap();
+        *task_result.borrow_mut() = Some((value, time));
+    });
+
+    sim.step_until_no_events();
+    assert_eq!(*result.borrow(), Some((7, 5.)));
+    assert!(worker.handler_messages.borrow().is_empty());
+}
+
+#[test]
+fn test_abort_releases_promises() {
+    let (mut sim, worker) = build_simulation();
+    let sender = sim.create_context("sender");
+    sender.emit(Message { value: 1 }, worker.ctx.id(), 3.);
+
+    let receive = worker.ctx.spawn(worker.clone().receive());
+    let sleep = worker.ctx.spawn(worker.clone().sleep(10.));
+    sim.step_until_time(1.);
+    assert!(!receive.is_finished());
+    receive.abort();
+    sleep.abort();
+    assert!(receive.is_finished());
+    assert!(sleep.is_finished());
+
+    // the event is delivered to the handler and the timer does not advance the time
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 3.);
+    assert_eq!(*worker.handler_messages.borrow(), vec![1]);
+
+    let result = Rc::new(RefCell::new(None));
+    let task_result = result.clone();
+    sim.spawn(async move {
+        *task_result.borrow_mut() = Some(receive.await);
+    });
+    sim.step_until_no_events();
+    assert_eq!(*result.borrow(), Some(Err(JoinError::Aborted)));
+}
+
+#[test]
+fn test_abort_finished_task() {
+    let (mut sim, worker) = build_simulation();
+    let sleep = worker.ctx.spawn(worker.clone().sleep(2.));
+    sim.step_until_no_events();
+    assert!(sleep.is_finished());
+    sleep.abort();
+
+    let result = Rc::new(RefCell::new(None));
+    let task_result = result.clone();
+    sim.spawn(async move {
+        *task_result.borrow_mut() = Some(sleep.await);
+    });
+    sim.step_until_no_events();
+    assert_eq!(*result.borrow(), Some(Ok(2.)));
+}
+
+#[test]
+fn test_dropped_handle_does_not_cancel_task() {
+    let (mut sim, worker) = build_simulation();
+    drop(worker.ctx.spawn(worker.clone().sleep(2.)));
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 2.);
+}
+
+#[test]
+fn test_task_aborts_itself() {
+    let (mut sim, worker) = build_simulation();
+    let handle = worker.ctx.spawn(worker.clone().abort_itself());
+    worker.own_task.replace(Some(handle));
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 1.);
+    assert!(worker.own_task.borrow().as_ref().unwrap().is_finished());
+}
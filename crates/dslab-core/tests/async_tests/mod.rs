@@ -1,7 +1,14 @@
+mod breakpoint;
 mod conflict_waiting;
+mod diagnostics;
 mod future_drop;
+mod interval;
+mod join_handle;
+mod observer;
+mod profiling;
 mod queue;
 mod recv_event;
 mod recv_event_by_key;
 mod select;
 mod sleep;
+mod sync;
@@ -0,0 +1,56 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::observer::{EventObserver, ObserverAction};
+use dslab_core::{Event, Simulation};
+
+#[derive(Clone, Serialize)]
+struct Message {
+    value: u32,
+}
+
+#[derive(Default)]
+struct Observer {
+    before: Vec<u32>,
+    after: Vec<u32>,
+}
+
+impl EventObserver for Observer {
+    fn before_delivery(&mut self, event: &mut Event) -> ObserverAction {
+        let value = event.data.downcast_ref::<Message>().unwrap().value;
+        self.before.push(value);
+        if value == 1 {
+            ObserverAction::Drop
+        } else {
+            ObserverAction::Deliver
+        }
+    }
+
+    fn after_delivery(&mut self, event: &Event) {
+        self.after.push(event.data.downcast_ref::<Message>().unwrap().value);
+    }
+}
+
+#[test]
+fn test_observer_with_awaited_events() {
+    let mut sim = Simulation::new(123);
+    let observer = Rc::new(RefCell::new(Observer::default()));
+    sim.add_observer(observer.clone());
+
+    let sender = sim.create_context("sender");
+    let receiver = sim.create_context("receiver");
+    let receiver_id = receiver.id();
+    sim.spawn(async move {
+        let event = receiver.recv_event::<Message>().await;
+        assert_eq!(event.data.value, 2);
+        assert_eq!(receiver.time(), 2.);
+    });
+    sender.emit(Message { value: 1 }, receiver_id, 1.);
+    sender.emit(Message { value: 2 }, receiver_id, 2.);
+
+    sim.step_until_no_events();
+    assert_eq!(observer.borrow().before, vec![1, 2]);
+    assert_eq!(observer.borrow().after, vec![2]);
+}
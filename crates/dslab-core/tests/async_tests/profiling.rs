@@ -0,0 +1,69 @@
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use dslab_core::{Event, Simulation, SimulationContext, StaticEventHandler};
+
+#[derive(Clone, Serialize)]
+struct Request {}
+
+struct Worker {
+    ctx: SimulationContext,
+}
+
+impl Worker {
+    async fn serve(self: Rc<Self>) {
+        for _ in 0..2 {
+            self.ctx.recv_event::<Request>().await;
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    async fn tick(self: Rc<Self>) {
+        for _ in 0..3 {
+            self.ctx.sleep(1.).await;
+        }
+    }
+}
+
+impl StaticEventHandler for Worker {
+    fn on(self: Rc<Self>, _event: Event) {}
+}
+
+#[test]
+fn test_async_profiling() {
+    let mut sim = Simulation::new(123);
+    sim.enable_profiling(1.);
+    let client = sim.create_context("client");
+    let worker = Rc::new(Worker {
+        ctx: sim.create_context("worker"),
+    });
+    let worker_id = sim.add_static_handler("worker", worker.clone());
+    worker.ctx.spawn(worker.clone().serve());
+    worker.ctx.spawn(worker.clone().tick());
+    let sim_ctx = sim.create_context("sim");
+    sim.spawn(async move {
+        sim_ctx.sleep(2.).await;
+    });
+    client.emit(Request {}, worker_id, 1.5);
+    client.emit(Request {}, worker_id, 2.5);
+    sim.step_until_no_events();
+
+    let report = sim.profiling_report().unwrap();
+    let worker_profile = report.component("worker").unwrap();
+    assert_eq!(report.components[0].component_name, "worker");
+    // the polls after receiving requests are attributed to the events
+    assert_eq!(worker_profile.event_count, 2);
+    assert_eq!(worker_profile.event_types[0].event_type, "Request");
+    assert!(worker_profile.event_types[0].wall_time >= 0.01);
+    // initial polls of 2 tasks and 3 polls after timers
+    assert_eq!(worker_profile.poll_count, 5);
+
+    // the task spawned via Simulation::spawn is polled initially and after the timer
+    let sim_profile = report.component("simulation").unwrap();
+    assert_eq!(sim_profile.component_id, None);
+    assert_eq!(sim_profile.poll_count, 2);
+    assert_eq!(sim_profile.event_count, 0);
+}
@@ -0,0 +1,248 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures::{select, FutureExt};
+
+use dslab_core::Simulation;
+
+type Log = Rc<RefCell<Vec<(u32, f64)>>>;
+
+#[test]
+fn test_semaphore_fifo() {
+    let mut sim = Simulation::new(123);
+    let semaphore = Rc::new(sim.create_semaphore(3));
+    let log = Log::default();
+
+    // task 1 requests more permits than available and blocks task 2 until enough permits are released
+    for (i, (start, permits, duration)) in [(0., 2, 10.), (1., 2, 1.), (2., 1, 1.)].into_iter().enumerate() {
+        let ctx = sim.create_context(format!("task{}", i));
+        let semaphore = semaphore.clone();
+        let log = log.clone();
+        sim.spawn(async move {
+            ctx.sleep(start).await;
+            let permit = semaphore.acquire_many(permits).await;
+            assert_eq!(permit.count(), permits);
+            log.borrow_mut().push((i as u32, ctx.time()));
+            ctx.sleep(duration).await;
+        });
+    }
+
+    sim.step_until_no_events();
+    assert_eq!(*log.borrow(), vec![(0, 0.), (1, 10.), (2, 10.)]);
+    assert_eq!(semaphore.available_permits(), 3);
+}
+
+#[test]
+fn test_semaphore_cancellation() {
+    let mut sim = Simulation::new(123);
+    let semaphore = Rc::new(sim.create_semaphore(1));
+    let log = Log::default();
+
+    let holder = semaphore.try_acquire().unwrap();
+    let mut handles = Vec::new();
+    for i in 0..3 {
+        let semaphore = semaphore.clone();
+        let log = log.clone();
+        let ctx = sim.create_context(format!("task{}", i));
+        handles.push(sim.spawn(async move {
+            let _permit = semaphore.acquire_many(1 + (i == 0) as usize).await;
+            log.borrow_mut().push((i, ctx.time()));
+            ctx.sleep(1.).await;
+        }));
+    }
+    sim.step_until_no_events();
+    assert!(log.borrow().is_empty());
+
+    // withdrawn task unblocks the following ones
+    drop(holder);
+    handles[0].abort();
+    sim.step_until_no_events();
+    assert_eq!(*log.borrow(), vec![(1, 0.), (2, 1.)]);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn test_granted_permit_returned_on_abort() {
+    let mut sim = Simulation::new(123);
+    let semaphore = Rc::new(sim.create_semaphore(0));
+    let task_semaphore = semaphore.clone();
+    let handle = sim.spawn(async move {
+        let _permit = task_semaphore.acquire().await;
+        panic!("This code must be unreachable");
+    });
+    sim.step_until_no_events();
+    // permit is granted to the task, but the task is aborted before it is resumed
+    semaphore.add_permits(1);
+    assert_eq!(semaphore.available_permits(), 0);
+    handle.abort();
+    assert_eq!(semaphore.available_permits(), 1);
+    sim.step_until_no_events();
+}
+
+#[test]
+fn test_mutex() {
+    let mut sim = Simulation::new(123);
+    let counter = Rc::new(sim.create_mutex(0));
+    for i in 0..3 {
+        let ctx = sim.create_context(format!("task{}", i));
+        let counter = counter.clone();
+        sim.spawn(async move {
+            let mut value = counter.lock().await;
+            let read = *value;
+            ctx.sleep(1.).await;
+            *value = read + 1;
+        });
+    }
+    sim.step_until_time(1.5);
+    assert!(counter.is_locked());
+    assert!(counter.try_lock().is_none());
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 3.);
+    assert_eq!(*counter.try_lock().unwrap(), 3);
+}
+
+#[test]
+fn test_barrier() {
+    let mut sim = Simulation::new(123);
+    let barrier = Rc::new(sim.create_barrier(2));
+    let log = Log::default();
+
+    for i in 0..3 {
+        let ctx = sim.create_context(format!("task{}", i));
+        let barrier = barrier.clone();
+        let log = log.clone();
+        sim.spawn(async move {
+            if i == 2 {
+                // the task withdraws from the barrier on timeout
+                select! {
+                    _ = barrier.wait().fuse() => panic!("Barrier must not be passed"),
+                    _ = ctx.sleep(0.5).fuse() => {},
+                }
+                return;
+            }
+            for round in 0..2 {
+                ctx.sleep((i + 1) as f64).await;
+                barrier.wait().await;
+                log.borrow_mut().push((i * 10 + round, ctx.time()));
+            }
+        });
+    }
+
+    sim.step_until_no_events();
+    assert_eq!(*log.borrow(), vec![(10, 2.), (0, 2.), (11, 4.), (1, 4.)]);
+}
+
+#[test]
+fn test_notify() {
+    let mut sim = Simulation::new(123);
+    let notify = Rc::new(sim.create_notify());
+    let log = Log::default();
+
+    for i in 0..3 {
+        let ctx = sim.create_context(format!("task{}", i));
+        let notify = notify.clone();
+        let log = log.clone();
+        sim.spawn(async move {
+            notify.notified().await;
+            log.borrow_mut().push((i, ctx.time()));
+        });
+    }
+    let ctx = sim.create_context("notifier");
+    let notifier = notify.clone();
+    sim.spawn(async move {
+        ctx.sleep(1.).await;
+        notifier.notify_one();
+        ctx.sleep(1.).await;
+        notifier.notify_waiters();
+        // stored notification
+        notifier.notify_one();
+        notifier.notify_one();
+    });
+    sim.step_until_no_events();
+    assert_eq!(*log.borrow(), vec![(0, 1.), (1, 2.), (2, 2.)]);
+
+    let ctx = sim.create_context("late");
+    let log_late = log.clone();
+    sim.spawn(async move {
+        notify.notified().await;
+        log_late.borrow_mut().push((3, ctx.time()));
+        notify.notified().await;
+        panic!("Only one notification is stored");
+    });
+    sim.step_until_no_events();
+    assert_eq!(log.borrow().last(), Some(&(3, 2.)));
+}
+
+#[test]
+fn test_notification_forwarded_on_abort() {
+    let mut sim = Simulation::new(123);
+    let notify = Rc::new(sim.create_notify());
+    let log = Log::default();
+
+    let mut spawn_waiter = |i: u32| {
+        let ctx = sim.create_context(format!("task{}", i));
+        let notify = notify.clone();
+        let log = log.clone();
+        let handle = sim.spawn(async move {
+            notify.notified().await;
+            log.borrow_mut().push((i, ctx.time()));
+        });
+        sim.step_until_no_events();
+        handle
+    };
+
+    // notification from notify_waiters is not passed on when the woken task is aborted
+    let handle = spawn_waiter(0);
+    notify.notify_waiters();
+    handle.abort();
+    spawn_waiter(1);
+    assert!(log.borrow().is_empty());
+
+    // notification from notify_one is passed to the next waiting task
+    let handle = spawn_waiter(2);
+    notify.notify_one();
+    handle.abort();
+    sim.step_until_no_events();
+    assert_eq!(*log.borrow(), vec![(1, 0.)]);
+}
+
+#[test]
+fn test_bounded_queue() {
+    let mut sim = Simulation::new(123);
+    let queue = Rc::new(sim.create_bounded_queue(2));
+    let log = Log::default();
+
+    assert_eq!(queue.try_put(0), Ok(()));
+    assert_eq!(queue.try_put(1), Ok(()));
+    assert_eq!(queue.try_put(2), Err(2));
+    assert_eq!(queue.len(), 2);
+
+    for i in 0..2 {
+        let ctx = sim.create_context(format!("producer{}", i));
+        let queue = queue.clone();
+        let log = log.clone();
+        sim.spawn(async move {
+            queue.put(10 + i).await;
+            log.borrow_mut().push((10 + i, ctx.time()));
+        });
+    }
+    let ctx = sim.create_context("consumer");
+    let consumer_queue = queue.clone();
+    let consumer_log = log.clone();
+    sim.spawn(async move {
+        for _ in 0..4 {
+            ctx.sleep(1.).await;
+            let item = consumer_queue.take().await;
+            consumer_log.borrow_mut().push((item, ctx.time()));
+        }
+    });
+
+    sim.step_until_no_events();
+    assert_eq!(
+        *log.borrow(),
+        vec![(0, 1.), (10, 1.), (1, 2.), (11, 2.), (10, 3.), (11, 4.)]
+    );
+    assert!(queue.is_empty());
+    assert_eq!(queue.try_take(), None);
+    assert_eq!(queue.capacity(), 2);
+}
@@ -0,0 +1,104 @@
+//! Tests of predicate-driven stepping and breakpoints.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::{cast, Event, EventHandler, Id, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize)]
+struct Request {
+    id: u32,
+}
+
+#[derive(Clone, Serialize)]
+struct Response {
+    id: u32,
+}
+
+struct Server {
+    ctx: SimulationContext,
+    processed: u32,
+}
+
+impl EventHandler for Server {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Request { id } => {
+                self.processed += 1;
+                self.ctx.emit(Response { id }, event.src, 0.5);
+            }
+        })
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Server>>, Id) {
+    let mut sim = Simulation::new(123);
+    let server = Rc::new(RefCell::new(Server {
+        ctx: sim.create_context("server"),
+        processed: 0,
+    }));
+    let server_id = sim.add_handler("server", server.clone());
+    let client = sim.create_context("client");
+    for id in 0..5 {
+        client.emit(Request { id }, server_id, id as f64);
+    }
+    (sim, server, client.id())
+}
+
+#[test]
+fn test_step_until() {
+    let (mut sim, server, _) = build_simulation();
+    let condition_server = server.clone();
+    assert!(sim.step_until(move |_| condition_server.borrow().processed == 3));
+    assert_eq!(sim.time(), 2.);
+
+    // condition which already holds does not advance the simulation
+    assert!(sim.step_until(|_| true));
+    assert_eq!(sim.time(), 2.);
+
+    assert!(!sim.step_until(|_| false));
+    assert_eq!(server.borrow().processed, 5);
+    assert_eq!(sim.time(), 4.5);
+}
+
+#[test]
+fn test_breakpoints() {
+    let (mut sim, server, client_id) = build_simulation();
+    let response = sim.add_event_breakpoint::<Response>(client_id);
+    let request = sim.add_breakpoint(|e| e.data.downcast_ref::<Request>().is_some_and(|r| r.id == 3));
+    // breakpoint for the event type which is never delivered to the client
+    sim.add_event_breakpoint::<Request>(client_id);
+
+    let hit = sim.step_until_breakpoint().unwrap();
+    assert_eq!(hit.breakpoint_id, response);
+    assert_eq!(hit.event.time_seconds(), 0.5);
+    assert_eq!(hit.event.data.downcast_ref::<Response>().unwrap().id, 0);
+    assert_eq!(sim.time(), 0.);
+
+    // the event is delivered when the simulation is continued by other methods
+    sim.step();
+    assert_eq!(sim.time(), 0.5);
+
+    sim.remove_breakpoint(response);
+    let hit = sim.step_until_breakpoint().unwrap();
+    assert_eq!(hit.breakpoint_id, request);
+    assert_eq!(hit.event.time_seconds(), 3.);
+    assert_eq!(server.borrow().processed, 3);
+
+    // the paused event does not stop the simulation again
+    assert!(sim.step_until_breakpoint().is_none());
+    assert_eq!(server.borrow().processed, 5);
+    assert_eq!(sim.time(), 4.5);
+}
+
+#[test]
+fn test_first_added_breakpoint_is_reported() {
+    let (mut sim, _, _) = build_simulation();
+    let first = sim.add_breakpoint(|e| e.time_seconds() >= 1.);
+    sim.add_breakpoint(|e| e.time_seconds() >= 1.);
+    let hit = sim.step_until_breakpoint().unwrap();
+    assert_eq!(hit.breakpoint_id, first);
+    assert_eq!(hit.event.time_seconds(), 1.);
+}
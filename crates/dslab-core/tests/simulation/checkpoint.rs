@@ -0,0 +1,140 @@
+//! Tests of simulation checkpoints.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use dslab_core::checkpoint::Checkpointable;
+use dslab_core::{cast, Event, EventHandler, Id, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Ping {
+    seq: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Tick {}
+
+struct Node {
+    peer: Id,
+    received: Vec<(f64, u64)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Node {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Ping { seq } => {
+                self.received.push((self.ctx.time(), seq));
+                let delay = self.ctx.gen_range(0.5..1.5);
+                self.ctx.emit(Ping { seq: seq + 1 }, self.peer, delay);
+            }
+            Tick {} => {
+                self.ctx.emit_ordered_self(Tick {}, 2.);
+            }
+        })
+    }
+}
+
+impl Checkpointable for Node {
+    fn save_state(&self) -> Value {
+        json!({ "received": self.received })
+    }
+
+    fn restore_state(&mut self, state: &Value) {
+        self.received = serde_json::from_value(state["received"].clone()).unwrap();
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Node>>, Rc<RefCell<Node>>) {
+    let mut sim = Simulation::new(123);
+    sim.register_event_type::<Ping>();
+    sim.register_event_type::<Tick>();
+    let node1 = Rc::new(RefCell::new(Node {
+        peer: 1,
+        received: Vec::new(),
+        ctx: sim.create_context("node1"),
+    }));
+    let node2 = Rc::new(RefCell::new(Node {
+        peer: 0,
+        received: Vec::new(),
+        ctx: sim.create_context("node2"),
+    }));
+    for (name, node) in [("node1", node1.clone()), ("node2", node2.clone())] {
+        sim.add_handler(name, node.clone());
+        sim.add_checkpointable(name, node);
+    }
+    node1.borrow().ctx.emit(Ping { seq: 0 }, 1, 0.);
+    node2.borrow().ctx.emit_ordered_self(Tick {}, 1.);
+    (sim, node1, node2)
+}
+
+fn received(node: &Rc<RefCell<Node>>) -> Vec<(f64, u64)> {
+    node.borrow().received.clone()
+}
+
+#[test]
+fn test_restore_in_memory() {
+    let (mut sim, node1, node2) = build_simulation();
+    sim.step_until_time(20.);
+    let checkpoint = sim.checkpoint();
+    assert_eq!(checkpoint.time(), 20.);
+    assert_eq!(checkpoint.pending_event_count(), 2);
+
+    sim.step_until_time(50.);
+    let expected = (received(&node1), received(&node2), sim.event_count());
+
+    // the same checkpoint can be restored several times
+    for _ in 0..2 {
+        sim.restore(&checkpoint);
+        assert_eq!(sim.time(), 20.);
+        sim.step_until_time(50.);
+        assert_eq!((received(&node1), received(&node2), sim.event_count()), expected);
+    }
+}
+
+#[test]
+fn test_cancelled_events_are_not_saved() {
+    let (mut sim, _, _) = build_simulation();
+    sim.step_until_time(10.);
+    sim.cancel_events(|e| e.data.is::<Tick>());
+    let checkpoint = sim.checkpoint();
+    assert_eq!(checkpoint.pending_event_count(), 1);
+}
+
+#[test]
+fn test_restore_from_file() {
+    let path = std::env::temp_dir().join(format!("dslab-checkpoint-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let (mut sim, node1, node2) = build_simulation();
+    sim.step_until_time(20.);
+    let checkpoint = sim.checkpoint();
+    sim.save_checkpoint(&checkpoint, path).unwrap();
+    sim.step_until_time(50.);
+
+    let (mut new_sim, new_node1, new_node2) = build_simulation();
+    let loaded = new_sim.load_checkpoint(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    new_sim.restore(&loaded);
+    new_sim.step_until_time(50.);
+
+    assert_eq!(received(&new_node1), received(&node1));
+    assert_eq!(received(&new_node2), received(&node2));
+    assert_eq!(new_sim.event_count(), sim.event_count());
+}
+
+#[test]
+fn test_save_unregistered_event_type() {
+    #[derive(Clone, Serialize)]
+    struct Unknown {}
+
+    let (mut sim, _, _) = build_simulation();
+    let ctx = sim.create_context("client");
+    ctx.emit(Unknown {}, 0, 1.);
+    let checkpoint = sim.checkpoint();
+    let path = std::env::temp_dir().join("dslab-checkpoint-unregistered.json");
+    assert!(sim.save_checkpoint(&checkpoint, path.to_str().unwrap()).is_err());
+}
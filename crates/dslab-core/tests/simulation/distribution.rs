@@ -0,0 +1,188 @@
+//! Tests of distribution specifications.
+
+use std::fs;
+
+use dslab_core::distribution::{DistributionSampler, DistributionSpec, MixtureComponent};
+use dslab_core::Simulation;
+
+const SAMPLE_COUNT: usize = 100000;
+
+fn sample(dist: &DistributionSampler) -> Vec<f64> {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    (0..SAMPLE_COUNT).map(|_| ctx.sample_from_distribution(dist)).collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn assert_mean(spec: DistributionSpec, expected: f64) {
+    let values = sample(&spec.build().unwrap());
+    let actual = mean(&values);
+    assert!(
+        (actual - expected).abs() < 0.02 * expected.abs().max(1.),
+        "{:?}: mean {}, expected {}",
+        spec,
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_means() {
+    assert_mean(DistributionSpec::Constant { value: 2.5 }, 2.5);
+    assert_mean(DistributionSpec::Uniform { min: 1., max: 3. }, 2.);
+    assert_mean(DistributionSpec::Exponential { rate: 0.5 }, 2.);
+    assert_mean(DistributionSpec::Normal { mean: -3., std_dev: 2. }, -3.);
+    // exp(mu + sigma^2 / 2)
+    assert_mean(DistributionSpec::LogNormal { mu: 0.5, sigma: 0.5 }, (0.625f64).exp());
+    // Weibull with shape 1 is exponential with mean equal to scale
+    assert_mean(DistributionSpec::Weibull { scale: 3., shape: 1. }, 3.);
+    // Weibull with shape 2: scale * sqrt(pi) / 2
+    assert_mean(
+        DistributionSpec::Weibull { scale: 2., shape: 2. },
+        std::f64::consts::PI.sqrt(),
+    );
+    // shape * scale / (shape - 1)
+    assert_mean(DistributionSpec::Pareto { scale: 1., shape: 3. }, 1.5);
+    assert_mean(
+        DistributionSpec::Empirical {
+            points: vec![(0., 0.), (10., 1.)],
+        },
+        5.,
+    );
+}
+
+#[test]
+fn test_value_ranges() {
+    let values = sample(&DistributionSpec::Pareto { scale: 2., shape: 1.5 }.build().unwrap());
+    assert!(values.iter().all(|&v| v >= 2.));
+    let values = sample(&DistributionSpec::Uniform { min: 1., max: 3. }.build().unwrap());
+    assert!(values.iter().all(|&v| (1. ..3.).contains(&v)));
+    // discrete empirical distribution
+    let values = sample(
+        &DistributionSpec::Empirical {
+            points: vec![(1., 0.25), (1., 0.25), (2., 0.25), (2., 1.)],
+        }
+        .build()
+        .unwrap(),
+    );
+    assert!(values.iter().all(|&v| (1. ..=2.).contains(&v)));
+    let ones = values.iter().filter(|&&v| v == 1.).count() as f64 / SAMPLE_COUNT as f64;
+    let twos = values.iter().filter(|&&v| v == 2.).count() as f64 / SAMPLE_COUNT as f64;
+    assert!((ones - 0.25).abs() < 0.01);
+    assert!((twos - 0.75).abs() < 0.01);
+}
+
+#[test]
+fn test_mixture() {
+    let spec = DistributionSpec::Mixture {
+        components: vec![
+            MixtureComponent {
+                weight: 3.,
+                distribution: DistributionSpec::Constant { value: 0. },
+            },
+            MixtureComponent {
+                weight: 1.,
+                distribution: DistributionSpec::Uniform { min: 10., max: 20. },
+            },
+        ],
+    };
+    let values = sample(&spec.build().unwrap());
+    let zeros = values.iter().filter(|&&v| v == 0.).count() as f64 / SAMPLE_COUNT as f64;
+    assert!((zeros - 0.75).abs() < 0.01);
+    assert!((mean(&values) - 3.75).abs() < 0.1);
+}
+
+#[test]
+fn test_deterministic() {
+    let dist = DistributionSpec::LogNormal { mu: 0., sigma: 1. }.build().unwrap();
+    assert_eq!(sample(&dist), sample(&dist));
+}
+
+#[test]
+fn test_yaml() {
+    let yaml = "
+type: mixture
+components:
+  - weight: 0.8
+    distribution: { type: exponential, rate: 2.0 }
+  - weight: 0.2
+    distribution:
+      type: empirical
+      points: [[1.0, 0.5], [3.0, 1.0]]
+";
+    let spec: DistributionSpec = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        spec,
+        DistributionSpec::Mixture {
+            components: vec![
+                MixtureComponent {
+                    weight: 0.8,
+                    distribution: DistributionSpec::Exponential { rate: 2. },
+                },
+                MixtureComponent {
+                    weight: 0.2,
+                    distribution: DistributionSpec::Empirical {
+                        points: vec![(1., 0.5), (3., 1.)],
+                    },
+                },
+            ],
+        }
+    );
+    // 0.8 * 0.5 + 0.2 * (0.5 * 1 + 0.5 * 2)
+    assert_mean(spec, 0.7);
+
+    let spec: DistributionSpec = serde_yaml::from_str("{type: log_normal, mu: 1, sigma: 0.5}").unwrap();
+    assert_eq!(spec, DistributionSpec::LogNormal { mu: 1., sigma: 0.5 });
+    assert!(serde_yaml::from_str::<DistributionSpec>("{type: gamma, shape: 1}").is_err());
+}
+
+#[test]
+fn test_empirical_file() {
+    let path = std::env::temp_dir().join("dslab_core_test_empirical_cdf.csv");
+    fs::write(&path, "0, 0\n4, 0.5\n6, 1\n").unwrap();
+    let spec = DistributionSpec::EmpiricalFile {
+        path: path.to_str().unwrap().to_string(),
+    };
+    // 0.5 * 2 + 0.5 * 5
+    assert_mean(spec, 3.5);
+    fs::remove_file(&path).unwrap();
+
+    let err = DistributionSpec::EmpiricalFile {
+        path: "missing.csv".to_string(),
+    }
+    .build()
+    .unwrap_err();
+    assert!(err.starts_with("Failed to open file missing.csv"));
+}
+
+#[test]
+fn test_invalid_specs() {
+    let invalid = vec![
+        DistributionSpec::Uniform { min: 2., max: 1. },
+        DistributionSpec::Exponential { rate: 0. },
+        DistributionSpec::Normal { mean: 0., std_dev: -1. },
+        DistributionSpec::Weibull { scale: 1., shape: 0. },
+        DistributionSpec::Pareto { scale: -1., shape: 1. },
+        DistributionSpec::Empirical { points: vec![] },
+        DistributionSpec::Empirical {
+            points: vec![(1., 0.5)],
+        },
+        DistributionSpec::Empirical {
+            points: vec![(2., 0.5), (1., 1.)],
+        },
+        DistributionSpec::Mixture { components: vec![] },
+        DistributionSpec::Mixture {
+            components: vec![MixtureComponent {
+                weight: 1.,
+                distribution: DistributionSpec::Exponential { rate: -1. },
+            }],
+        },
+    ];
+    for spec in invalid {
+        let err = spec.build().unwrap_err();
+        assert!(err.starts_with("Invalid distribution"), "{:?}: {}", spec, err);
+    }
+}
@@ -0,0 +1,170 @@
+//! Tests of event queue implementations.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+use serde::Serialize;
+
+use dslab_core::event_queue::{EventQueue, EventQueueType};
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+const QUEUE_TYPES: [EventQueueType; 3] = [
+    EventQueueType::BinaryHeap,
+    EventQueueType::Calendar,
+    EventQueueType::Ladder,
+];
+
+fn make_event(id: u64, time: f64) -> Event {
+    Event::new(id, time, 0, 0, Box::new(()))
+}
+
+// Performs random interleaved pushes and pops and returns the ids of popped events.
+fn run_random_workload(queue: &mut dyn EventQueue, seed: u64, time_gen: fn(&mut Pcg64) -> f64) -> Vec<u64> {
+    let mut rand = Pcg64::seed_from_u64(seed);
+    let mut popped = Vec::new();
+    let mut now = 0.;
+    let mut next_id = 0;
+    for _ in 0..20000 {
+        if queue.is_empty() || rand.gen_bool(0.55) {
+            queue.push(make_event(next_id, now + time_gen(&mut rand)));
+            next_id += 1;
+        } else {
+            let peeked = queue.peek().map(|e| e.id);
+            let event = queue.pop().unwrap();
+            assert_eq!(peeked, Some(event.id));
+            assert!(event.time_seconds() >= now);
+            now = event.time_seconds();
+            popped.push(event.id);
+        }
+        assert_eq!(queue.len(), next_id as usize - popped.len());
+    }
+    while let Some(event) = queue.pop() {
+        popped.push(event.id);
+    }
+    assert!(queue.is_empty());
+    popped
+}
+
+fn check_workload(time_gen: fn(&mut Pcg64) -> f64) {
+    for seed in 0..3 {
+        let expected = run_random_workload(EventQueueType::BinaryHeap.create().as_mut(), seed, time_gen);
+        for queue_type in QUEUE_TYPES {
+            let popped = run_random_workload(queue_type.create().as_mut(), seed, time_gen);
+            assert_eq!(popped, expected, "wrong order of events in {:?} queue", queue_type);
+        }
+    }
+}
+
+#[test]
+fn test_uniform_delays() {
+    check_workload(|rand| rand.gen_range(0.0..10.));
+}
+
+#[test]
+fn test_skewed_delays() {
+    check_workload(|rand| rand.gen::<f64>().powi(8) * 1000.);
+}
+
+#[test]
+fn test_delays_with_ties() {
+    check_workload(|rand| rand.gen_range(0..5) as f64);
+}
+
+#[test]
+fn test_infinite_time() {
+    for queue_type in QUEUE_TYPES {
+        let mut queue = queue_type.create();
+        // the infinite event is the only one, so the queue moves its position to it
+        queue.push(make_event(0, f64::INFINITY));
+        assert_eq!(queue.peek().map(|e| e.id), Some(0));
+        for id in 1..100 {
+            let time = if id % 2 == 0 { f64::INFINITY } else { id as f64 };
+            queue.push(make_event(id, time));
+        }
+        let mut popped = Vec::new();
+        while let Some(event) = queue.pop() {
+            popped.push(event.id);
+        }
+        let expected = (1..100)
+            .filter(|id| id % 2 != 0)
+            .chain((0..100).step_by(2))
+            .collect::<Vec<_>>();
+        assert_eq!(popped, expected, "wrong order of events in {:?} queue", queue_type);
+    }
+}
+
+#[test]
+fn test_clear_and_iter() {
+    for queue_type in QUEUE_TYPES {
+        let mut queue = queue_type.create();
+        for id in 0..1000 {
+            queue.push(make_event(id, (id % 17) as f64));
+        }
+        for _ in 0..100 {
+            queue.pop();
+        }
+        let mut ids = queue.iter().map(|e| e.id).collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 900);
+
+        let mut cloned = queue.clone();
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.pop().is_none());
+        assert_eq!(cloned.len(), 900);
+        queue.push(make_event(1000, 0.5));
+        assert_eq!(queue.pop().unwrap().id, 1000);
+        assert!(cloned.pop().is_some());
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct Timer {
+    period: f64,
+}
+
+struct Node {
+    fired: Rc<RefCell<Vec<(f64, u64)>>>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Node {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Timer { period } => {
+                self.fired.borrow_mut().push((self.ctx.time(), event.id));
+                let delay = period * self.ctx.gen_range(0.5..1.5);
+                self.ctx.emit_self(Timer { period }, delay);
+            }
+        })
+    }
+}
+
+#[test]
+fn test_simulation_with_event_queues() {
+    let run = |queue_type: EventQueueType| {
+        let mut sim = Simulation::with_event_queue(123, queue_type.create());
+        let fired = Rc::new(RefCell::new(Vec::new()));
+        for i in 0..100 {
+            let name = format!("node{}", i);
+            let node = Rc::new(RefCell::new(Node {
+                fired: fired.clone(),
+                ctx: sim.create_context(&name),
+            }));
+            node.borrow().ctx.emit_self(Timer { period: 1. + i as f64 }, 0.);
+            sim.add_handler(&name, node);
+        }
+        sim.step_until_time(100.);
+        sim.cancel_events(|e| e.src % 2 == 0);
+        sim.step_until_time(200.);
+        let result = fired.borrow().clone();
+        result
+    };
+    let expected = run(EventQueueType::BinaryHeap);
+    for queue_type in QUEUE_TYPES {
+        assert_eq!(run(queue_type), expected);
+    }
+}
@@ -0,0 +1,179 @@
+//! Tests of metrics collection.
+
+use dslab_core::metrics::{save_snapshots_csv, MetricValue, MetricsSnapshot};
+use dslab_core::Simulation;
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("dslab-metrics-{}-{}", std::process::id(), name));
+    path.to_str().unwrap().to_string()
+}
+
+// Checks that the approximate percentile is within the histogram accuracy.
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 0.01 * expected.abs(),
+        "{} is not close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_metric_types() {
+    let mut sim = Simulation::new(123);
+    let server = sim.create_context("server");
+    let client = sim.create_context("client");
+
+    for latency in [5., 1., 3., 2., 4.] {
+        server.metrics().observe("latency", latency);
+    }
+    server.metrics().increment_counter("requests", 5);
+    server.metrics().set_gauge("queue", 3.);
+    server.metrics().set_gauge("queue", 1.);
+    client.metrics().increment_counter("requests", 1);
+    server.metrics().update_time_weighted("busy", 2.);
+    sim.step_until_time(1.);
+    server.metrics().update_time_weighted("busy", 0.);
+    sim.step_until_time(2.);
+
+    let snapshot = sim.metrics_snapshot();
+    assert_eq!(snapshot.time, 2.);
+    let names = snapshot
+        .metrics
+        .iter()
+        .map(|m| (m.component.as_str(), m.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            ("client", "requests"),
+            ("server", "busy"),
+            ("server", "latency"),
+            ("server", "queue"),
+            ("server", "requests")
+        ]
+    );
+    assert_eq!(
+        snapshot.get("client", "requests"),
+        Some(&MetricValue::Counter { value: 1 })
+    );
+    assert_eq!(
+        snapshot.get("server", "requests"),
+        Some(&MetricValue::Counter { value: 5 })
+    );
+    assert_eq!(snapshot.get("server", "queue"), Some(&MetricValue::Gauge { value: 1. }));
+    let Some(MetricValue::Histogram(latency)) = snapshot.get("server", "latency") else {
+        panic!("latency should be a histogram");
+    };
+    assert_eq!((latency.count, latency.sum, latency.mean), (5, 15., 3.));
+    assert_eq!((latency.min, latency.max), (1., 5.));
+    assert_close(latency.p50, 3.);
+    assert_close(latency.p90, 5.);
+    assert_close(latency.p99, 5.);
+    assert_eq!(
+        snapshot.get("server", "busy"),
+        Some(&MetricValue::TimeWeighted {
+            value: 0.,
+            average: 1.,
+            min: 0.,
+            max: 2.,
+        })
+    );
+
+    // time-weighted average is computed up to the snapshot time
+    sim.step_until_time(4.);
+    let snapshot = sim.metrics_snapshot();
+    assert!(matches!(
+        snapshot.get("server", "busy"),
+        Some(MetricValue::TimeWeighted { average, .. }) if *average == 0.5
+    ));
+    assert!(snapshot.get("server", "unknown").is_none());
+}
+
+#[test]
+fn test_histogram() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    for i in 1..=100000 {
+        ctx.metrics().observe("latency", i as f64 / 1000.);
+        ctx.metrics().observe("delta", -(i as f64));
+    }
+    ctx.metrics().observe("delta", 0.);
+
+    let snapshot = sim.metrics_snapshot();
+    let Some(MetricValue::Histogram(latency)) = snapshot.get("comp", "latency") else {
+        panic!("latency should be a histogram");
+    };
+    assert_eq!(latency.count, 100000);
+    assert_eq!((latency.min, latency.max), (0.001, 100.));
+    assert_close(latency.mean, 50.0005);
+    assert_close(latency.p50, 50.);
+    assert_close(latency.p90, 90.);
+    assert_close(latency.p99, 99.);
+
+    // negative values and zeros are supported as well
+    let Some(MetricValue::Histogram(delta)) = snapshot.get("comp", "delta") else {
+        panic!("delta should be a histogram");
+    };
+    assert_eq!(delta.count, 100001);
+    assert_eq!((delta.min, delta.max), (-100000., 0.));
+    assert_close(delta.p50, -50000.);
+    assert_close(delta.p90, -10000.);
+    assert_close(delta.p99, -1000.);
+}
+
+#[test]
+#[should_panic(expected = "Metric requests of component comp is already registered with type counter")]
+fn test_metric_type_conflict() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    ctx.metrics().increment_counter("requests", 1);
+    ctx.metrics().set_gauge("requests", 1.);
+}
+
+#[test]
+fn test_export() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    ctx.metrics().increment_counter("requests", 1);
+    let snapshot1 = sim.metrics_snapshot();
+    sim.step_until_time(1.5);
+    ctx.metrics().increment_counter("requests", 2);
+    ctx.metrics().observe("latency", 0.5);
+    let snapshot2 = sim.metrics_snapshot();
+
+    let json_path = temp_path("snapshot.json");
+    snapshot2.save_json(&json_path).unwrap();
+    let loaded: MetricsSnapshot = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    std::fs::remove_file(&json_path).unwrap();
+    assert_eq!(loaded, snapshot2);
+
+    let csv_path = temp_path("snapshots.csv");
+    save_snapshots_csv(&[snapshot1, snapshot2], &csv_path).unwrap();
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    std::fs::remove_file(&csv_path).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "time,component,metric,type,stat,value");
+    assert_eq!(lines[1], "0,comp,requests,counter,value,1");
+    assert_eq!(lines[2], "1.5,comp,latency,histogram,count,1");
+    assert_eq!(lines[10], "1.5,comp,requests,counter,value,3");
+}
+
+#[test]
+fn test_checkpoint() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("comp");
+    ctx.metrics().increment_counter("requests", 1);
+    let checkpoint = sim.checkpoint();
+    ctx.metrics().increment_counter("requests", 1);
+    ctx.metrics().set_gauge("queue", 1.);
+
+    sim.restore(&checkpoint);
+    let snapshot = sim.metrics_snapshot();
+    assert_eq!(snapshot.metrics.len(), 1);
+    assert_eq!(
+        snapshot.get("comp", "requests"),
+        Some(&MetricValue::Counter { value: 1 })
+    );
+}
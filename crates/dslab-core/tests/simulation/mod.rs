@@ -1 +1,16 @@
+mod breakpoint;
+mod checkpoint;
+mod distribution;
 mod event_cancellation;
+mod event_queue;
+mod metrics;
+mod naming;
+mod observer;
+mod parallel;
+mod periodic;
+mod profiling;
+mod random;
+mod realtime;
+mod time;
+mod recording;
+mod trace;
@@ -0,0 +1,147 @@
+//! Tests of hierarchical component naming.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::{cast, Event, EventCancellationPolicy, EventHandler, Simulation};
+
+#[derive(Clone, Serialize)]
+struct Message {}
+
+#[derive(Default)]
+struct Receiver {
+    received: u32,
+}
+
+impl EventHandler for Receiver {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Message {} => {
+                self.received += 1;
+            }
+        })
+    }
+}
+
+#[test]
+fn test_child_context_names() {
+    let mut sim = Simulation::new(123);
+    let rack = sim.create_context("rack1");
+    let host = rack.create_child_context("host3");
+    let disk = host.create_child_context("disk0");
+
+    assert_eq!(host.name(), "rack1/host3");
+    assert_eq!(disk.name(), "rack1/host3/disk0");
+    assert_eq!(sim.lookup_id("rack1/host3/disk0"), disk.id());
+    assert_eq!(sim.lookup_name(disk.id()), "rack1/host3/disk0");
+    // creating the same child again returns the same component
+    assert_eq!(rack.create_child_context("host3").id(), host.id());
+    // the child can be registered by its full name too
+    assert_eq!(sim.create_context("rack1/host3/disk0").id(), disk.id());
+}
+
+#[test]
+fn test_lookup_by_pattern() {
+    let mut sim = Simulation::new(123);
+    let mut ids = Vec::new();
+    for rack in 0..2 {
+        let rack_ctx = sim.create_context(format!("rack{}", rack));
+        for host in 0..2 {
+            let host_ctx = rack_ctx.create_child_context(&format!("host{}", host));
+            ids.push(host_ctx.create_child_context("disk0").id());
+            ids.push(host_ctx.create_child_context("nic").id());
+        }
+    }
+    sim.create_context("rack10");
+
+    assert_eq!(sim.lookup_ids("rack1/host0/disk0"), vec![ids[4]]);
+    assert_eq!(sim.lookup_ids("rack?/*/disk*"), vec![ids[0], ids[2], ids[4], ids[6]]);
+    assert_eq!(sim.lookup_ids("rack1/**/nic"), vec![ids[5], ids[7]]);
+    assert_eq!(sim.lookup_ids("**/nic").len(), 4);
+    assert_eq!(sim.lookup_ids("rack1*").len(), 2);
+    assert_eq!(sim.lookup_ids("rack1/**").len(), 7);
+    assert!(sim.lookup_ids("rack1/host").is_empty());
+    assert!(sim.lookup_ids("rack1/*/disk0/*").is_empty());
+}
+
+#[test]
+fn test_remove_subtree() {
+    let mut sim = Simulation::new(123);
+    let client = sim.create_context("client");
+    let host = sim.create_context("host");
+    let disk = host.create_child_context("disk");
+    let other = sim.create_context("host2");
+
+    let host_receiver = Rc::new(RefCell::new(Receiver::default()));
+    let disk_receiver = Rc::new(RefCell::new(Receiver::default()));
+    let other_receiver = Rc::new(RefCell::new(Receiver::default()));
+    sim.add_handler(host.name(), host_receiver.clone());
+    sim.add_handler(disk.name(), disk_receiver.clone());
+    sim.add_handler(other.name(), other_receiver.clone());
+
+    client.emit(Message {}, host.id(), 1.0);
+    client.emit(Message {}, disk.id(), 1.0);
+    client.emit(Message {}, other.id(), 1.0);
+    disk.emit(Message {}, other.id(), 1.0);
+    sim.step_until_no_events();
+    assert_eq!(host_receiver.borrow().received, 1);
+    assert_eq!(disk_receiver.borrow().received, 1);
+    assert_eq!(other_receiver.borrow().received, 2);
+
+    client.emit(Message {}, host.id(), 1.0);
+    client.emit(Message {}, disk.id(), 1.0);
+    client.emit(Message {}, other.id(), 1.0);
+    disk.emit(Message {}, other.id(), 1.0);
+    // "host2" is not a part of "host" subtree
+    sim.remove_subtree("host", EventCancellationPolicy::All);
+    sim.step_until_no_events();
+    assert_eq!(host_receiver.borrow().received, 1);
+    assert_eq!(disk_receiver.borrow().received, 1);
+    assert_eq!(other_receiver.borrow().received, 3);
+
+    // handlers of the subtree can be added again
+    sim.add_handler(disk.name(), disk_receiver.clone());
+    client.emit(Message {}, disk.id(), 1.0);
+    sim.step_until_no_events();
+    assert_eq!(disk_receiver.borrow().received, 2);
+}
+
+#[test]
+fn test_remove_subtree_without_handlers() {
+    let mut sim = Simulation::new(123);
+    let client = sim.create_context("client");
+    let host = sim.create_context("host");
+    let disk = host.create_child_context("disk");
+    let client_receiver = Rc::new(RefCell::new(Receiver::default()));
+    sim.add_handler(client.name(), client_receiver.clone());
+
+    client.emit(Message {}, disk.id(), 1.0);
+    disk.emit(Message {}, client.id(), 1.0);
+    sim.remove_subtree("host", EventCancellationPolicy::Incoming);
+    sim.step_until_no_events();
+    // only the outgoing event of disk is delivered
+    assert_eq!(client_receiver.borrow().received, 1);
+    assert_eq!(sim.time(), 1.0);
+}
+
+#[test]
+fn test_remove_handler_keeps_children() {
+    let mut sim = Simulation::new(123);
+    let client = sim.create_context("client");
+    let host = sim.create_context("host");
+    let disk = host.create_child_context("disk");
+    let host_receiver = Rc::new(RefCell::new(Receiver::default()));
+    let disk_receiver = Rc::new(RefCell::new(Receiver::default()));
+    sim.add_handler(host.name(), host_receiver.clone());
+    sim.add_handler(disk.name(), disk_receiver.clone());
+
+    client.emit(Message {}, host.id(), 1.0);
+    client.emit(Message {}, disk.id(), 1.0);
+    // only the handler of "host" itself is removed
+    sim.remove_handler("host", EventCancellationPolicy::All);
+    sim.step_until_no_events();
+    assert_eq!(host_receiver.borrow().received, 0);
+    assert_eq!(disk_receiver.borrow().received, 1);
+}
@@ -0,0 +1,137 @@
+//! Tests of event observers.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::observer::{EventObserver, ObserverAction};
+use dslab_core::{cast, Event, EventHandler, EventId, Simulation};
+
+#[derive(Clone, Serialize)]
+struct Message {
+    value: u32,
+}
+
+#[derive(Default)]
+struct Receiver {
+    received: Vec<(f64, u32)>,
+}
+
+impl EventHandler for Receiver {
+    fn on(&mut self, event: Event) {
+        let time = event.time_seconds();
+        cast!(match event.data {
+            Message { value } => {
+                self.received.push((time, value));
+            }
+        })
+    }
+}
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+struct TestObserver {
+    name: &'static str,
+    action: fn(&Event) -> ObserverAction,
+    log: Log,
+}
+
+impl EventObserver for TestObserver {
+    fn before_delivery(&mut self, event: &mut Event) -> ObserverAction {
+        self.log.borrow_mut().push(format!("{} before {}", self.name, event.id));
+        (self.action)(event)
+    }
+
+    fn after_delivery(&mut self, event: &Event) {
+        self.log.borrow_mut().push(format!("{} after {}", self.name, event.id));
+    }
+}
+
+fn add_observer(sim: &mut Simulation, name: &'static str, action: fn(&Event) -> ObserverAction, log: &Log) -> u32 {
+    sim.add_observer(Rc::new(RefCell::new(TestObserver {
+        name,
+        action,
+        log: log.clone(),
+    })))
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Receiver>>, Vec<EventId>) {
+    let mut sim = Simulation::new(123);
+    let receiver = Rc::new(RefCell::new(Receiver::default()));
+    let receiver_id = sim.add_handler("receiver", receiver.clone());
+    let ctx = sim.create_context("sender");
+    let events = (1..=3)
+        .map(|value| ctx.emit(Message { value }, receiver_id, value as f64))
+        .collect();
+    (sim, receiver, events)
+}
+
+#[test]
+fn test_observers_order() {
+    let (mut sim, receiver, events) = build_simulation();
+    let log = Log::default();
+    add_observer(&mut sim, "first", |_| ObserverAction::Deliver, &log);
+    add_observer(
+        &mut sim,
+        "second",
+        |e| {
+            if e.id == 1 {
+                ObserverAction::Drop
+            } else {
+                ObserverAction::Deliver
+            }
+        },
+        &log,
+    );
+    let third = add_observer(&mut sim, "third", |_| ObserverAction::Deliver, &log);
+
+    sim.step();
+    sim.step();
+    sim.remove_observer(third);
+    sim.step();
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            format!("first before {}", events[0]),
+            format!("second before {}", events[0]),
+            format!("third before {}", events[0]),
+            format!("first after {}", events[0]),
+            format!("second after {}", events[0]),
+            format!("third after {}", events[0]),
+            // dropped event is not passed to the following observers
+            format!("first before {}", events[1]),
+            format!("second before {}", events[1]),
+            format!("first before {}", events[2]),
+            format!("second before {}", events[2]),
+            format!("first after {}", events[2]),
+            format!("second after {}", events[2]),
+        ]
+    );
+    assert_eq!(receiver.borrow().received, vec![(1., 1), (3., 3)]);
+}
+
+#[test]
+fn test_delay_and_mutation() {
+    struct Mutator {}
+
+    impl EventObserver for Mutator {
+        fn before_delivery(&mut self, event: &mut Event) -> ObserverAction {
+            let msg = event.data.downcast_mut::<Message>().unwrap();
+            if msg.value < 100 {
+                msg.value += 100;
+                ObserverAction::Delay(2.5)
+            } else {
+                ObserverAction::Deliver
+            }
+        }
+    }
+
+    let (mut sim, receiver, _) = build_simulation();
+    sim.add_observer(Rc::new(RefCell::new(Mutator {})));
+    sim.step_until_no_events();
+    assert_eq!(receiver.borrow().received, vec![(3.5, 101), (4.5, 102), (5.5, 103)]);
+    // delayed events are re-emitted with new ids
+    assert_eq!(sim.event_count(), 6);
+}
@@ -0,0 +1,249 @@
+//! Tests of parallel simulation with multiple partitions.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::component::Id;
+use dslab_core::parallel::{ParallelSimulation, PartitionLink, RemoteEventData, RemoteId};
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+const NODE_COUNT: usize = 12;
+const TOKEN_COUNT: usize = 5;
+const LOOKAHEAD: f64 = 1.;
+
+#[derive(Clone, Serialize)]
+struct Token {
+    id: usize,
+    hops: u32,
+}
+
+struct Node {
+    index: usize,
+    partition_count: usize,
+    log: Vec<(f64, usize, usize)>,
+    link: PartitionLink,
+    ctx: SimulationContext,
+}
+
+// Node i is placed in partition i % partition_count and has local id i / partition_count.
+fn node_address(index: usize, partition_count: usize) -> RemoteId {
+    RemoteId::new(index % partition_count, (index / partition_count) as u32)
+}
+
+impl EventHandler for Node {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Token { id, hops } => {
+                self.log.push((self.ctx.time(), self.index, id));
+                if hops > 0 {
+                    let next = (self.index * 7 + id) % NODE_COUNT;
+                    let delay = LOOKAHEAD + 0.0137 * self.index as f64 + 0.0011 * id as f64;
+                    let dst = node_address(next, self.partition_count);
+                    self.link.emit(&self.ctx, Token { id, hops: hops - 1 }, dst, delay);
+                }
+            }
+        })
+    }
+}
+
+fn run(partition_count: usize, until: f64) -> (Vec<(f64, usize, usize)>, Vec<f64>) {
+    let mut sim = ParallelSimulation::new(123, LOOKAHEAD);
+    for partition in 0..partition_count {
+        sim.add_partition(move |sim, link| {
+            let mut nodes = Vec::new();
+            for index in (partition..NODE_COUNT).step_by(partition_count) {
+                let name = format!("node{}", index);
+                let node = Rc::new(RefCell::new(Node {
+                    index,
+                    partition_count,
+                    log: Vec::new(),
+                    link: link.clone(),
+                    ctx: sim.create_context(&name),
+                }));
+                sim.add_handler(&name, node.clone());
+                if index < TOKEN_COUNT {
+                    node.borrow().ctx.emit_self(Token { id: index, hops: 50 }, 0.);
+                }
+                nodes.push(node);
+            }
+            Box::new(move |_| nodes.iter().flat_map(|n| n.borrow().log.clone()).collect::<Vec<_>>())
+        });
+    }
+    let results = sim.run_until(until);
+    let times = results.iter().map(|r| r.time).collect();
+    let mut log = results.into_iter().flat_map(|r| r.result).collect::<Vec<_>>();
+    log.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    (log, times)
+}
+
+#[test]
+fn test_same_results_as_sequential() {
+    let (expected, _) = run(1, f64::INFINITY);
+    assert_eq!(expected.len(), TOKEN_COUNT * 51);
+    for partition_count in [2, 3, 4] {
+        let (log, _) = run(partition_count, f64::INFINITY);
+        assert_eq!(log, expected);
+    }
+}
+
+#[test]
+fn test_run_until() {
+    let (expected, _) = run(1, 20.);
+    let (log, times) = run(3, 20.);
+    assert!(log.iter().all(|e| e.0 <= 20.));
+    assert_eq!(log, expected);
+    assert!(times.iter().all(|t| *t == 20.));
+}
+
+#[derive(Clone, Serialize)]
+struct Ping {
+    id: usize,
+    hops: u32,
+}
+
+#[derive(Clone, Serialize)]
+struct Ack {
+    id: usize,
+}
+
+// Received events as (time, source name, payload).
+type EventLog = Vec<(f64, String, String)>;
+
+// Passes pings to random nodes with random delays and acknowledges the received pings.
+struct Walker {
+    partition_count: usize,
+    log: EventLog,
+    // not used in sequential simulation
+    link: Option<PartitionLink>,
+    ctx: SimulationContext,
+}
+
+impl Walker {
+    fn send<T: RemoteEventData>(&self, data: T, dst: RemoteId, delay: f64) {
+        match &self.link {
+            Some(link) => link.emit(&self.ctx, data, dst, delay),
+            None => self.ctx.emit(data, dst.id, delay),
+        };
+    }
+
+    fn address(&self, id: Id) -> RemoteId {
+        match &self.link {
+            Some(link) => link.resolve(id),
+            None => RemoteId::new(0, id),
+        }
+    }
+}
+
+impl EventHandler for Walker {
+    fn on(&mut self, event: Event) {
+        let src_name = self.ctx.lookup_name(event.src);
+        cast!(match event.data {
+            Ping { id, hops } => {
+                self.log
+                    .push((self.ctx.time(), src_name, format!("ping {} {}", id, hops)));
+                if event.src != self.ctx.id() {
+                    self.send(Ack { id }, self.address(event.src), LOOKAHEAD + self.ctx.rand());
+                }
+                if hops > 0 {
+                    let next = self.ctx.gen_range(0..NODE_COUNT);
+                    let dst = match self.link {
+                        Some(_) => node_address(next, self.partition_count),
+                        None => RemoteId::new(0, next as Id),
+                    };
+                    self.send(Ping { id, hops: hops - 1 }, dst, LOOKAHEAD + self.ctx.rand());
+                }
+            }
+            Ack { id } => {
+                self.log.push((self.ctx.time(), src_name, format!("ack {}", id)));
+            }
+        })
+    }
+}
+
+fn add_walkers(
+    sim: &mut Simulation,
+    indices: impl Iterator<Item = usize>,
+    partition_count: usize,
+    link: Option<PartitionLink>,
+) -> Vec<(usize, Rc<RefCell<Walker>>)> {
+    let mut walkers = Vec::new();
+    for index in indices {
+        let name = format!("walker{}", index);
+        let walker = Rc::new(RefCell::new(Walker {
+            partition_count,
+            log: Vec::new(),
+            link: link.clone(),
+            ctx: sim.create_context(&name),
+        }));
+        sim.add_handler(&name, walker.clone());
+        if index < TOKEN_COUNT {
+            walker.borrow().ctx.emit_self(Ping { id: index, hops: 30 }, 0.);
+        }
+        walkers.push((index, walker));
+    }
+    walkers
+}
+
+#[test]
+fn test_events_match_sequential_simulation() {
+    let mut sim = Simulation::new(123);
+    sim.enable_component_rand_streams();
+    let walkers = add_walkers(&mut sim, 0..NODE_COUNT, 1, None);
+    sim.step_until_no_events();
+    let expected: Vec<EventLog> = walkers.iter().map(|(_, w)| w.borrow().log.clone()).collect();
+    let pings = expected.iter().flatten().filter(|e| e.2.starts_with("ping")).count();
+    assert_eq!(pings, TOKEN_COUNT * 31);
+
+    for partition_count in [2, 3, 5] {
+        let mut sim = ParallelSimulation::new(123, LOOKAHEAD);
+        for partition in 0..partition_count {
+            sim.add_partition(move |sim, link| {
+                let indices = (partition..NODE_COUNT).step_by(partition_count);
+                let walkers = add_walkers(sim, indices, partition_count, Some(link));
+                Box::new(move |_| {
+                    walkers
+                        .iter()
+                        .map(|(index, w)| (*index, w.borrow().log.clone()))
+                        .collect::<Vec<_>>()
+                })
+            });
+        }
+        let mut logs = vec![EventLog::new(); NODE_COUNT];
+        for result in sim.run() {
+            assert!(result.remote_events > 0);
+            for (index, log) in result.result {
+                logs[index] = log;
+            }
+        }
+        // each component receives the same events at the same times in the same order
+        assert_eq!(logs, expected);
+    }
+}
+
+fn emit_remote(delay: f64) {
+    let mut sim = ParallelSimulation::new(123, LOOKAHEAD);
+    for _ in 0..2 {
+        sim.add_partition(move |sim, link| {
+            let ctx = sim.create_context("comp");
+            if link.partition() == 0 {
+                link.emit(&ctx, Token { id: 0, hops: 0 }, RemoteId::new(1, 0), delay);
+            }
+            Box::new(|_| ())
+        });
+    }
+    sim.run();
+}
+
+#[test]
+#[should_panic(expected = "is less than lookahead")]
+fn test_delay_below_lookahead() {
+    emit_remote(LOOKAHEAD / 2.);
+}
+
+#[test]
+#[should_panic(expected = "is less than lookahead")]
+fn test_delay_slightly_below_lookahead() {
+    emit_remote(LOOKAHEAD - 1e-13);
+}
@@ -0,0 +1,191 @@
+//! Tests of periodic events.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use dslab_core::{cast, Event, EventCancellationPolicy, EventHandler, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Tick {
+    value: u32,
+}
+
+struct Component {
+    ticks: Vec<(f64, u32)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Component {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Tick { value } => {
+                self.ticks.push((self.ctx.time(), value));
+            }
+        })
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Component>>) {
+    let mut sim = Simulation::new(123);
+    let comp = Rc::new(RefCell::new(Component {
+        ticks: Vec::new(),
+        ctx: sim.create_context("comp"),
+    }));
+    sim.add_handler("comp", comp.clone());
+    (sim, comp)
+}
+
+fn tick_times(comp: &Rc<RefCell<Component>>) -> Vec<f64> {
+    comp.borrow().ticks.iter().map(|(time, _)| *time).collect()
+}
+
+#[test]
+fn test_periodic_events() {
+    let (mut sim, comp) = build_simulation();
+    let handle1 = comp.borrow().ctx.emit_periodic(Tick { value: 1 }, 1., 0.);
+    let handle2 = comp.borrow().ctx.emit_periodic(Tick { value: 2 }, 2.5, 1.);
+    assert_ne!(handle1.id(), handle2.id());
+    sim.step_until_time(6.);
+    // simultaneous occurrences are ordered by the time of their scheduling
+    assert_eq!(
+        comp.borrow().ticks,
+        vec![
+            (0., 1),
+            (1., 2),
+            (1., 1),
+            (2., 1),
+            (3., 1),
+            (3.5, 2),
+            (4., 1),
+            (5., 1),
+            (6., 2),
+            (6., 1),
+        ]
+    );
+    assert_eq!(handle1.period(), 1.);
+    assert!(handle1.next_event_id().is_some());
+}
+
+#[test]
+fn test_pause_resume_and_set_period() {
+    let (mut sim, comp) = build_simulation();
+    let handle = comp.borrow().ctx.emit_periodic(Tick { value: 0 }, 2., 2.);
+    sim.step_until_time(5.);
+    assert_eq!(tick_times(&comp), vec![2., 4.]);
+
+    handle.pause();
+    assert!(handle.is_paused());
+    assert!(handle.next_event_id().is_none());
+    assert!(!sim.step());
+
+    handle.set_period(3.);
+    handle.resume();
+    assert!(!handle.is_paused());
+    sim.step_until_time(12.);
+    assert_eq!(tick_times(&comp), vec![2., 4., 8., 11.]);
+
+    // set_period does not affect the already scheduled occurrence
+    handle.set_period(1.);
+    sim.step_until_time(16.);
+    assert_eq!(tick_times(&comp), vec![2., 4., 8., 11., 14., 15., 16.]);
+
+    handle.cancel();
+    assert!(!handle.is_active());
+    sim.step_until_no_events();
+    assert_eq!(comp.borrow().ticks.len(), 7);
+}
+
+#[test]
+fn test_cancel_events_stops_series() {
+    let (mut sim, comp) = build_simulation();
+    let handle1 = comp.borrow().ctx.emit_periodic(Tick { value: 1 }, 1., 1.);
+    let handle2 = comp.borrow().ctx.emit_periodic(Tick { value: 2 }, 1., 1.);
+    sim.step_until_time(3.);
+
+    // cancel the pending occurrence by its id
+    comp.borrow().ctx.cancel_event(handle1.next_event_id().unwrap());
+    assert!(!handle1.is_active());
+    assert!(handle2.is_active());
+
+    // paused series are cancelled by predicate too
+    handle2.pause();
+    sim.cancel_events(|e| e.data.is::<Tick>());
+    assert!(!handle2.is_active());
+
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 3.);
+    assert_eq!(comp.borrow().ticks.len(), 6);
+}
+
+#[test]
+fn test_handler_removal() {
+    let (mut sim, comp) = build_simulation();
+    let handle = comp.borrow().ctx.emit_periodic(Tick { value: 0 }, 1., 1.);
+    sim.step_until_time(2.);
+    // the series is stopped, but its pending occurrence is kept
+    sim.remove_handler("comp", EventCancellationPolicy::None);
+    assert!(!handle.is_active());
+
+    sim.add_handler("comp", comp.clone());
+    sim.step_until_no_events();
+    assert_eq!(tick_times(&comp), vec![1., 2., 3.]);
+
+    let handle = comp.borrow().ctx.emit_periodic(Tick { value: 0 }, 1., 1.);
+    sim.remove_handler("comp", EventCancellationPolicy::Incoming);
+    assert!(!handle.is_active());
+    assert!(!sim.step());
+}
+
+#[test]
+fn test_handler_removal_without_readding() {
+    let (mut sim, comp) = build_simulation();
+    let handle = comp.borrow().ctx.emit_periodic(Tick { value: 0 }, 1., 1.);
+    let paused = comp.borrow().ctx.emit_periodic(Tick { value: 1 }, 1., 1.);
+    paused.pause();
+    sim.step_until_time(2.);
+    sim.remove_handler("comp", EventCancellationPolicy::None);
+    assert!(!handle.is_active());
+    assert!(!paused.is_active());
+
+    // the pending occurrence is not delivered and the series is not rescheduled
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 3.);
+    assert_eq!(tick_times(&comp), vec![1., 2.]);
+}
+
+#[test]
+fn test_checkpoint() {
+    let (mut sim, comp) = build_simulation();
+    sim.register_event_type::<Tick>();
+    let handle1 = comp.borrow().ctx.emit_periodic(Tick { value: 1 }, 1., 1.);
+    let handle2 = comp.borrow().ctx.emit_periodic(Tick { value: 2 }, 1.5, 0.);
+    sim.step_until_time(2.);
+    handle2.pause();
+    let checkpoint = sim.checkpoint();
+    assert_eq!(checkpoint.pending_event_count(), 1);
+
+    sim.step_until_time(4.);
+    handle1.cancel();
+    handle2.resume();
+    sim.step_until_time(5.);
+
+    let path = std::env::temp_dir().join(format!("dslab-checkpoint-periodic-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    sim.save_checkpoint(&checkpoint, path).unwrap();
+    let loaded = sim.load_checkpoint(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    for checkpoint in [checkpoint, loaded] {
+        sim.restore(&checkpoint);
+        comp.borrow_mut().ticks.clear();
+        assert!(handle1.is_active());
+        assert!(handle2.is_paused());
+        sim.step_until_time(4.);
+        assert_eq!(tick_times(&comp), vec![3., 4.]);
+        handle2.resume();
+        sim.step_until_time(6.);
+        assert_eq!(tick_times(&comp), vec![3., 4., 5., 5.5, 6.]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Tests of event processing profiling.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize)]
+struct Light {}
+
+#[derive(Clone, Serialize)]
+struct Heavy {}
+
+struct Worker {
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Worker {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Light {} => {}
+            Heavy {} => {
+                thread::sleep(Duration::from_millis(5));
+                self.ctx.emit_self(Light {}, 1.);
+            }
+        })
+    }
+}
+
+fn build_simulation(profiling: bool) -> Simulation {
+    let mut sim = Simulation::new(123);
+    if profiling {
+        sim.enable_profiling(5.);
+    }
+    let client = sim.create_context("client");
+    for name in ["fast", "slow"] {
+        let ctx = sim.create_context(name);
+        sim.add_handler(name, Rc::new(RefCell::new(Worker { ctx })));
+    }
+    let fast_id = sim.lookup_id("fast");
+    let slow_id = sim.lookup_id("slow");
+    for i in 0..10 {
+        client.emit(Light {}, fast_id, i as f64);
+    }
+    for i in 0..3 {
+        client.emit(Heavy {}, slow_id, i as f64 * 10.);
+    }
+    sim
+}
+
+#[test]
+fn test_disabled() {
+    let mut sim = build_simulation(false);
+    sim.step_until_no_events();
+    assert!(sim.profiling_report().is_none());
+}
+
+#[test]
+fn test_report() {
+    let mut sim = build_simulation(true);
+    sim.step_until_no_events();
+    let report = sim.profiling_report().unwrap();
+
+    assert_eq!(report.event_count, 16);
+    assert_eq!(report.components.len(), 2);
+    // components are sorted by wall time
+    let slow = &report.components[0];
+    assert_eq!(slow.component_name, "slow");
+    assert_eq!(slow.component_id, Some(sim.lookup_id("slow")));
+    assert_eq!(slow.event_count, 6);
+    assert!(slow.wall_time >= 0.015);
+    assert_eq!(slow.event_types.len(), 2);
+    assert_eq!(slow.event_types[0].event_type, "Heavy");
+    assert_eq!(slow.event_types[0].event_count, 3);
+    assert_eq!(slow.event_types[1].event_type, "Light");
+    assert_eq!(slow.event_types[1].event_count, 3);
+    assert_eq!(slow.poll_count, 0);
+
+    let fast = report.component("fast").unwrap();
+    assert_eq!(fast.event_count, 10);
+    assert_eq!(fast.event_types.len(), 1);
+    assert!(report.wall_time >= slow.wall_time + fast.wall_time - 1e-9);
+
+    // queue size is sampled at times 0, 5, 10, 20 (no events between 10 and 20)
+    let sample_times: Vec<f64> = report.queue_size.iter().map(|(time, _)| *time).collect();
+    assert_eq!(sample_times, vec![0., 5., 10., 20.]);
+    // 13 initial events minus the first delivered one
+    assert_eq!(report.queue_size[0].1, 12);
+    assert_eq!(report.max_queue_size, 12);
+
+    let text = report.to_string();
+    assert!(text.starts_with("Processed 16 events"));
+    assert!(text.find("slow").unwrap() < text.find("fast").unwrap());
+    assert!(text.contains("  Heavy"));
+}
+
+#[test]
+fn test_enabled_later() {
+    let mut sim = build_simulation(false);
+    sim.step_until_time(10.);
+    sim.enable_profiling(100.);
+    sim.step_until_no_events();
+    let report = sim.profiling_report().unwrap();
+    // events up to time 10 are processed before
+    assert!(report.component("fast").is_none());
+    assert_eq!(report.component("slow").unwrap().event_count, 3);
+    assert_eq!(report.queue_size.len(), 1);
+}
@@ -0,0 +1,111 @@
+//! Tests of per-component random streams.
+
+use dslab_core::Simulation;
+
+fn draw_values(sim: &mut Simulation, comp_name: &str) -> Vec<u64> {
+    let ctx = sim.create_context(comp_name);
+    (0..5).map(|_| ctx.gen_range(0..1000000)).collect()
+}
+
+#[test]
+fn test_global_stream_by_default() {
+    let mut sim1 = Simulation::new(123);
+    let mut sim2 = Simulation::new(123);
+    sim2.rand();
+    assert_ne!(draw_values(&mut sim1, "comp"), draw_values(&mut sim2, "comp"));
+}
+
+#[test]
+fn test_component_streams() {
+    let mut sim1 = Simulation::new(123);
+    sim1.enable_component_rand_streams();
+    let values1 = draw_values(&mut sim1, "comp");
+
+    // extra components and random draws do not affect the component stream
+    let mut sim2 = Simulation::new(123);
+    sim2.enable_component_rand_streams();
+    sim2.rand();
+    let other_values = draw_values(&mut sim2, "other");
+    let values2 = draw_values(&mut sim2, "comp");
+    assert_eq!(values1, values2);
+    assert_ne!(values1, other_values);
+
+    // streams depend on the simulation seed
+    let mut sim3 = Simulation::new(124);
+    sim3.enable_component_rand_streams();
+    assert_ne!(values1, draw_values(&mut sim3, "comp"));
+}
+
+#[test]
+fn test_named_streams() {
+    let mut sim = Simulation::new(123);
+    let ctx1 = sim.create_context("comp1");
+    let ctx2 = sim.create_context("comp2");
+
+    let arrivals = ctx1.random_stream("arrivals");
+    let first = arrivals.gen_range(0..1000000);
+    // handles with the same name share the stream state
+    let second = ctx1.random_stream("arrivals").gen_range(0..1000000);
+
+    let mut sim = Simulation::new(123);
+    let ctx1 = sim.create_context("comp1");
+    sim.rand();
+    ctx1.rand();
+    let arrivals = ctx1.random_stream("arrivals");
+    assert_eq!(arrivals.gen_range(0..1000000), first);
+    assert_eq!(arrivals.gen_range(0..1000000), second);
+
+    let services = ctx1.random_stream("services").gen_range(0..1000000);
+    let other_arrivals = ctx2.random_stream("arrivals").gen_range(0..1000000);
+    assert_ne!(services, first);
+    assert_ne!(other_arrivals, first);
+}
+
+// FNV-1a hash of stream key as it is computed without the seed.
+fn key_hash(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (i, part) in parts.iter().enumerate() {
+        let separator = if i > 0 { vec![0xff] } else { vec![] };
+        for byte in separator.into_iter().chain(part.bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+#[test]
+fn test_named_streams_with_related_seeds() {
+    let draws = |seed: u64, component: &str| {
+        let mut sim = Simulation::new(seed);
+        let stream = sim.create_context(component).random_stream("arrivals");
+        (0..3).map(|_| stream.gen_range(0..u64::MAX)).collect::<Vec<_>>()
+    };
+    // the streams would coincide if the seed was combined with the key hash via XOR
+    let related_seed = 123 ^ key_hash(&["comp1", "arrivals"]) ^ key_hash(&["comp2", "arrivals"]);
+    assert_ne!(draws(123, "comp1"), draws(related_seed, "comp2"));
+}
+
+#[test]
+fn test_checkpoint() {
+    let mut sim = Simulation::new(123);
+    sim.enable_component_rand_streams();
+    let ctx = sim.create_context("comp");
+    let stream = ctx.random_stream("stream");
+    ctx.rand();
+    stream.rand();
+    let checkpoint = sim.checkpoint();
+
+    let expected = (ctx.rand(), stream.rand(), ctx.random_stream("late").rand());
+
+    let path = std::env::temp_dir().join(format!("dslab-checkpoint-random-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    sim.save_checkpoint(&checkpoint, path).unwrap();
+    let loaded = sim.load_checkpoint(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    for checkpoint in [checkpoint, loaded] {
+        sim.restore(&checkpoint);
+        assert_eq!((ctx.rand(), stream.rand(), ctx.random_stream("late").rand()), expected);
+    }
+}
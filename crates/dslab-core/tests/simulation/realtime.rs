@@ -0,0 +1,114 @@
+//! Tests of real-time execution mode.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use dslab_core::{cast, Event, EventHandler, Simulation};
+
+#[derive(Clone, Serialize)]
+struct Message {
+    value: u32,
+}
+
+#[derive(Default)]
+struct Receiver {
+    received: Vec<(f64, u32)>,
+}
+
+impl EventHandler for Receiver {
+    fn on(&mut self, event: Event) {
+        let time = event.time_seconds();
+        cast!(match event.data {
+            Message { value } => {
+                self.received.push((time, value));
+            }
+        })
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Receiver>>, u32) {
+    let mut sim = Simulation::new(123);
+    let receiver = Rc::new(RefCell::new(Receiver::default()));
+    let receiver_id = sim.add_handler("receiver", receiver.clone());
+    (sim, receiver, receiver_id)
+}
+
+#[test]
+fn test_pacing() {
+    let (mut sim, receiver, receiver_id) = build_simulation();
+    let ctx = sim.create_context("sender");
+    for value in 1..=4 {
+        ctx.emit(Message { value }, receiver_id, value as f64);
+    }
+
+    sim.enable_realtime_mode(40.);
+    assert_eq!(sim.realtime_speed(), Some(40.));
+    let start = Instant::now();
+    sim.steps(2);
+    let elapsed = start.elapsed().as_secs_f64();
+    assert!(elapsed >= 0.05, "elapsed {}", elapsed);
+
+    // simulation time is kept when the mode is switched
+    sim.disable_realtime_mode();
+    assert_eq!(sim.realtime_speed(), None);
+    sim.step_until_no_events();
+    assert_eq!(receiver.borrow().received.len(), 4);
+    assert_eq!(sim.time(), 4.);
+}
+
+#[test]
+fn test_step_until_time_waits_without_events() {
+    let (mut sim, _, _) = build_simulation();
+    sim.enable_realtime_mode(100.);
+    let start = Instant::now();
+    assert!(!sim.step_for_duration(5.));
+    assert!(start.elapsed().as_secs_f64() >= 0.05);
+    assert_eq!(sim.time(), 5.);
+}
+
+#[test]
+fn test_external_events() {
+    let (mut sim, receiver, receiver_id) = build_simulation();
+    let sender_id = sim.create_context("sender").id();
+    let sender = sim.external_event_sender();
+    let thread = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        sender.send(Message { value: 1 }, sender_id, receiver_id).unwrap();
+    });
+
+    sim.enable_realtime_mode(100.);
+    sim.step_until_time(20.);
+    thread.join().unwrap();
+
+    let received = &receiver.borrow().received;
+    assert_eq!(received.len(), 1);
+    // the event time corresponds to the wall-clock time of its receipt
+    assert!(
+        received[0].0 >= 5. && received[0].0 < 20.,
+        "event time {}",
+        received[0].0
+    );
+    assert_eq!(sim.time(), 20.);
+}
+
+#[test]
+fn test_external_events_without_realtime() {
+    let (mut sim, receiver, receiver_id) = build_simulation();
+    let sender_id = sim.create_context("sender").id();
+    sim.step_until_time(3.);
+    let sender = sim.external_event_sender();
+    thread::spawn(move || {
+        for value in 0..2 {
+            sender.send(Message { value }, sender_id, receiver_id).unwrap();
+        }
+    })
+    .join()
+    .unwrap();
+
+    sim.step_until_no_events();
+    assert_eq!(receiver.borrow().received, vec![(3., 0), (3., 1)]);
+}
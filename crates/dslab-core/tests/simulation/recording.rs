@@ -0,0 +1,122 @@
+//! Tests of event recording and trace verification.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::recording::{read_event_trace, FileEventRecorder, TraceVerifier};
+use dslab_core::{cast, Event, EventHandler, Id, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize)]
+struct Request {
+    size: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct Response {
+    size: u64,
+}
+
+struct Client {
+    server: Id,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Client {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Response { size } => {
+                if size < 10 {
+                    self.ctx.emit(Request { size: size + 1 }, self.server, self.ctx.rand());
+                }
+            }
+        })
+    }
+}
+
+struct Server {
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Server {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Request { size } => {
+                self.ctx.emit(Response { size }, event.src, size as f64 * 0.1);
+            }
+        })
+    }
+}
+
+fn run(sim: &mut Simulation) {
+    let server = Rc::new(RefCell::new(Server {
+        ctx: sim.create_context("server"),
+    }));
+    let server_id = sim.add_handler("server", server);
+    let client_ctx = sim.create_context("client");
+    client_ctx.emit(Request { size: 0 }, server_id, 0.);
+    let client = Rc::new(RefCell::new(Client {
+        server: server_id,
+        ctx: client_ctx,
+    }));
+    sim.add_handler("client", client);
+    sim.step_until_no_events();
+}
+
+fn trace_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("dslab-trace-{}-{}.jsonl", name, std::process::id()));
+    path.to_str().unwrap().to_string()
+}
+
+fn record(seed: u64, path: &str) {
+    let mut sim = Simulation::new(seed);
+    sim.set_event_recorder(Rc::new(RefCell::new(FileEventRecorder::new(path).unwrap())));
+    run(&mut sim);
+}
+
+#[test]
+fn test_record_trace() {
+    let path = trace_path("record");
+    record(123, &path);
+    let records = read_event_trace(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(records.len(), 22);
+    assert_eq!(records[0].type_name, "Request");
+    assert_eq!(records[1].type_name, "Response");
+    assert_eq!(records[21].data["size"], 10);
+    assert!(records.windows(2).all(|w| w[0].time <= w[1].time));
+}
+
+#[test]
+fn test_verify_same_run() {
+    let path = trace_path("same");
+    record(123, &path);
+    let mut sim = Simulation::new(123);
+    let verifier = Rc::new(RefCell::new(TraceVerifier::from_file(&path).unwrap()));
+    std::fs::remove_file(&path).unwrap();
+    sim.set_event_recorder(verifier.clone());
+    run(&mut sim);
+
+    assert!(verifier.borrow_mut().finish().is_none());
+    assert_eq!(verifier.borrow().verified_count(), 22);
+}
+
+#[test]
+fn test_verify_diverged_run() {
+    let path = trace_path("diverged");
+    record(123, &path);
+    let mut sim = Simulation::new(124);
+    let verifier = Rc::new(RefCell::new(TraceVerifier::from_file(&path).unwrap()));
+    std::fs::remove_file(&path).unwrap();
+    sim.set_event_recorder(verifier.clone());
+    run(&mut sim);
+
+    // random delays start to differ after the first response
+    let mut verifier = verifier.borrow_mut();
+    let divergence = verifier.finish().unwrap();
+    assert_eq!(divergence.position, 2);
+    assert_eq!(divergence.expected.as_ref().unwrap().type_name, "Request");
+    assert_ne!(divergence.expected, divergence.actual);
+}
@@ -0,0 +1,172 @@
+//! Tests of time representation, including the integer time mode.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use dslab_core::time::{from_nanos, to_nanos, INTEGER_TIME, NANOS_PER_SECOND};
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Tick {
+    value: u32,
+}
+
+struct Component {
+    ticks: Vec<(f64, u32)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Component {
+    fn on(&mut self, event: Event) {
+        let time = event.time_seconds();
+        cast!(match event.data {
+            Tick { value } => {
+                self.ticks.push((time, value));
+            }
+        })
+    }
+}
+
+fn build_simulation() -> (Simulation, Rc<RefCell<Component>>) {
+    let mut sim = Simulation::new(123);
+    let comp = Rc::new(RefCell::new(Component {
+        ticks: Vec::new(),
+        ctx: sim.create_context("comp"),
+    }));
+    sim.add_handler("comp", comp.clone());
+    (sim, comp)
+}
+
+#[test]
+fn test_conversions() {
+    assert_eq!(to_nanos(0.), 0);
+    assert_eq!(to_nanos(1.), NANOS_PER_SECOND);
+    assert_eq!(to_nanos(1e-9), 1);
+    assert_eq!(to_nanos(0.4e-9), 0);
+    assert_eq!(to_nanos(0.1 + 0.2), 300_000_000);
+    assert_eq!(to_nanos(-1.), 0);
+    assert_eq!(to_nanos(f64::INFINITY), u64::MAX);
+    assert_eq!(from_nanos(1), 1e-9);
+    assert_eq!(from_nanos(3_600 * NANOS_PER_SECOND), 3600.);
+    assert_eq!(INTEGER_TIME, cfg!(feature = "integer_time"));
+    assert_eq!(Event::new(0, 1.25, 0, 0, Box::new(())).time_nanos(), 1_250_000_000);
+}
+
+#[test]
+fn test_time_nanos() {
+    let (mut sim, comp) = build_simulation();
+    let ctx = sim.create_context("client");
+    let comp_id = comp.borrow().ctx.id();
+    ctx.emit(Tick { value: 1 }, comp_id, 1.25);
+    ctx.emit_nanos(Tick { value: 2 }, comp_id, 2_500_000_000);
+    sim.step();
+    assert_eq!(sim.time(), 1.25);
+    assert_eq!(sim.time_nanos(), 1_250_000_000);
+    assert_eq!(ctx.time_nanos(), 1_250_000_000);
+    sim.step();
+    assert_eq!(ctx.time(), 2.5);
+    assert_eq!(ctx.time_nanos(), 2_500_000_000);
+    assert_eq!(ctx.to_nanos(ctx.time()), ctx.time_nanos());
+    assert_eq!(ctx.from_nanos(ctx.time_nanos()), ctx.time());
+    assert_eq!(comp.borrow().ticks, vec![(1.25, 1), (2.5, 2)]);
+}
+
+#[test]
+fn test_large_delays() {
+    let (mut sim, comp) = build_simulation();
+    let ctx = sim.create_context("client");
+    let comp_id = comp.borrow().ctx.id();
+    ctx.emit(Tick { value: 1 }, comp_id, 1.);
+    sim.step();
+    // the delays exceeding the range of integer time do not overflow the clock
+    ctx.emit(Tick { value: 2 }, comp_id, f64::INFINITY);
+    ctx.emit(Tick { value: 3 }, comp_id, 1e20);
+    ctx.emit_nanos(Tick { value: 4 }, comp_id, u64::MAX);
+    ctx.emit(Tick { value: 5 }, comp_id, 1.);
+    sim.step_until_time(100.);
+    assert_eq!(comp.borrow().ticks, vec![(1., 1), (2., 5)]);
+}
+
+#[test]
+fn test_step_until_time() {
+    let (mut sim, comp) = build_simulation();
+    comp.borrow().ctx.emit_self(Tick { value: 1 }, 0.1 + 0.2);
+    sim.step_until_time(0.3);
+    assert_eq!(sim.time_nanos(), 300_000_000);
+    // with integer time the event is delivered since its time is exactly 0.3 s
+    assert_eq!(comp.borrow().ticks.len(), if INTEGER_TIME { 1 } else { 0 });
+}
+
+#[test]
+fn test_accumulated_time() {
+    let (mut sim, comp) = build_simulation();
+    let ctx = sim.create_context("client");
+    let comp_id = comp.borrow().ctx.id();
+    for _ in 0..100000 {
+        ctx.emit(Tick { value: 0 }, comp_id, 0.1);
+        sim.step();
+    }
+    if INTEGER_TIME {
+        assert_eq!(sim.time_nanos(), 10_000 * NANOS_PER_SECOND);
+        assert_eq!(sim.time(), 10_000.);
+    } else {
+        // floating-point errors are accumulated by repeated additions
+        assert!((sim.time() - 10_000.).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_simultaneous_events() {
+    let (mut sim, comp) = build_simulation();
+    let ctx = sim.create_context("client");
+    let comp_id = comp.borrow().ctx.id();
+    ctx.emit(Tick { value: 1 }, comp_id, 0.1);
+    sim.step();
+    ctx.emit(Tick { value: 2 }, comp_id, 0.2);
+    ctx.emit(Tick { value: 3 }, comp_id, 0.1);
+    ctx.emit(Tick { value: 4 }, comp_id, 0.2);
+    sim.step_until_no_events();
+    let values: Vec<u32> = comp.borrow().ticks.iter().map(|(_, value)| *value).collect();
+    if INTEGER_TIME {
+        // events with equal integer times are delivered in the order of their creation
+        assert_eq!(values, vec![1, 3, 2, 4]);
+        assert_eq!(sim.time_nanos(), 300_000_000);
+    } else {
+        assert_eq!(values.len(), 4);
+    }
+}
+
+#[test]
+fn test_checkpoint_file() {
+    let (mut sim, comp) = build_simulation();
+    sim.register_event_type::<Tick>();
+    comp.borrow().ctx.emit_self(Tick { value: 1 }, 100. + 1e-9);
+    comp.borrow().ctx.emit_periodic(Tick { value: 2 }, 0.3, 0.1);
+    sim.step();
+    let checkpoint = sim.checkpoint();
+    let path = std::env::temp_dir().join(format!("dslab-time-checkpoint-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    sim.save_checkpoint(&checkpoint, path).unwrap();
+    let checkpoint = sim.load_checkpoint(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    sim.step_until_time(100.);
+    let expected_nanos = sim.time_nanos();
+    let expected_ticks = comp.borrow().ticks.clone();
+    sim.step();
+    let last_nanos = sim.time_nanos();
+
+    sim.restore(&checkpoint);
+    comp.borrow_mut().ticks.truncate(1);
+    assert_eq!(sim.time_nanos(), 100_000_000);
+    sim.step_until_time(100.);
+    assert_eq!(sim.time_nanos(), expected_nanos);
+    assert_eq!(comp.borrow().ticks, expected_ticks);
+    sim.step();
+    assert_eq!(sim.time_nanos(), last_nanos);
+    if INTEGER_TIME {
+        assert_eq!(last_nanos, 100 * NANOS_PER_SECOND + 1);
+    }
+}
@@ -0,0 +1,110 @@
+//! Tests of trace export.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use dslab_core::trace::SpanId;
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+#[derive(Clone, Serialize)]
+struct Start {
+    task: u32,
+}
+
+#[derive(Clone, Serialize)]
+struct Finish {
+    span: SpanId,
+}
+
+struct Worker {
+    ctx: SimulationContext,
+}
+
+impl EventHandler for Worker {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Start { task } => {
+                let span = self.ctx.start_span(&format!("task {}", task));
+                self.ctx.emit_self(Finish { span }, 2.);
+            }
+            Finish { span } => {
+                self.ctx.finish_span(span);
+            }
+        })
+    }
+}
+
+fn run(enable_tracing: bool) -> Simulation {
+    let mut sim = Simulation::new(123);
+    if enable_tracing {
+        sim.enable_tracing();
+    }
+    let client = sim.create_context("client");
+    let worker_ctx = sim.create_context("worker");
+    let worker_id = sim.add_handler("worker", Rc::new(RefCell::new(Worker { ctx: worker_ctx })));
+    client.emit(Start { task: 1 }, worker_id, 1.);
+    client.emit(Start { task: 2 }, worker_id, 2.);
+    sim.step_until_no_events();
+    sim
+}
+
+fn events_with_phase<'a>(trace: &'a Value, phase: &str) -> Vec<&'a Value> {
+    trace["traceEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|e| e["ph"] == phase)
+        .collect()
+}
+
+#[test]
+fn test_tracing_disabled() {
+    let sim = run(false);
+    assert!(sim.trace().is_none());
+    assert!(sim.save_trace("trace.json").is_err());
+}
+
+#[test]
+fn test_trace() {
+    let sim = run(true);
+    let trace = sim.trace().unwrap();
+
+    let processes = events_with_phase(&trace, "M");
+    assert_eq!(processes.len(), 2);
+    assert_eq!(processes[1]["pid"], 1);
+    assert_eq!(processes[1]["args"]["name"], "worker");
+
+    let deliveries = events_with_phase(&trace, "i");
+    let names: Vec<_> = deliveries.iter().map(|e| e["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["Start", "Start", "Finish", "Finish"]);
+    assert_eq!(deliveries[0]["ts"], 1e6);
+    assert_eq!(deliveries[0]["pid"], 1);
+    assert_eq!(deliveries[0]["args"]["src"], 0);
+
+    // spans of the same component overlap
+    let starts = events_with_phase(&trace, "b");
+    let ends = events_with_phase(&trace, "e");
+    assert_eq!(starts.len(), 2);
+    assert_eq!(ends.len(), 2);
+    for (start, end, time) in [(starts[0], ends[0], 1.), (starts[1], ends[1], 2.)] {
+        assert_eq!(start["id"], end["id"]);
+        assert_eq!(start["name"], end["name"]);
+        assert_eq!(start["ts"].as_f64().unwrap(), time * 1e6);
+        assert_eq!(end["ts"].as_f64().unwrap(), (time + 2.) * 1e6);
+    }
+    assert_eq!(starts[1]["name"], "task 2");
+}
+
+#[test]
+fn test_save_trace() {
+    let sim = run(true);
+    let path = std::env::temp_dir().join("dslab-core-test-trace.json");
+    let path = path.to_str().unwrap();
+    sim.save_trace(path).unwrap();
+    let saved: Value = serde_json::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+    assert_eq!(saved, sim.trace().unwrap());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,336 @@
+//! Experiment consisting of multiple replications of simulation runs.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+
+use serde::Serialize;
+use threadpool::ThreadPool;
+
+use crate::stats::Summary;
+
+/// Scalar metrics collected from a single replication, by metric name.
+pub type Metrics = BTreeMap<String, f64>;
+
+/// Rule which determines the number of replications for each configuration.
+#[derive(Clone, Debug)]
+pub enum StoppingRule {
+    /// Runs the fixed number of replications (at least 1).
+    Replications(usize),
+    /// Runs replications until the relative precision of the specified metrics reaches the target value,
+    /// i.e. the ratio of confidence interval half-width to the absolute mean value is not greater than the target.
+    ///
+    /// If `metrics` is empty, the precision of all collected metrics is checked.
+    RelativePrecision {
+        /// Names of metrics to check.
+        metrics: Vec<String>,
+        /// Target relative precision, e.g. 0.05.
+        target: f64,
+        /// Minimum number of replications (at least 2).
+        min_replications: usize,
+        /// Maximum number of replications.
+        max_replications: usize,
+    },
+}
+
+impl StoppingRule {
+    fn min_replications(&self) -> usize {
+        match self {
+            StoppingRule::Replications(count) => *count,
+            StoppingRule::RelativePrecision { min_replications, .. } => *min_replications,
+        }
+    }
+
+    fn max_replications(&self) -> usize {
+        match self {
+            StoppingRule::Replications(count) => *count,
+            StoppingRule::RelativePrecision { max_replications, .. } => *max_replications,
+        }
+    }
+
+    // Checks whether the rule is satisfied after the specified replications.
+    fn is_satisfied(&self, replications: &[Metrics], confidence_level: f64) -> bool {
+        replications.len() >= self.min_replications()
+            && (replications.len() >= self.max_replications() || self.precision_reached(replications, confidence_level))
+    }
+
+    // Checks whether the target precision is reached after the specified replications.
+    fn precision_reached(&self, replications: &[Metrics], confidence_level: f64) -> bool {
+        match self {
+            StoppingRule::Replications(_) => true,
+            StoppingRule::RelativePrecision { metrics, target, .. } => {
+                let summary = summarize(replications, confidence_level);
+                if metrics.is_empty() {
+                    summary.values().all(|s| s.relative_precision() <= *target)
+                } else {
+                    metrics
+                        .iter()
+                        .all(|m| summary.get(m).is_some_and(|s| s.relative_precision() <= *target))
+                }
+            }
+        }
+    }
+}
+
+/// Results of replications of a single configuration.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigResults {
+    /// Configuration name.
+    pub name: String,
+    /// Metrics collected from each replication, in order of replication indices.
+    pub replications: Vec<Metrics>,
+    /// Summary of each metric.
+    pub summary: BTreeMap<String, Summary>,
+    /// Whether the target precision of stopping rule is reached
+    /// (false if the maximum number of replications is reached first).
+    pub precision_reached: bool,
+}
+
+/// Results of experiment.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExperimentResults {
+    /// Results for each configuration, in order of configurations.
+    pub configs: Vec<ConfigResults>,
+}
+
+impl ExperimentResults {
+    /// Returns the results for configuration with specified name.
+    pub fn get(&self, name: &str) -> Option<&ConfigResults> {
+        self.configs.iter().find(|c| c.name == name)
+    }
+
+    /// Returns the text report with summary of each metric for each configuration.
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for config in self.configs.iter() {
+            writeln!(
+                report,
+                "{} ({} replications{})",
+                config.name,
+                config.replications.len(),
+                if config.precision_reached {
+                    ""
+                } else {
+                    ", target precision is not reached"
+                }
+            )
+            .unwrap();
+            for (metric, s) in config.summary.iter() {
+                writeln!(
+                    report,
+                    "  {}: mean {:.6}, stddev {:.6}, {:.0}% CI [{:.6}, {:.6}], min {:.6}, max {:.6}",
+                    metric,
+                    s.mean,
+                    s.stddev,
+                    s.confidence_level * 100.,
+                    s.ci_low(),
+                    s.ci_high(),
+                    s.min,
+                    s.max
+                )
+                .unwrap();
+            }
+        }
+        report
+    }
+
+    /// Prints the text report.
+    pub fn print_report(&self) {
+        print!("{}", self.report());
+    }
+
+    /// Saves results to JSON file.
+    pub fn save_to_file(&self, path: &str) -> Result<(), String> {
+        let file = std::fs::File::create(path).map_err(|e| format!("Failed to create file {}: {}", path, e))?;
+        serde_json::to_writer_pretty(file, self).map_err(|e| format!("Failed to write results: {}", e))
+    }
+}
+
+/// Represents experiment consisting of multiple replications of simulation runs for each configuration.
+///
+/// Each replication is performed by calling the user-defined run function with configuration and random seed,
+/// which returns the collected scalar metrics. Replications are run in parallel in a thread pool.
+/// Replication `i` of each configuration uses seed `seed + i` (wrapping around on overflow), so the configurations
+/// are compared using common random numbers.
+///
+/// The number of replications is determined by [`StoppingRule`]. When the rule depends on the achieved
+/// precision, the replications are run in batches until the rule is satisfied. The resulting number of replications
+/// is the smallest one satisfying the rule, so the results do not depend on the number of threads.
+///
+/// # Examples
+///
+/// ```rust
+/// use dslab_experiment::{Experiment, Metrics, StoppingRule};
+///
+/// let experiment = Experiment::new(vec![("slow".to_string(), 2.), ("fast".to_string(), 1.)])
+///     .with_stopping_rule(StoppingRule::Replications(10))
+///     .with_threads(2);
+/// let results = experiment.run(|delay, seed| {
+///     let mut metrics = Metrics::new();
+///     metrics.insert("time".to_string(), delay * (1. + (seed % 3) as f64 / 10.));
+///     metrics
+/// });
+/// assert_eq!(results.get("slow").unwrap().replications.len(), 10);
+/// assert!(results.get("slow").unwrap().summary["time"].mean > results.get("fast").unwrap().summary["time"].mean);
+/// ```
+pub struct Experiment<C> {
+    configs: Vec<(String, C)>,
+    seed: u64,
+    stopping_rule: StoppingRule,
+    confidence_level: f64,
+    num_threads: usize,
+}
+
+impl<C: Send + Sync + 'static> Experiment<C> {
+    /// Creates new experiment with specified named configurations.
+    ///
+    /// By default 10 replications are run for each configuration with seeds starting from 0,
+    /// 95% confidence intervals are computed and the number of threads is equal to the number of available CPUs.
+    pub fn new(configs: Vec<(String, C)>) -> Self {
+        Self {
+            configs,
+            seed: 0,
+            stopping_rule: StoppingRule::Replications(10),
+            confidence_level: 0.95,
+            num_threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// Sets the seed of the first replication.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the stopping rule.
+    pub fn with_stopping_rule(mut self, stopping_rule: StoppingRule) -> Self {
+        match &stopping_rule {
+            StoppingRule::Replications(count) => {
+                assert!(*count > 0, "Number of replications should be positive");
+            }
+            StoppingRule::RelativePrecision {
+                min_replications,
+                max_replications,
+                ..
+            } => {
+                assert!(
+                    *min_replications >= 2,
+                    "At least 2 replications are needed to estimate precision"
+                );
+                assert!(
+                    min_replications <= max_replications,
+                    "Minimum number of replications should not exceed the maximum one"
+                );
+            }
+        }
+        self.stopping_rule = stopping_rule;
+        self
+    }
+
+    /// Sets the confidence level of confidence intervals.
+    pub fn with_confidence_level(mut self, confidence_level: f64) -> Self {
+        assert!(
+            confidence_level > 0. && confidence_level < 1.,
+            "Confidence level should be in (0, 1)"
+        );
+        self.confidence_level = confidence_level;
+        self
+    }
+
+    /// Sets the number of threads used to run replications.
+    pub fn with_threads(mut self, num_threads: usize) -> Self {
+        assert!(num_threads > 0, "Number of threads should be positive");
+        self.num_threads = num_threads;
+        self
+    }
+
+    /// Runs the experiment using the specified function, which runs a single replication
+    /// with the given configuration and seed and returns the collected metrics.
+    ///
+    /// Panics if some replication panics.
+    pub fn run<F>(self, run_fn: F) -> ExperimentResults
+    where
+        F: Fn(&C, u64) -> Metrics + Send + Sync + 'static,
+    {
+        let pool = ThreadPool::new(self.num_threads);
+        let run_fn = Arc::new(run_fn);
+        let names: Vec<String> = self.configs.iter().map(|(name, _)| name.clone()).collect();
+        let configs: Arc<Vec<C>> = Arc::new(self.configs.into_iter().map(|(_, config)| config).collect());
+
+        let mut replications: Vec<Vec<Metrics>> = vec![Vec::new(); configs.len()];
+        let mut active: Vec<usize> = (0..configs.len()).collect();
+        let mut batch_size = self.stopping_rule.min_replications();
+        while !active.is_empty() {
+            // run the next batch of replications for each active configuration
+            let (tx, rx) = channel();
+            let mut job_count = 0;
+            for &config_idx in active.iter() {
+                let start = replications[config_idx].len();
+                let end = (start + batch_size).min(self.stopping_rule.max_replications());
+                for idx in start..end {
+                    let tx = tx.clone();
+                    let configs = configs.clone();
+                    let run_fn = run_fn.clone();
+                    let seed = self.seed.wrapping_add(idx as u64);
+                    pool.execute(move || {
+                        let metrics = run_fn(&configs[config_idx], seed);
+                        tx.send((config_idx, idx, metrics)).unwrap();
+                    });
+                    job_count += 1;
+                }
+            }
+            drop(tx);
+            let mut batch: Vec<(usize, usize, Metrics)> = rx.iter().collect();
+            assert_eq!(batch.len(), job_count, "Some replications failed");
+            batch.sort_by_key(|(config_idx, idx, _)| (*config_idx, *idx));
+            for (config_idx, _, metrics) in batch {
+                replications[config_idx].push(metrics);
+            }
+
+            // find the smallest number of replications satisfying the stopping rule
+            active.retain(|&config_idx| {
+                let results = &mut replications[config_idx];
+                let stop = (1..=results.len()).find(|&count| {
+                    self.stopping_rule
+                        .is_satisfied(&results[..count], self.confidence_level)
+                });
+                match stop {
+                    Some(count) => {
+                        results.truncate(count);
+                        false
+                    }
+                    None => true,
+                }
+            });
+            batch_size = self.num_threads.div_ceil(active.len().max(1));
+        }
+
+        let configs = names
+            .into_iter()
+            .zip(replications)
+            .map(|(name, replications)| ConfigResults {
+                name,
+                summary: summarize(&replications, self.confidence_level),
+                precision_reached: self
+                    .stopping_rule
+                    .precision_reached(&replications, self.confidence_level),
+                replications,
+            })
+            .collect();
+        ExperimentResults { configs }
+    }
+}
+
+fn summarize(replications: &[Metrics], confidence_level: f64) -> BTreeMap<String, Summary> {
+    let mut values: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for metrics in replications {
+        for (name, value) in metrics {
+            values.entry(name.clone()).or_default().push(*value);
+        }
+    }
+    values
+        .into_iter()
+        .map(|(name, values)| (name, Summary::from_values(&values, confidence_level)))
+        .collect()
+}
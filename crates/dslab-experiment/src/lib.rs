@@ -0,0 +1,7 @@
+#![doc = include_str!("../readme.md")]
+
+pub mod experiment;
+pub mod stats;
+
+pub use experiment::{ConfigResults, Experiment, ExperimentResults, Metrics, StoppingRule};
+pub use stats::Summary;
@@ -0,0 +1,200 @@
+//! Statistical summary of replication results.
+
+use serde::Serialize;
+
+/// Summary statistics of metric values collected from multiple replications.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Summary {
+    /// Number of values.
+    pub count: usize,
+    /// Sample mean.
+    pub mean: f64,
+    /// Sample standard deviation (zero if there is a single value).
+    pub stddev: f64,
+    /// Minimum value.
+    pub min: f64,
+    /// Maximum value.
+    pub max: f64,
+    /// Confidence level of the confidence interval.
+    pub confidence_level: f64,
+    /// Half-width of the confidence interval for the mean (infinite if there is a single value).
+    pub ci_half_width: f64,
+}
+
+impl Summary {
+    /// Computes summary of the specified values.
+    ///
+    /// The confidence interval for the mean is computed using Student's t-distribution.
+    ///
+    /// Panics if there are no values or the confidence level is not in (0, 1).
+    pub fn from_values(values: &[f64], confidence_level: f64) -> Self {
+        assert!(!values.is_empty(), "Cannot compute summary of empty sample");
+        assert!(
+            confidence_level > 0. && confidence_level < 1.,
+            "Confidence level should be in (0, 1)"
+        );
+        let count = values.len();
+        let n = count as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let stddev = if count > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.)).sqrt()
+        } else {
+            0.
+        };
+        let ci_half_width = if count > 1 {
+            student_t_quantile((1. + confidence_level) / 2., n - 1.) * stddev / n.sqrt()
+        } else {
+            f64::INFINITY
+        };
+        Self {
+            count,
+            mean,
+            stddev,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            confidence_level,
+            ci_half_width,
+        }
+    }
+
+    /// Returns the lower bound of the confidence interval.
+    pub fn ci_low(&self) -> f64 {
+        self.mean - self.ci_half_width
+    }
+
+    /// Returns the upper bound of the confidence interval.
+    pub fn ci_high(&self) -> f64 {
+        self.mean + self.ci_half_width
+    }
+
+    /// Returns the relative precision, i.e. the ratio of confidence interval half-width to the absolute mean value.
+    ///
+    /// Returns zero if the confidence interval has zero width, and infinity if the mean is zero otherwise,
+    /// so the precision of metrics with zero mean can be reached only if all values are equal.
+    pub fn relative_precision(&self) -> f64 {
+        if self.ci_half_width == 0. {
+            0.
+        } else if self.mean == 0. {
+            f64::INFINITY
+        } else {
+            self.ci_half_width / self.mean.abs()
+        }
+    }
+}
+
+/// Returns the quantile of Student's t-distribution with `df` degrees of freedom for probability `p`.
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    assert!(p > 0. && p < 1., "Probability should be in (0, 1)");
+    if p < 0.5 {
+        return -student_t_quantile(1. - p, df);
+    }
+    // the CDF is monotonic, so the quantile is found by bisection
+    let mut low = 0.;
+    let mut high = 1.;
+    while student_t_cdf(high, df) < p {
+        low = high;
+        high *= 2.;
+    }
+    for _ in 0..100 {
+        let mid = (low + high) / 2.;
+        if student_t_cdf(mid, df) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.
+}
+
+fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * incomplete_beta(df / 2., 0.5, df / (df + t * t));
+    if t >= 0. {
+        1. - tail
+    } else {
+        tail
+    }
+}
+
+// Regularized incomplete beta function I_x(a, b) (see Numerical Recipes, 6.4).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1. - x).ln()).exp();
+    if x < (a + 1.) / (a + b + 2.) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - front * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPS: f64 = 1e-15;
+    const FPMIN: f64 = 1e-300;
+    let qab = a + b;
+    let qap = a + 1.;
+    let qam = a - 1.;
+    let mut c = 1.;
+    let mut d = 1. - qab * x / qap;
+    if d.abs() < FPMIN {
+        d = FPMIN;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2. * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1. + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1. + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1. / d;
+        h *= d * c;
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1. + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1. + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+// Lanczos approximation of ln(Gamma(x)) for x > 0.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut y = x;
+    let mut series = 1.000000000190015;
+    for coeff in COEFFS {
+        y += 1.;
+        series += coeff / y;
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
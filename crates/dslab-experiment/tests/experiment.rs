@@ -0,0 +1,188 @@
+use dslab_experiment::stats::student_t_quantile;
+use dslab_experiment::{Experiment, Metrics, StoppingRule, Summary};
+
+fn metrics(values: &[(&str, f64)]) -> Metrics {
+    values.iter().map(|(name, value)| (name.to_string(), *value)).collect()
+}
+
+// Simple deterministic pseudo-random value in [0, 1) derived from seed.
+fn noise(seed: u64) -> f64 {
+    let x = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[test]
+fn test_student_t_quantile() {
+    let cases = [
+        (0.975, 1., 12.7062),
+        (0.975, 2., 4.3027),
+        (0.975, 9., 2.2622),
+        (0.95, 5., 2.0150),
+        (0.995, 29., 2.7564),
+        (0.975, 1000., 1.9623),
+    ];
+    for (p, df, expected) in cases {
+        let q = student_t_quantile(p, df);
+        assert!(
+            (q - expected).abs() < 1e-3,
+            "t({}, {}) = {}, expected {}",
+            p,
+            df,
+            q,
+            expected
+        );
+        assert!((student_t_quantile(1. - p, df) + q).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_summary() {
+    let summary = Summary::from_values(&[1., 2., 3., 4., 5.], 0.95);
+    assert_eq!(summary.count, 5);
+    assert_eq!(summary.mean, 3.);
+    assert!((summary.stddev - 2.5f64.sqrt()).abs() < 1e-12);
+    assert_eq!(summary.min, 1.);
+    assert_eq!(summary.max, 5.);
+    // t(0.975, 4) = 2.7764
+    let expected = 2.7764 * 2.5f64.sqrt() / 5f64.sqrt();
+    assert!((summary.ci_half_width - expected).abs() < 1e-3);
+    assert!((summary.ci_low() + summary.ci_high() - 6.).abs() < 1e-12);
+    assert!((summary.relative_precision() - expected / 3.).abs() < 1e-3);
+
+    let single = Summary::from_values(&[7.], 0.95);
+    assert_eq!(single.stddev, 0.);
+    assert!(single.ci_half_width.is_infinite());
+
+    let constant = Summary::from_values(&[2., 2., 2.], 0.9);
+    assert_eq!(constant.ci_half_width, 0.);
+    assert_eq!(constant.relative_precision(), 0.);
+
+    let zero_mean = Summary::from_values(&[-1., 1.], 0.95);
+    assert_eq!(zero_mean.relative_precision(), f64::INFINITY);
+}
+
+#[test]
+fn test_fixed_replications() {
+    let configs = vec![("a".to_string(), 1.), ("b".to_string(), 10.)];
+    let results = Experiment::new(configs)
+        .with_seed(100)
+        .with_stopping_rule(StoppingRule::Replications(5))
+        .with_threads(3)
+        .run(|scale, seed| metrics(&[("seed", seed as f64), ("value", scale * noise(seed))]));
+
+    assert_eq!(results.configs.len(), 2);
+    for config in results.configs.iter() {
+        let seeds: Vec<f64> = config.replications.iter().map(|m| m["seed"]).collect();
+        assert_eq!(seeds, vec![100., 101., 102., 103., 104.]);
+        assert!(config.precision_reached);
+    }
+    // common random numbers are used for all configurations
+    let a = results.get("a").unwrap();
+    let b = results.get("b").unwrap();
+    for (ma, mb) in a.replications.iter().zip(b.replications.iter()) {
+        assert!((mb["value"] - 10. * ma["value"]).abs() < 1e-12);
+    }
+    assert!(results.get("c").is_none());
+    assert!(results.report().contains("a (5 replications)"));
+}
+
+#[test]
+fn test_seed_overflow() {
+    let first_seed = u64::MAX - 1;
+    let results = Experiment::new(vec![("a".to_string(), 1.)])
+        .with_seed(first_seed)
+        .with_stopping_rule(StoppingRule::Replications(3))
+        .run(move |_, seed| metrics(&[("offset", seed.wrapping_sub(first_seed) as f64)]));
+
+    let offsets: Vec<f64> = results.configs[0].replications.iter().map(|m| m["offset"]).collect();
+    assert_eq!(offsets, vec![0., 1., 2.]);
+}
+
+#[test]
+fn test_relative_precision() {
+    let rule = StoppingRule::RelativePrecision {
+        metrics: vec!["value".to_string()],
+        target: 0.05,
+        min_replications: 3,
+        max_replications: 1000,
+    };
+    let run = |spread: &f64, seed: u64| metrics(&[("value", 10. + spread * noise(seed)), ("other", noise(seed))]);
+    let configs = vec![("narrow".to_string(), 1.), ("wide".to_string(), 8.)];
+    let results = Experiment::new(configs.clone())
+        .with_stopping_rule(rule.clone())
+        .with_threads(4)
+        .run(run);
+
+    let narrow = results.get("narrow").unwrap();
+    let wide = results.get("wide").unwrap();
+    assert!(wide.replications.len() > narrow.replications.len());
+    for config in results.configs.iter() {
+        assert!(config.precision_reached);
+        assert!(config.summary["value"].relative_precision() <= 0.05);
+        // the smallest number of replications is used
+        let n = config.replications.len();
+        let prev: Vec<f64> = config.replications[..n - 1].iter().map(|m| m["value"]).collect();
+        assert!(n == 3 || Summary::from_values(&prev, 0.95).relative_precision() > 0.05);
+    }
+
+    // the results do not depend on the number of threads
+    let results1 = Experiment::new(configs)
+        .with_stopping_rule(rule)
+        .with_threads(1)
+        .run(run);
+    assert_eq!(results1.get("wide").unwrap().replications, wide.replications);
+}
+
+#[test]
+fn test_max_replications() {
+    let rule = StoppingRule::RelativePrecision {
+        metrics: vec![],
+        target: 0.001,
+        min_replications: 2,
+        max_replications: 6,
+    };
+    let results = Experiment::new(vec![("noisy".to_string(), ())])
+        .with_stopping_rule(rule)
+        .with_threads(4)
+        .run(|_, seed| metrics(&[("value", noise(seed))]));
+    let noisy = results.get("noisy").unwrap();
+    assert_eq!(noisy.replications.len(), 6);
+    assert!(!noisy.precision_reached);
+    assert!(results.report().contains("target precision is not reached"));
+}
+
+#[test]
+#[should_panic(expected = "Number of replications should be positive")]
+fn test_zero_replications() {
+    Experiment::new(vec![("a".to_string(), ())]).with_stopping_rule(StoppingRule::Replications(0));
+}
+
+#[test]
+fn test_zero_mean() {
+    let rule = StoppingRule::RelativePrecision {
+        metrics: vec![],
+        target: 0.05,
+        min_replications: 2,
+        max_replications: 10,
+    };
+    let results = Experiment::new(vec![("zero".to_string(), ())])
+        .with_stopping_rule(rule)
+        .run(|_, seed| metrics(&[("value", if seed % 2 == 0 { 1. } else { -1. })]));
+    let zero = results.get("zero").unwrap();
+    assert_eq!(zero.replications.len(), 10);
+    assert!(!zero.precision_reached);
+}
+
+#[test]
+#[should_panic(expected = "Some replications failed")]
+fn test_failed_replication() {
+    Experiment::new(vec![("a".to_string(), ())])
+        .with_stopping_rule(StoppingRule::Replications(4))
+        .with_threads(2)
+        .run(|_, seed| {
+            if seed == 2 {
+                panic!("replication failed");
+            }
+            Metrics::new()
+        });
+}
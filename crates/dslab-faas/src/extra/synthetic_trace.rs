@@ -1,9 +1,30 @@
 //! Synthetic trace generator.
+//!
+//! The generator settings can be also read from YAML file via [`SyntheticTraceConfig::from_yaml`],
+//! where the random values are described by [`DistributionSpec`], e.g.:
+//!
+//! ```yaml
+//! memory_name: mem
+//! random_seed: 1
+//! apps:
+//!   - activity_window: [0.0, 100.0]
+//!     arrival_interval: { type: exponential, rate: 1.0 }
+//!     cold_start_latency: 0.1
+//!     concurrency_level: 1
+//!     cpu_share: 1.0
+//!     duration: { type: log_normal, mu: -0.38, sigma: 2.36 }
+//!     memory: { type: constant, value: 128 }
+//! ```
 use std::boxed::Box;
+use std::fs::File;
 use std::iter::zip;
+use std::path::Path;
 
 use rand::prelude::*;
 use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+
+use dslab_core::distribution::DistributionSpec;
 
 use crate::trace::{ApplicationData, RequestData, Trace};
 
@@ -75,6 +96,75 @@ pub struct SyntheticTraceConfig {
     pub random_seed: u64,
 }
 
+/// Similar to [`SyntheticTraceAppConfig`], but is YAML-serializable.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RawSyntheticTraceAppConfig {
+    /// Time interval that will contain all arrival times.
+    pub activity_window: (f64, f64),
+    /// Distribution of intervals between consecutive arrivals.
+    pub arrival_interval: DistributionSpec,
+    /// Container cold start latency.
+    pub cold_start_latency: f64,
+    /// App concurrency level.
+    pub concurrency_level: usize,
+    /// Container CPU share.
+    pub cpu_share: f64,
+    /// Distribution of invocation durations.
+    pub duration: DistributionSpec,
+    /// Distribution of container memory requirement, the sampled value is rounded to the nearest integer.
+    pub memory: DistributionSpec,
+}
+
+/// Similar to [`SyntheticTraceConfig`], but is YAML-serializable.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RawSyntheticTraceConfig {
+    /// Raw application generator configs.
+    pub apps: Vec<RawSyntheticTraceAppConfig>,
+    /// Memory resource name.
+    pub memory_name: String,
+    /// Random generator seed.
+    pub random_seed: u64,
+}
+
+impl SyntheticTraceConfig {
+    /// Creates SyntheticTraceConfig from RawSyntheticTraceConfig.
+    ///
+    /// Panics if some distribution specification is invalid.
+    pub fn from_raw(raw: RawSyntheticTraceConfig) -> Self {
+        let build = |spec: DistributionSpec, app: usize| {
+            spec.build()
+                .unwrap_or_else(|e| panic!("Can't build distribution for app {}: {}", app, e))
+        };
+        let apps = raw
+            .apps
+            .into_iter()
+            .enumerate()
+            .map(|(app, config)| SyntheticTraceAppConfig {
+                activity_window: config.activity_window,
+                arrival_generator: ArrivalGenerator::Random(Box::new(build(config.arrival_interval, app))),
+                cold_start_latency: config.cold_start_latency,
+                concurrency_level: config.concurrency_level,
+                cpu_share: config.cpu_share,
+                duration_generator: DurationGenerator::Random(Box::new(build(config.duration, app))),
+                memory_generator: MemoryGenerator::Random(Box::new(
+                    build(config.memory, app).map(|memory| memory.round() as u64),
+                )),
+            })
+            .collect();
+        Self {
+            apps,
+            memory_name: raw.memory_name,
+            random_seed: raw.random_seed,
+        }
+    }
+
+    /// Reads SyntheticTraceConfig from YAML file.
+    pub fn from_yaml(path: &Path) -> Self {
+        let f = File::open(path).unwrap();
+        Self::from_raw(serde_yaml::from_reader(f).unwrap())
+    }
+}
+
 /// Synthetically generated trace.
 #[derive(Clone, Default)]
 pub struct SyntheticTrace {
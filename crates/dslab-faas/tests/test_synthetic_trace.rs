@@ -0,0 +1,69 @@
+use dslab_faas::extra::synthetic_trace::{generate_synthetic_trace, SyntheticTraceConfig};
+use dslab_faas::trace::Trace;
+
+const CONFIG: &str = "
+memory_name: mem
+random_seed: 1
+apps:
+  - activity_window: [0.0, 100.0]
+    arrival_interval: { type: constant, value: 10.0 }
+    cold_start_latency: 0.1
+    concurrency_level: 1
+    cpu_share: 1.0
+    duration: { type: uniform, min: 1.0, max: 2.0 }
+    memory: { type: constant, value: 256 }
+  - activity_window: [0.0, 100.0]
+    arrival_interval: { type: exponential, rate: 2.0 }
+    cold_start_latency: 0.5
+    concurrency_level: 2
+    cpu_share: 0.5
+    duration:
+      type: mixture
+      components:
+        - weight: 1.0
+          distribution: { type: constant, value: 0.5 }
+        - weight: 1.0
+          distribution: { type: log_normal, mu: 0.0, sigma: 1.0 }
+    memory: { type: uniform, min: 100.0, max: 200.0 }
+";
+
+fn write_config(name: &str, config: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("dslab-faas-{}-{}.yaml", name, std::process::id()));
+    std::fs::write(&path, config).unwrap();
+    path
+}
+
+#[test]
+fn test_synthetic_trace_from_yaml() {
+    let path = write_config("synthetic-trace", CONFIG);
+    let config = SyntheticTraceConfig::from_yaml(&path);
+    std::fs::remove_file(&path).unwrap();
+    let trace = generate_synthetic_trace(config);
+
+    let apps: Vec<_> = trace.app_iter().collect();
+    assert_eq!(apps.len(), 2);
+    assert_eq!(apps[0].container_resources, vec![("mem".to_string(), 256)]);
+    assert_eq!(apps[1].concurrent_invocations, 2);
+    assert!((100..=200).contains(&apps[1].container_resources[0].1));
+
+    let requests: Vec<_> = trace.request_iter().collect();
+    let first_app_times: Vec<f64> = requests.iter().filter(|r| r.id == 0).map(|r| r.time).collect();
+    assert_eq!(first_app_times, (0..=10).map(|i| i as f64 * 10.).collect::<Vec<_>>());
+    assert!(requests
+        .iter()
+        .filter(|r| r.id == 0)
+        .all(|r| (1. ..2.).contains(&r.duration)));
+    let second_app_count = requests.iter().filter(|r| r.id == 1).count();
+    assert!(second_app_count > 100 && second_app_count < 300);
+    assert!(requests.windows(2).all(|w| w[0].time <= w[1].time));
+}
+
+#[test]
+#[should_panic(expected = "Can't build distribution for app 0")]
+fn test_synthetic_trace_invalid_distribution() {
+    let config = CONFIG.replace("{ type: constant, value: 10.0 }", "{ type: exponential, rate: -1.0 }");
+    let path = write_config("invalid-synthetic-trace", &config);
+    let raw = serde_yaml::from_reader(std::fs::File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    SyntheticTraceConfig::from_raw(raw);
+}
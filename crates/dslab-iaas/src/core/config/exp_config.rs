@@ -8,6 +8,8 @@ use std::rc::Rc;
 use serde::{Deserialize, Serialize};
 use sugars::{rc, refcell};
 
+use dslab_core::distribution::DistributionSpec;
+
 use crate::core::config::dynamic_variable::{DynVar, GenericDynVar, GenericValues, NumericValues};
 use crate::core::config::sim_config::{HostConfig, SchedulerConfig, SimulationConfig, VmDatasetConfig};
 
@@ -19,6 +21,8 @@ struct RawExperimentConfig {
     pub allocation_retry_period: Option<NumericValues<f64>>,
     pub vm_start_duration: Option<NumericValues<f64>>,
     pub vm_stop_duration: Option<NumericValues<f64>>,
+    pub vm_start_duration_distribution: Option<DistributionSpec>,
+    pub vm_stop_duration_distribution: Option<DistributionSpec>,
     pub allow_vm_overcommit: Option<bool>,
     pub network_throughput: Option<NumericValues<u64>>,
     pub simulation_length: Option<NumericValues<f64>>,
@@ -52,6 +56,8 @@ struct ConfigState {
     pub allocation_retry_period: Rc<RefCell<GenericDynVar<f64>>>,
     pub vm_start_duration: Rc<RefCell<GenericDynVar<f64>>>,
     pub vm_stop_duration: Rc<RefCell<GenericDynVar<f64>>>,
+    pub vm_start_duration_distribution: Option<DistributionSpec>,
+    pub vm_stop_duration_distribution: Option<DistributionSpec>,
     pub allow_vm_overcommit: bool,
     pub network_throughput: Rc<RefCell<GenericDynVar<u64>>>,
     pub simulation_length: Rc<RefCell<GenericDynVar<f64>>>,
@@ -202,6 +208,8 @@ impl ExperimentConfig {
             allocation_retry_period,
             vm_start_duration,
             vm_stop_duration,
+            vm_start_duration_distribution: current_state_raw.vm_start_duration_distribution,
+            vm_stop_duration_distribution: current_state_raw.vm_stop_duration_distribution,
             allow_vm_overcommit: current_state_raw.allow_vm_overcommit.unwrap_or(false),
             network_throughput,
             simulation_length,
@@ -246,6 +254,8 @@ impl ExperimentConfig {
             allocation_retry_period: self.current_state.allocation_retry_period.borrow().value(),
             vm_start_duration: self.current_state.vm_start_duration.borrow().value(),
             vm_stop_duration: self.current_state.vm_stop_duration.borrow().value(),
+            vm_start_duration_distribution: self.current_state.vm_start_duration_distribution.clone(),
+            vm_stop_duration_distribution: self.current_state.vm_stop_duration_distribution.clone(),
             allow_vm_overcommit: self.current_state.allow_vm_overcommit,
             network_throughput: self.current_state.network_throughput.borrow().value(),
             simulation_length: self.current_state.simulation_length.borrow().value(),
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use dslab_core::distribution::{DistributionSampler, DistributionSpec};
+
 use crate::extensions::dataset_type::VmDatasetType;
 
 /// Holds raw simulation config parsed from YAML file.
@@ -12,6 +14,8 @@ struct RawSimulationConfig {
     pub allocation_retry_period: Option<f64>,
     pub vm_start_duration: Option<f64>,
     pub vm_stop_duration: Option<f64>,
+    pub vm_start_duration_distribution: Option<DistributionSpec>,
+    pub vm_stop_duration_distribution: Option<DistributionSpec>,
     pub allow_vm_overcommit: Option<bool>,
     pub network_throughput: Option<u64>,
     pub simulation_length: Option<f64>,
@@ -84,6 +88,10 @@ pub struct SimulationConfig {
     pub vm_start_duration: f64,
     /// VM stop duration in seconds.
     pub vm_stop_duration: f64,
+    /// Distribution of VM start duration in seconds, overrides the constant `vm_start_duration` if specified.
+    pub vm_start_duration_distribution: Option<DistributionSpec>,
+    /// Distribution of VM stop duration in seconds, overrides the constant `vm_stop_duration` if specified.
+    pub vm_stop_duration_distribution: Option<DistributionSpec>,
     /// Whether to schedule VMs based on real resource utilization instead of allocated resources.
     pub allow_vm_overcommit: bool,
     /// Network throughput in GB/s.
@@ -118,6 +126,8 @@ impl SimulationConfig {
             allocation_retry_period: raw.allocation_retry_period.unwrap_or(1.0),
             vm_start_duration: raw.vm_start_duration.unwrap_or(1.),
             vm_stop_duration: raw.vm_stop_duration.unwrap_or(0.5),
+            vm_start_duration_distribution: raw.vm_start_duration_distribution,
+            vm_stop_duration_distribution: raw.vm_stop_duration_distribution,
             allow_vm_overcommit: raw.allow_vm_overcommit.unwrap_or(false),
             network_throughput: raw.network_throughput.unwrap_or(1),
             simulation_length: raw.simulation_length.unwrap_or(0.),
@@ -128,4 +138,21 @@ impl SimulationConfig {
             schedulers: raw.schedulers.unwrap_or_default(),
         }
     }
+
+    /// Builds the distribution of VM start duration.
+    pub fn build_vm_start_duration(&self) -> Result<DistributionSampler, String> {
+        build_duration(self.vm_start_duration, &self.vm_start_duration_distribution)
+    }
+
+    /// Builds the distribution of VM stop duration.
+    pub fn build_vm_stop_duration(&self) -> Result<DistributionSampler, String> {
+        build_duration(self.vm_stop_duration, &self.vm_stop_duration_distribution)
+    }
+}
+
+fn build_duration(value: f64, distribution: &Option<DistributionSpec>) -> Result<DistributionSampler, String> {
+    match distribution {
+        Some(spec) => spec.build(),
+        None => DistributionSpec::Constant { value }.build(),
+    }
 }
@@ -10,6 +10,7 @@ use serde::Serialize;
 
 use dslab_core::cast;
 use dslab_core::context::SimulationContext;
+use dslab_core::distribution::DistributionSampler;
 use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
 use dslab_models::power::host::{HostPowerModel, HostState};
@@ -63,6 +64,8 @@ pub struct HostManager {
     allow_vm_overcommit: bool,
     power_model: HostPowerModel,
     slav_metric: Box<dyn HostSLAVMetric>,
+    vm_start_duration: DistributionSampler,
+    vm_stop_duration: DistributionSampler,
 
     ctx: SimulationContext,
     logger: Rc<RefCell<Box<dyn Logger>>>,
@@ -82,6 +85,8 @@ impl HostManager {
         allow_vm_overcommit: bool,
         power_model: HostPowerModel,
         slav_metric: Box<dyn HostSLAVMetric>,
+        vm_start_duration: DistributionSampler,
+        vm_stop_duration: DistributionSampler,
         ctx: SimulationContext,
         logger: Rc<RefCell<Box<dyn Logger>>>,
         sim_config: Rc<SimulationConfig>,
@@ -109,6 +114,8 @@ impl HostManager {
             allow_vm_overcommit,
             power_model,
             slav_metric,
+            vm_start_duration,
+            vm_stop_duration,
             ctx,
             logger,
             sim_config,
@@ -249,7 +256,7 @@ impl HostManager {
     fn on_allocation_request(&mut self, vm_id: u32) -> bool {
         if self.can_allocate(vm_id) == AllocationVerdict::Success {
             let vm = self.vm_api.borrow().get_vm(vm_id);
-            let start_duration = vm.borrow().start_duration();
+            let start_duration = self.ctx.sample_from_distribution(&self.vm_start_duration);
             self.allocate(self.ctx.time(), vm);
             self.recent_vm_status_changes.insert(vm_id, VmStatus::Initializing);
             self.logger
@@ -279,7 +286,7 @@ impl HostManager {
         if self.can_allocate(vm_id) == AllocationVerdict::Success {
             let vm = self.vm_api.borrow().get_vm(vm_id);
             let migration_duration = (vm.borrow().memory_usage as f64) / (self.sim_config.network_throughput as f64);
-            let start_duration = vm.borrow().start_duration();
+            let start_duration = self.ctx.sample_from_distribution(&self.vm_start_duration);
 
             self.allocate(self.ctx.time(), vm);
             self.logger.borrow_mut().log_debug(
@@ -319,8 +326,8 @@ impl HostManager {
             if !is_migrating {
                 self.recent_vm_status_changes.insert(vm_id, VmStatus::Finished);
             }
-            let vm = self.vm_api.borrow().get_vm(vm_id).borrow().clone();
-            self.ctx.emit_self(VMDeleted { vm_id }, vm.stop_duration());
+            let stop_duration = self.ctx.sample_from_distribution(&self.vm_stop_duration);
+            self.ctx.emit_self(VMDeleted { vm_id }, stop_duration);
         } else {
             self.logger.borrow_mut().log_trace(
                 &self.ctx,
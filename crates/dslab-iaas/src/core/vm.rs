@@ -139,11 +139,15 @@ impl VirtualMachine {
     }
 
     /// Returns VM start duration (the value is taken from the simulation config).
+    ///
+    /// If `vm_start_duration_distribution` is specified in the config, the actual start durations are sampled from it.
     pub fn start_duration(&self) -> f64 {
         self.sim_config.vm_start_duration
     }
 
     /// Returns VM stop duration (the value is taken from the simulation config).
+    ///
+    /// If `vm_stop_duration_distribution` is specified in the config, the actual stop durations are sampled from it.
     pub fn stop_duration(&self) -> f64 {
         self.sim_config.vm_stop_duration
     }
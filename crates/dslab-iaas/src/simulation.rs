@@ -8,6 +8,7 @@ use std::rc::Rc;
 use sugars::{rc, refcell};
 
 use dslab_core::context::SimulationContext;
+use dslab_core::distribution::DistributionSampler;
 use dslab_core::simulation::Simulation;
 use dslab_core::Id;
 use dslab_models::power::cpu_models::linear::LinearCpuPowerModel;
@@ -52,6 +53,8 @@ pub struct CloudSimulation {
     components: HashMap<u32, Rc<RefCell<dyn CustomComponent>>>,
     host_power_model: HostPowerModel,
     slav_metric: Box<dyn HostSLAVMetric>,
+    vm_start_duration: DistributionSampler,
+    vm_stop_duration: DistributionSampler,
     batch_mode: bool,
     batch_buffer: Vec<VMSpawnRequest>,
     logger: Rc<RefCell<Box<dyn Logger>>>,
@@ -88,6 +91,13 @@ impl CloudSimulation {
         )));
         sim.add_handler("placement_store", placement_store.clone());
 
+        let vm_start_duration = sim_config
+            .build_vm_start_duration()
+            .unwrap_or_else(|e| panic!("Invalid VM start duration distribution: {}", e));
+        let vm_stop_duration = sim_config
+            .build_vm_stop_duration()
+            .unwrap_or_else(|e| panic!("Invalid VM stop duration distribution: {}", e));
+
         let ctx = sim.create_context("simulation");
         let mut sim = Self {
             monitoring,
@@ -100,6 +110,8 @@ impl CloudSimulation {
                 .cpu(Box::new(LinearCpuPowerModel::new(0.4, 1.)))
                 .build(),
             slav_metric: Box::new(OverloadTimeFraction::new()),
+            vm_start_duration,
+            vm_stop_duration,
             batch_mode: false,
             batch_buffer: Vec::new(),
             logger,
@@ -180,6 +192,8 @@ impl CloudSimulation {
             self.sim_config.allow_vm_overcommit,
             self.host_power_model.clone(),
             self.slav_metric.clone(),
+            self.vm_start_duration.clone(),
+            self.vm_stop_duration.clone(),
             self.sim.create_context(name),
             self.logger.clone(),
             self.sim_config.clone(),
@@ -454,3 +454,32 @@ fn test_batch_request() {
     assert_eq!(cloud_sim.vm_location(vm_ids[1]), Some(h));
     assert_eq!(cloud_sim.vm_location(vm_ids[2]), Some(h));
 }
+
+#[test]
+// VM start durations are sampled from the uniform distribution in [1, 2] specified in the config,
+// so the VMs allocated at the same time are started at different times.
+fn test_random_vm_durations() {
+    let spawn_vms = |config: &str| {
+        let sim = Simulation::new(123);
+        let sim_config = SimulationConfig::from_file(&name_wrapper(config));
+        let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+        cloud_sim.add_host("h", 100, 100);
+        let s = cloud_sim.add_scheduler("s", VMPlacementAlgorithm::single(FirstFit::new()));
+        let vms: Vec<u32> = (0..5)
+            .map(|_| cloud_sim.spawn_vm_now(ResourceConsumer::with_full_load(10, 10), 10.0, None, s))
+            .collect();
+        cloud_sim.step_for_duration(5.);
+        let start_times: Vec<f64> = vms.iter().map(|&vm| cloud_sim.vm(vm).borrow().start_time()).collect();
+        cloud_sim.step_for_duration(15.);
+        assert!(vms.iter().all(|&vm| cloud_sim.vm_status(vm) == VmStatus::Finished));
+        start_times
+    };
+
+    // the VMs are allocated one second before the start with constant duration
+    let allocation_time = spawn_vms("config.yaml")[0] - 1.;
+    let start_times = spawn_vms("config_random_durations.yaml");
+    for &start_time in start_times.iter() {
+        assert!(start_time >= allocation_time + 1. && start_time <= allocation_time + 2.);
+    }
+    assert!(start_times.windows(2).any(|w| w[0] != w[1]));
+}
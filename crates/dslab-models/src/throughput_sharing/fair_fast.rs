@@ -90,6 +90,48 @@ impl<T> FairThroughputSharingModel<T> {
         }
     }
 
+    /// Removes activities for which `pred` returns true at `ctx.time()`,
+    /// the throughput of remaining activities is updated accordingly.
+    ///
+    /// Returns the removed activities in the order of their insertion along with their remaining amount of work.
+    /// The amount of work is scaled by the activity factor (see [`ActivityFactorFn`]).
+    pub fn remove<F: Fn(&T) -> bool>(&mut self, pred: F, ctx: &SimulationContext) -> Vec<(T, f64)> {
+        if self.activities.is_empty() {
+            return Vec::new();
+        }
+        self.increment_total_work((ctx.time() - self.last_update) * self.throughput_per_activity);
+        self.last_update = ctx.time();
+        let (mut removed, remaining): (Vec<_>, Vec<_>) = std::mem::take(&mut self.activities)
+            .into_vec()
+            .into_iter()
+            .partition(|activity| pred(&activity.item));
+        self.activities = remaining.into();
+        let count = self.activities.len();
+        if count > 0 {
+            self.throughput_per_activity = (self.throughput_function)(count) / count as f64;
+        } else {
+            self.throughput_per_activity = 0.;
+        }
+        removed.sort_by_key(|activity| activity.id);
+        let total_work = self.total_work;
+        removed
+            .into_iter()
+            .map(|activity| (activity.item, (activity.finish_work - total_work).max(0.)))
+            .collect()
+    }
+
+    /// Returns the remaining amount of work at `ctx.time()` for activities for which `pred` returns true.
+    ///
+    /// The amount of work is scaled by the activity factor (see [`ActivityFactorFn`]).
+    pub fn remaining_volume<F: Fn(&T) -> bool>(&self, pred: F, ctx: &SimulationContext) -> Vec<(&T, f64)> {
+        let total_work = self.total_work + (ctx.time() - self.last_update) * self.throughput_per_activity;
+        self.activities
+            .iter()
+            .filter(|activity| pred(&activity.item))
+            .map(|activity| (&activity.item, (activity.finish_work - total_work).max(0.)))
+            .collect()
+    }
+
     fn increment_total_work(&mut self, delta: f64) {
         self.total_work += delta;
         if self.total_work > TOTAL_WORK_MAX_VALUE {
@@ -4,6 +4,7 @@ use rand::distributions::{Distribution, Uniform, WeightedError, WeightedIndex};
 use sugars::boxed;
 
 use dslab_core::context::SimulationContext;
+use dslab_core::distribution::{DistributionSampler, DistributionSpec};
 
 use crate::throughput_sharing::model::ActivityFactorFn;
 use crate::throughput_sharing::ResourceThroughputFn;
@@ -58,6 +59,12 @@ pub fn make_uniform_factor_fn(low: f64, high: f64) -> RandomizedFactorFn<Uniform
     RandomizedFactorFn::new(Uniform::<f64>::new(low, high))
 }
 
+/// Creates randomized activity factor function with distribution built from the specification,
+/// e.g. read from configuration file.
+pub fn make_factor_fn_from_spec(spec: &DistributionSpec) -> Result<RandomizedFactorFn<DistributionSampler>, String> {
+    spec.build().map(RandomizedFactorFn::new)
+}
+
 /// Activity factor function which generates random factor from the specified weighted points distribution.
 pub struct EmpiricalFactorFn {
     /// Factor values.
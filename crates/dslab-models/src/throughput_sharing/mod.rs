@@ -11,6 +11,7 @@ mod tests;
 pub use fair_fast::FairThroughputSharingModel;
 pub use fair_slow::SlowFairThroughputSharingModel;
 pub use functions::{
-    make_constant_throughput_fn, make_uniform_factor_fn, ConstantFactorFn, EmpiricalFactorFn, RandomizedFactorFn,
+    make_constant_throughput_fn, make_factor_fn_from_spec, make_uniform_factor_fn, ConstantFactorFn, EmpiricalFactorFn,
+    RandomizedFactorFn,
 };
 pub use model::{ActivityFactorFn, ResourceThroughputFn, ThroughputSharingModel};
@@ -1,10 +1,11 @@
 use sugars::boxed;
 
+use dslab_core::distribution::DistributionSpec;
 use dslab_core::{Simulation, SimulationContext};
 
 use super::fair_fast::FairThroughputSharingModel;
 use super::fair_slow::SlowFairThroughputSharingModel;
-use super::functions::make_constant_throughput_fn;
+use super::functions::{make_constant_throughput_fn, make_factor_fn_from_spec};
 use super::model::{ActivityFactorFn, ThroughputSharingModel};
 
 fn assert_float_eq(x: f64, y: f64, eps: f64) {
@@ -241,3 +242,47 @@ fn throughput_factor_and_degradation() {
     assert_eq!(model.pop(), Some((4.125, 0)));
     assert_eq!(model.pop(), Some((5.125, 1)));
 }
+
+#[test]
+fn throughput_factor_from_spec() {
+    let mut sim = Simulation::new(123);
+    let mut ctx = sim.create_context("test");
+    let spec = DistributionSpec::Uniform { min: 0.5, max: 1. };
+    let mut model: FairThroughputSharingModel<u32> = FairThroughputSharingModel::new(
+        make_constant_throughput_fn(100.),
+        boxed!(make_factor_fn_from_spec(&spec).unwrap()),
+    );
+    for i in 0..10 {
+        model.insert(i, 100., &mut ctx);
+    }
+    // each activity is completed in 10 / factor seconds
+    let mut finish_times = Vec::new();
+    while let Some((time, _)) = model.pop() {
+        assert!((10. ..=20.).contains(&time));
+        finish_times.push(time);
+    }
+    assert_eq!(finish_times.len(), 10);
+    assert!(finish_times.windows(2).any(|w| w[0] != w[1]));
+
+    let invalid = DistributionSpec::Exponential { rate: 0. };
+    assert!(make_factor_fn_from_spec(&invalid).is_err());
+}
+
+#[test]
+fn remove_activities() {
+    let mut sim = Simulation::new(123);
+    let mut ctx = sim.create_context("test");
+    let mut model: FairThroughputSharingModel<u32> = FairThroughputSharingModel::with_fixed_throughput(100.);
+    model.insert(0, 100., &mut ctx);
+    model.insert(1, 300., &mut ctx);
+    model.insert(2, 500., &mut ctx);
+    sim.step_until_time(1.5);
+    assert_eq!(model.remaining_volume(|item| *item == 1, &ctx), vec![(&1, 250.)]);
+    assert_eq!(model.remove(|item| *item != 1, &ctx), vec![(0, 50.), (2, 450.)]);
+    assert_eq!(model.remaining_volume(|_| true, &ctx), vec![(&1, 250.)]);
+    assert_eq!(model.peek(), Some((4., &1)));
+    sim.step_until_time(2.);
+    assert!(model.remove(|item| *item == 0, &ctx).is_empty());
+    assert_eq!(model.pop(), Some((4., 1)));
+    assert!(model.remove(|_| true, &ctx).is_empty());
+}
@@ -22,7 +22,7 @@ impl Client {
     }
 
     pub fn run(self: Rc<Self>) {
-        self.ctx.spawn(self.clone().submit_tasks())
+        self.ctx.spawn(self.clone().submit_tasks());
     }
 
     async fn submit_tasks(self: Rc<Self>) {
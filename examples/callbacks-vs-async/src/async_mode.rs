@@ -23,7 +23,7 @@ impl Process {
     }
 
     fn send_request(self: Rc<Self>, dst: Id) {
-        self.ctx.spawn(self.clone().send_request_and_get_response(dst))
+        self.ctx.spawn(self.clone().send_request_and_get_response(dst));
     }
 
     async fn send_request_and_get_response(self: Rc<Self>, dst: Id) {
@@ -44,7 +44,7 @@ impl StaticEventHandler for Process {
     fn on(self: Rc<Self>, event: Event) {
         cast!(match event.data {
             Request {} => {
-                self.ctx.spawn(self.clone().process_request(event.src))
+                self.ctx.spawn(self.clone().process_request(event.src));
             }
         })
     }
@@ -6,6 +6,8 @@ use std::rc::Rc;
 use serde::Serialize;
 use sugars::{rc, refcell};
 
+use dslab_compute::failure::FailureModel;
+use dslab_compute::migration::Migrator;
 use dslab_compute::multicore::*;
 use dslab_core::component::Id;
 use dslab_core::context::SimulationContext;
@@ -13,6 +15,10 @@ use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
 use dslab_core::simulation::Simulation;
 use dslab_core::{cast, log_error, log_info};
+use dslab_models::power::cpu_models::dvfs::DvfsAwareCpuPowerModel;
+use dslab_models::power::host::HostPowerModelBuilder;
+use dslab_network::models::{ConstantBandwidthNetworkModel, SharedBandwidthNetworkModel};
+use dslab_network::Network;
 
 #[derive(Clone, Serialize)]
 pub struct Start {}
@@ -78,6 +84,16 @@ impl EventHandler for Task {
                     cores
                 );
             }
+            CompRestored { id, cores } => {
+                println!(
+                    "{} [{}] received CompRestored from {} for {:?} on {} cores",
+                    self.ctx.time(),
+                    self.id,
+                    event.src,
+                    id,
+                    cores
+                );
+            }
             CompFinished { id } => {
                 println!(
                     "{} [{}] received CompFinished from {} for {:?}",
@@ -260,4 +276,174 @@ fn main() {
     ctx.emit(Start {}, allocator3_id, 6000.);
 
     sim.step_until_no_events();
+
+    run_time_shared();
+    run_dvfs();
+    run_failures();
+    run_migration();
+}
+
+// Runs 8 threads of three tasks on 4 cores in time-shared mode, so the tasks are slowed down until the first finishes.
+fn run_time_shared() {
+    println!("\nTime-shared mode:");
+    let mut sim = Simulation::new(123);
+
+    let compute = rc!(refcell!(
+        Compute::new(1., 4, 1024, sim.create_context("compute")).with_sharing_mode(SharingMode::TimeShared)
+    ));
+    sim.add_handler("compute", compute.clone());
+
+    let tasks = [("task1", 2), ("task2", 4), ("task3", 2)];
+    let ctx = sim.create_context("root");
+    for (name, cores) in tasks {
+        let task = Task::new(
+            compute.clone(),
+            100.,
+            256,
+            1,
+            cores,
+            CoresDependency::Linear,
+            sim.create_context(name),
+        );
+        let task_id = sim.add_handler(name, rc!(refcell!(task)));
+        ctx.emit(Start {}, task_id, 0.);
+    }
+
+    sim.step_until_no_events();
+}
+
+// Runs two tasks on a resource with DVFS, the frequency is lowered at time 5 which slows down the running task.
+fn run_dvfs() {
+    println!("\nDVFS:");
+    let mut sim = Simulation::new(123);
+
+    let power_model = HostPowerModelBuilder::new()
+        .cpu(Box::new(DvfsAwareCpuPowerModel::new(50., 50., 100.)))
+        .cpu_idle(40.)
+        .build();
+    let compute = rc!(refcell!(Compute::new(10., 4, 1024, sim.create_context("compute"))
+        .with_min_speed(5.)
+        .with_power_model(power_model)));
+    sim.add_handler("compute", compute.clone());
+
+    let tasks = [("task1", 100., 10.), ("task2", 200., 20.)];
+    let ctx = sim.create_context("root");
+    for (name, flops, delay) in tasks {
+        let task = Task::new(
+            compute.clone(),
+            flops,
+            256,
+            2,
+            2,
+            CoresDependency::Linear,
+            sim.create_context(name),
+        );
+        let task_id = sim.add_handler(name, rc!(refcell!(task)));
+        ctx.emit(Start {}, task_id, delay);
+    }
+
+    sim.step_until_time(12.);
+    compute.borrow_mut().set_frequency(0.);
+    println!("{} speed is set to {}", sim.time(), compute.borrow().speed());
+    sim.step_until_no_events();
+
+    let compute = compute.borrow();
+    println!(
+        "{} average utilization: {:.3}, energy consumed: {:.1}",
+        sim.time(),
+        compute.average_utilization(),
+        compute.energy_consumed()
+    );
+}
+
+// Runs three tasks on a resource which is down from time 5 to 10, so the first task is aborted
+// and the second one is rejected.
+fn run_failures() {
+    println!("\nFailures:");
+    let mut sim = Simulation::new(123);
+
+    let failure_model = FailureModel::from_trace(vec![(5., 10.)]).unwrap();
+    let compute = rc!(refcell!(
+        Compute::new(1., 4, 1024, sim.create_context("compute")).with_failure_model(failure_model)
+    ));
+    sim.add_handler("compute", compute.clone());
+
+    let tasks = [("task1", 0.), ("task2", 7.), ("task3", 12.)];
+    let ctx = sim.create_context("root");
+    for (name, delay) in tasks {
+        let task = Task::new(
+            compute.clone(),
+            40.,
+            256,
+            4,
+            4,
+            CoresDependency::Linear,
+            sim.create_context(name),
+        );
+        let task_id = sim.add_handler(name, rc!(refcell!(task)));
+        ctx.emit(Start {}, task_id, delay);
+    }
+
+    sim.step_until_no_events();
+}
+
+// Migrates the running task at time 5 to another resource with faster cores, the task checkpoint is transferred
+// over the network and the task is completed on the target resource.
+fn run_migration() {
+    println!("\nMigration:");
+    let mut sim = Simulation::new(123);
+
+    let network = rc!(refcell!(Network::new(
+        Box::new(ConstantBandwidthNetworkModel::new(100., 0.)),
+        sim.create_context("net")
+    )));
+    sim.add_handler("net", network.clone());
+
+    let overhead = CheckpointOverhead {
+        checkpoint_time: 1.,
+        restart_time: 2.,
+        bandwidth: 100.,
+    };
+    let mut computes = Vec::new();
+    for (host, speed) in [("host1", 10.), ("host2", 20.)] {
+        let name = format!("{}::compute", host);
+        let compute = rc!(refcell!(
+            Compute::new(speed, 4, 1024, sim.create_context(&name)).with_checkpoint_overhead(overhead)
+        ));
+        let compute_id = sim.add_handler(&name, compute.clone());
+        network
+            .borrow_mut()
+            .add_node(host, Box::new(SharedBandwidthNetworkModel::new(1000., 0.)));
+        network.borrow_mut().set_location(compute_id, host);
+        computes.push(compute);
+    }
+
+    let migrator = rc!(refcell!(
+        Migrator::new(sim.create_context("migrator")).with_network(network)
+    ));
+    sim.add_handler("migrator", migrator.clone());
+
+    let task = Task::new(
+        computes[0].clone(),
+        800.,
+        256,
+        4,
+        4,
+        CoresDependency::Linear,
+        sim.create_context("task"),
+    );
+    let task_id = sim.add_handler("task", rc!(refcell!(task)));
+    sim.create_context("root").emit(Start {}, task_id, 0.);
+
+    sim.step_until_time(5.);
+    let comp_id = 1;
+    println!(
+        "{} migrating computation with {:.2} fraction done",
+        sim.time(),
+        computes[0].borrow().fraction_done(comp_id).unwrap()
+    );
+    migrator
+        .borrow_mut()
+        .migrate(comp_id, &computes[0], computes[1].clone(), 200);
+    sim.step_until_no_events();
 }
@@ -0,0 +1,93 @@
+#![doc = include_str!("../readme.md")]
+
+use std::time::Instant;
+
+use clap::Parser;
+use serde::Serialize;
+use sugars::{rc, refcell};
+
+use dslab_core::event_queue::EventQueueType;
+use dslab_core::{cast, Event, EventHandler, Simulation, SimulationContext};
+
+/// Event queue benchmark
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Number of periodic timers (>= 1)
+    #[clap(long, default_value_t = 10000)]
+    timers_count: u32,
+
+    /// Number of events to process (>= 1)
+    #[clap(long, default_value_t = 1000000)]
+    events_count: u64,
+
+    /// Event queue implementation (heap, calendar, ladder or all)
+    #[clap(long, default_value = "all")]
+    queue: String,
+}
+
+#[derive(Clone, Serialize)]
+struct Timer {
+    period: f64,
+}
+
+struct TimerOwner {
+    ctx: SimulationContext,
+}
+
+impl EventHandler for TimerOwner {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            Timer { period } => {
+                let delay = period * self.ctx.gen_range(0.9..1.1);
+                self.ctx.emit_self(Timer { period }, delay);
+            }
+        })
+    }
+}
+
+fn run(queue_type: EventQueueType, args: &Args) {
+    let mut sim = Simulation::with_event_queue(123, queue_type.create());
+    for i in 0..args.timers_count {
+        let name = format!("timer_{}", i);
+        let owner = rc!(refcell!(TimerOwner {
+            ctx: sim.create_context(&name),
+        }));
+        let period = sim.gen_range(1.0..100.);
+        let start_delay = sim.gen_range(0.0..period);
+        owner.borrow().ctx.emit_self(Timer { period }, start_delay);
+        sim.add_handler(name, owner);
+    }
+
+    let t = Instant::now();
+    sim.steps(args.events_count);
+    let elapsed = t.elapsed().as_secs_f64();
+
+    println!(
+        "{:<12} processed {} events in {:.2}s ({:.0} events/s), simulation time: {:.3}",
+        format!("{:?}:", queue_type),
+        sim.event_count(),
+        elapsed,
+        sim.event_count() as f64 / elapsed,
+        sim.time()
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let queue_types = match args.queue.as_str() {
+        "heap" => vec![EventQueueType::BinaryHeap],
+        "calendar" => vec![EventQueueType::Calendar],
+        "ladder" => vec![EventQueueType::Ladder],
+        "all" => vec![
+            EventQueueType::BinaryHeap,
+            EventQueueType::Calendar,
+            EventQueueType::Ladder,
+        ],
+        _ => panic!("Unknown event queue: {}", args.queue),
+    };
+    for queue_type in queue_types {
+        run(queue_type, &args);
+    }
+}
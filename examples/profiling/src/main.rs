@@ -34,12 +34,19 @@ struct Args {
     /// Display messages count
     #[clap(long)]
     display_messages_count: bool,
+
+    /// Print the per-component profiling report
+    #[clap(long)]
+    profile_components: bool,
 }
 
 fn main() {
     let args = Args::parse();
 
     let mut sim = Simulation::new(123);
+    if args.profile_components {
+        sim.enable_profiling(1.);
+    }
 
     let mut clients = vec![];
     let mut clients_ids = vec![];
@@ -81,4 +88,9 @@ fn main() {
         elapsed,
         sim.event_count() as f64 / elapsed
     );
+
+    if args.profile_components {
+        println!();
+        sim.print_profiling_report();
+    }
 }